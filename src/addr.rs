@@ -0,0 +1,298 @@
+//! IP, socket, MAC and EUI-64 address accessors, all in network byte order.
+
+use crate::{BufView, BufViewMut};
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+use std::str::FromStr;
+
+/// An IEEE 802 48-bit MAC address.
+///
+/// Displayed as `00:1b:63:84:45:e6`, parsed from hex pairs separated by `:` or `-`.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, BufViewMut, Eui64, MacAddr};
+/// use std::net::Ipv4Addr;
+///
+/// let mut buf = [0u8; 24];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// let mac: MacAddr = "00-1b-63-84-45-e6".parse().unwrap();
+/// buf_view.write_mac(mac);
+/// buf_view.write_eui64(Eui64::from_mac(mac));
+/// buf_view.write_ipv4(Ipv4Addr::new(192, 168, 0, 1));
+///
+/// let mut buf_view = BufView::wrap(&buf);
+/// assert_eq!(buf_view.read_mac().to_string(), "00:1b:63:84:45:e6");
+/// assert_eq!(buf_view.read_eui64().to_string(), "00:1b:63:ff:fe:84:45:e6");
+/// assert_eq!(buf_view.read_ipv4(), Ipv4Addr::new(192, 168, 0, 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MacAddr([u8; 6]);
+
+impl MacAddr {
+    pub const BROADCAST: MacAddr = MacAddr([0xff; 6]);
+
+    pub const fn new(octets: [u8; 6]) -> Self {
+        MacAddr(octets)
+    }
+
+    pub const fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+
+    pub fn is_broadcast(&self) -> bool {
+        *self == MacAddr::BROADCAST
+    }
+
+    /// The group bit is set, broadcast is multicast too.
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// The locally administered bit is set.
+    pub fn is_local(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+}
+
+impl From<[u8; 6]> for MacAddr {
+    fn from(octets: [u8; 6]) -> Self {
+        MacAddr(octets)
+    }
+}
+
+impl From<MacAddr> for [u8; 6] {
+    fn from(mac: MacAddr) -> Self {
+        mac.0
+    }
+}
+
+/// An IEEE EUI-64 identifier, displayed and parsed like [`MacAddr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Eui64([u8; 8]);
+
+impl Eui64 {
+    pub const fn new(octets: [u8; 8]) -> Self {
+        Eui64(octets)
+    }
+
+    /// The EUI-64 of a MAC address, `ff:fe` inserted in the middle.
+    pub const fn from_mac(mac: MacAddr) -> Self {
+        let m = mac.0;
+        Eui64([m[0], m[1], m[2], 0xff, 0xfe, m[3], m[4], m[5]])
+    }
+
+    pub const fn octets(&self) -> [u8; 8] {
+        self.0
+    }
+
+    /// The modified EUI-64 IPv6 interface identifier, the universal/local bit flipped.
+    pub const fn to_interface_id(&self) -> [u8; 8] {
+        let mut id = self.0;
+        id[0] ^= 0x02;
+        id
+    }
+}
+
+impl From<[u8; 8]> for Eui64 {
+    fn from(octets: [u8; 8]) -> Self {
+        Eui64(octets)
+    }
+}
+
+impl From<Eui64> for [u8; 8] {
+    fn from(eui: Eui64) -> Self {
+        eui.0
+    }
+}
+
+/// The error parsing a [`MacAddr`] or [`Eui64`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMacError(());
+
+impl fmt::Display for ParseMacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid MAC address syntax")
+    }
+}
+
+impl std::error::Error for ParseMacError {}
+
+fn fmt_octets(octets: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, b) in octets.iter().enumerate() {
+        if i > 0 {
+            f.write_str(":")?;
+        }
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+fn parse_octets<const N: usize>(s: &str) -> Result<[u8; N], ParseMacError> {
+    let sep = if s.contains('-') { '-' } else { ':' };
+    let mut octets = [0u8; N];
+    let mut parts = s.split(sep);
+    for octet in octets.iter_mut() {
+        let part = parts.next().ok_or(ParseMacError(()))?;
+        if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseMacError(()));
+        }
+        *octet = u8::from_str_radix(part, 16).unwrap();
+    }
+    match parts.next() {
+        Some(_) => Err(ParseMacError(())),
+        None => Ok(octets),
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_octets(&self.0, f)
+    }
+}
+
+impl fmt::Display for Eui64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_octets(&self.0, f)
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = ParseMacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_octets(s).map(MacAddr)
+    }
+}
+
+impl FromStr for Eui64 {
+    type Err = ParseMacError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_octets(s).map(Eui64)
+    }
+}
+
+macro_rules! addr_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            pub fn read_ipv4(&mut self) -> Ipv4Addr {
+                Ipv4Addr::from(self.read_array::<4>())
+            }
+
+            pub fn read_ipv6(&mut self) -> Ipv6Addr {
+                Ipv6Addr::from(self.read_array::<16>())
+            }
+
+            /// Read an IPv4 address followed by a port.
+            pub fn read_socket_addr_v4(&mut self) -> SocketAddrV4 {
+                let ip = self.read_ipv4();
+                SocketAddrV4::new(ip, self.read_u16())
+            }
+
+            /// Read an IPv6 address followed by a port, the flow info and scope id are 0.
+            pub fn read_socket_addr_v6(&mut self) -> SocketAddrV6 {
+                let ip = self.read_ipv6();
+                SocketAddrV6::new(ip, self.read_u16(), 0, 0)
+            }
+
+            pub fn read_mac(&mut self) -> MacAddr {
+                MacAddr(self.read_array())
+            }
+
+            pub fn read_eui64(&mut self) -> Eui64 {
+                Eui64(self.read_array())
+            }
+
+            pub fn get_ipv4(&self, index: usize) -> Ipv4Addr {
+                Ipv4Addr::from(self.get_array::<4>(index))
+            }
+
+            pub fn get_ipv6(&self, index: usize) -> Ipv6Addr {
+                Ipv6Addr::from(self.get_array::<16>(index))
+            }
+
+            pub fn get_socket_addr_v4(&self, index: usize) -> SocketAddrV4 {
+                let port = u16::from_be_bytes(self.get_array(index + 4));
+                SocketAddrV4::new(self.get_ipv4(index), port)
+            }
+
+            pub fn get_socket_addr_v6(&self, index: usize) -> SocketAddrV6 {
+                let port = u16::from_be_bytes(self.get_array(index + 16));
+                SocketAddrV6::new(self.get_ipv6(index), port, 0, 0)
+            }
+
+            pub fn get_mac(&self, index: usize) -> MacAddr {
+                MacAddr(self.get_array(index))
+            }
+
+            pub fn get_eui64(&self, index: usize) -> Eui64 {
+                Eui64(self.get_array(index))
+            }
+        }
+    };
+}
+
+addr_read_methods!(BufView);
+addr_read_methods!(BufViewMut);
+
+impl BufViewMut<'_> {
+    pub fn write_ipv4(&mut self, val: Ipv4Addr) {
+        self.write_array(&val.octets());
+    }
+
+    pub fn write_ipv6(&mut self, val: Ipv6Addr) {
+        self.write_array(&val.octets());
+    }
+
+    /// Write the IPv4 address followed by the port.
+    pub fn write_socket_addr_v4(&mut self, val: SocketAddrV4) {
+        assert!(self.buf.len() >= self.writer_index + 6);
+        self.write_ipv4(*val.ip());
+        self.write_u16(val.port());
+    }
+
+    /// Write the IPv6 address followed by the port, the flow info and scope id are
+    /// dropped.
+    pub fn write_socket_addr_v6(&mut self, val: SocketAddrV6) {
+        assert!(self.buf.len() >= self.writer_index + 18);
+        self.write_ipv6(*val.ip());
+        self.write_u16(val.port());
+    }
+
+    pub fn write_mac(&mut self, val: MacAddr) {
+        self.write_array(&val.0);
+    }
+
+    pub fn write_eui64(&mut self, val: Eui64) {
+        self.write_array(&val.0);
+    }
+
+    pub fn set_ipv4(&mut self, index: usize, val: Ipv4Addr) {
+        self.set_array(index, &val.octets());
+    }
+
+    pub fn set_ipv6(&mut self, index: usize, val: Ipv6Addr) {
+        self.set_array(index, &val.octets());
+    }
+
+    pub fn set_socket_addr_v4(&mut self, index: usize, val: SocketAddrV4) {
+        assert!(self.buf.len() >= index + 6);
+        self.set_ipv4(index, *val.ip());
+        self.set_u16(index + 4, val.port());
+    }
+
+    pub fn set_socket_addr_v6(&mut self, index: usize, val: SocketAddrV6) {
+        assert!(self.buf.len() >= index + 18);
+        self.set_ipv6(index, *val.ip());
+        self.set_u16(index + 16, val.port());
+    }
+
+    pub fn set_mac(&mut self, index: usize, val: MacAddr) {
+        self.set_array(index, &val.0);
+    }
+
+    pub fn set_eui64(&mut self, index: usize, val: Eui64) {
+        self.set_array(index, &val.0);
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{BufView, BufViewMut};
+
+macro_rules! array_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            /// Read the next `N` bytes as an array.
+            pub fn read_array<const N: usize>(&mut self) -> [u8; N] {
+                let val = self.peek_array::<N>();
+                self.reader_index += N;
+                val
+            }
+
+            /// The next `N` bytes as an array, without consuming them.
+            pub fn peek_array<const N: usize>(&self) -> [u8; N] {
+                let end = self.reader_index + N;
+                assert!(self.writer_index >= end);
+                self.buf[self.reader_index..end].try_into().unwrap()
+            }
+
+            pub fn get_array<const N: usize>(&self, index: usize) -> [u8; N] {
+                assert!(self.buf.len() >= index + N);
+                self.buf[index..index + N].try_into().unwrap()
+            }
+        }
+    };
+}
+
+array_read_methods!(BufView);
+array_read_methods!(BufViewMut);
+
+impl<'a> BufView<'a> {
+    /// Read the next `N` bytes as a borrowed array, without copying.
+    /// ```
+    /// use buf_view::BufView;
+    ///
+    /// let buf = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a, 0, 0, 0, 0x0d];
+    /// let mut buf_view = BufView::wrap(&buf);
+    /// assert_eq!(&buf_view.peek_array::<4>()[1..], b"PNG");
+    /// let magic: &[u8; 8] = buf_view.read_array_ref();
+    /// assert_eq!(magic, b"\x89PNG\r\n\x1a\n");
+    /// assert_eq!(buf_view.read_array::<4>(), [0, 0, 0, 0x0d]);
+    /// assert_eq!(buf_view.get_array(1), *b"PNG");
+    /// ```
+    pub fn read_array_ref<const N: usize>(&mut self) -> &'a [u8; N] {
+        let end = self.reader_index + N;
+        assert!(self.writer_index >= end);
+        let val = self.buf[self.reader_index..end].try_into().unwrap();
+        self.reader_index = end;
+        val
+    }
+}
+
+impl BufViewMut<'_> {
+    pub fn write_array<const N: usize>(&mut self, val: &[u8; N]) {
+        let end = self.writer_index + N;
+        assert!(self.buf.len() >= end);
+        self.buf[self.writer_index..end].copy_from_slice(val);
+        self.writer_index = end;
+    }
+
+    pub fn set_array<const N: usize>(&mut self, index: usize, val: &[u8; N]) {
+        assert!(self.buf.len() >= index + N);
+        self.buf[index..index + N].copy_from_slice(val);
+    }
+}
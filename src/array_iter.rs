@@ -0,0 +1,149 @@
+use crate::endian::Endian;
+use crate::primitive::Primitive;
+use crate::{BufView, BufViewMut};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// An iterator decoding packed `T` values in the byte order `E` on the fly.
+///
+/// Created by `read_array_iter`, it borrows the bytes and copies nothing, `nth` and
+/// `nth_back` are O(1).
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, LittleEndian};
+///
+/// let buf = [1, 0, 2, 0, 3, 0, 4, 0, 0xff];
+/// let mut buf_view = BufView::wrap(&buf);
+/// let mut samples = buf_view.read_array_iter::<u16, LittleEndian>(4);
+/// assert_eq!(buf_view.reader_index(), 8);
+/// assert_eq!(samples.len(), 4);
+/// assert_eq!(samples.next_back(), Some(4));
+/// assert_eq!(samples.clone().sum::<u16>(), 6);
+/// assert_eq!(samples.nth(1), Some(2));
+/// assert_eq!(buf_view.read_u8(), 0xff);
+/// ```
+#[derive(Debug)]
+pub struct ArrayIter<'a, T, E> {
+    bytes: &'a [u8],
+    marker: PhantomData<(T, E)>,
+}
+
+impl<'a, T: Primitive, E: Endian> ArrayIter<'a, T, E> {
+    /// Iterate over `bytes`, its length must be a multiple of `T::SIZE`.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        assert!(bytes.len().is_multiple_of(T::SIZE));
+        ArrayIter {
+            bytes,
+            marker: PhantomData,
+        }
+    }
+
+    /// The bytes not iterated yet.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<T, E> Clone for ArrayIter<'_, T, E> {
+    fn clone(&self) -> Self {
+        ArrayIter {
+            bytes: self.bytes,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Primitive, E: Endian> Iterator for ArrayIter<'_, T, E> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let (head, tail) = self.bytes.split_at(T::SIZE);
+        self.bytes = tail;
+        Some(E::decode(head))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        if n >= self.len() {
+            self.bytes = &self.bytes[self.bytes.len()..];
+            return None;
+        }
+        self.bytes = &self.bytes[n * T::SIZE..];
+        self.next()
+    }
+
+    fn last(mut self) -> Option<T> {
+        self.next_back()
+    }
+}
+
+impl<T: Primitive, E: Endian> DoubleEndedIterator for ArrayIter<'_, T, E> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let (head, tail) = self.bytes.split_at(self.bytes.len() - T::SIZE);
+        self.bytes = head;
+        Some(E::decode(tail))
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<T> {
+        if n >= self.len() {
+            self.bytes = &self.bytes[..0];
+            return None;
+        }
+        self.bytes = &self.bytes[..self.bytes.len() - n * T::SIZE];
+        self.next_back()
+    }
+}
+
+impl<T: Primitive, E: Endian> ExactSizeIterator for ArrayIter<'_, T, E> {
+    fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+}
+
+impl<T: Primitive, E: Endian> FusedIterator for ArrayIter<'_, T, E> {}
+
+impl<'a> BufView<'a> {
+    /// Read `count` packed `T` values lazily, the reader index is advanced past all of
+    /// them at once.
+    pub fn read_array_iter<T: Primitive, E: Endian>(
+        &mut self,
+        count: usize,
+    ) -> ArrayIter<'a, T, E> {
+        let end = self.reader_index + count * T::SIZE;
+        assert!(self.writer_index >= end);
+        let iter = ArrayIter::new(&self.buf[self.reader_index..end]);
+        self.reader_index = end;
+        iter
+    }
+}
+
+impl BufViewMut<'_> {
+    /// Read `count` packed `T` values lazily, the reader index is advanced past all of
+    /// them at once.
+    pub fn read_array_iter<T: Primitive, E: Endian>(
+        &mut self,
+        count: usize,
+    ) -> ArrayIter<'_, T, E> {
+        let end = self.reader_index + count * T::SIZE;
+        assert!(self.writer_index >= end);
+        let start = std::mem::replace(&mut self.reader_index, end);
+        ArrayIter::new(&self.buf[start..end])
+    }
+}
@@ -0,0 +1,229 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::{BufView, BufViewMut};
+
+const DEC_DIGITS_LUT: &[u8; 200] = b"\
+    0001020304050607080910111213141516171819\
+    2021222324252627282930313233343536373839\
+    4041424344454647484950515253545556575859\
+    6061626364656667686970717273747576777879\
+    8081828384858687888990919293949596979899";
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Format `val` in `radix` at the end of `buf`, and return the digits.
+fn fmt_u64(mut val: u64, radix: u32, buf: &mut [u8; 64]) -> &[u8] {
+    assert!((2..=36).contains(&radix));
+    let mut pos = buf.len();
+    if radix == 10 {
+        while val >= 100 {
+            let d = (val % 100) as usize * 2;
+            val /= 100;
+            pos -= 2;
+            buf[pos..pos + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
+        }
+        if val >= 10 {
+            let d = val as usize * 2;
+            pos -= 2;
+            buf[pos..pos + 2].copy_from_slice(&DEC_DIGITS_LUT[d..d + 2]);
+        } else {
+            pos -= 1;
+            buf[pos] = b'0' + val as u8;
+        }
+    } else {
+        let radix = radix as u64;
+        loop {
+            pos -= 1;
+            buf[pos] = DIGITS[(val % radix) as usize];
+            val /= radix;
+            if val == 0 {
+                break;
+            }
+        }
+    }
+    &buf[pos..]
+}
+
+impl BufView<'_> {
+    /// Read an unsigned ASCII number in `radix`, up to the first non digit byte.
+    ///
+    /// Letters of both cases are digits above 9. Fail with `Invalid` when there is no
+    /// digit and with `Overflow` when the number does not fit in u64, nothing is consumed
+    /// on failure.
+    /// ```
+    /// use buf_view::BufView;
+    ///
+    /// let mut buf_view = BufView::wrap(b"1234\r\n0000644\0-42 1.5e3;");
+    /// assert_eq!(buf_view.read_ascii_u64(10), Ok(1234));
+    /// buf_view.read_line();
+    /// assert_eq!(buf_view.read_ascii_u64(8), Ok(0o644));
+    /// buf_view.read_u8();
+    /// assert_eq!(buf_view.read_ascii_i64(10), Ok(-42));
+    /// buf_view.read_u8();
+    /// assert_eq!(buf_view.parse_ascii_f64(), Ok(1500.0));
+    /// assert_eq!(buf_view.read_u8(), b';');
+    /// ```
+    pub fn read_ascii_u64(&mut self, radix: u32) -> Result<u64> {
+        let (val, len) = self.ascii_u64(self.reader_index, radix)?;
+        self.reader_index += len;
+        Ok(val)
+    }
+
+    /// Read a signed ASCII number in `radix` with an optional `+` or `-` sign.
+    pub fn read_ascii_i64(&mut self, radix: u32) -> Result<i64> {
+        let start = self.reader_index;
+        let (neg, sign_len) = match self.buf[start..self.writer_index].first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+        let (abs, len) = self.ascii_u64(start + sign_len, radix)?;
+        let val = if neg {
+            0i64.checked_sub_unsigned(abs)
+        } else {
+            i64::try_from(abs).ok()
+        };
+        let val = val.ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, start))?;
+        self.reader_index += sign_len + len;
+        Ok(val)
+    }
+
+    /// Read a decimal ASCII float like `-12.5e-3`, the longest valid prefix is read.
+    ///
+    /// The conversion is correctly rounded. Fail with `Overflow` when the value is too
+    /// large for f64, nothing is consumed on failure.
+    pub fn parse_ascii_f64(&mut self) -> Result<f64> {
+        let start = self.reader_index;
+        let bytes = &self.buf[start..self.writer_index];
+        let digits = |from: usize| {
+            bytes[from.min(bytes.len())..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count()
+        };
+
+        let mut len = match bytes.first() {
+            Some(b'-' | b'+') => 1,
+            _ => 0,
+        };
+        let int_digits = digits(len);
+        len += int_digits;
+        let mut frac_digits = 0;
+        if bytes.get(len) == Some(&b'.') {
+            frac_digits = digits(len + 1);
+            if int_digits + frac_digits > 0 {
+                len += 1 + frac_digits;
+            }
+        }
+        if int_digits + frac_digits == 0 {
+            return Err(Error::invalid("expected digits", self.buf, start));
+        }
+        if let Some(b'e' | b'E') = bytes.get(len) {
+            let sign = matches!(bytes.get(len + 1), Some(b'-' | b'+')) as usize;
+            let exp_digits = digits(len + 1 + sign);
+            if exp_digits > 0 {
+                len += 1 + sign + exp_digits;
+            }
+        }
+
+        // the scanned bytes are ASCII digits, signs, '.' and 'e' only
+        let text = std::str::from_utf8(&bytes[..len]).unwrap();
+        let val: f64 = text.parse().unwrap();
+        if val.is_infinite() {
+            return Err(Error::new(ErrorKind::Overflow, self.buf, start));
+        }
+        self.reader_index += len;
+        Ok(val)
+    }
+
+    fn ascii_u64(&self, start: usize, radix: u32) -> Result<(u64, usize)> {
+        assert!((2..=36).contains(&radix));
+        let mut val = 0u64;
+        let mut len = 0;
+        for b in &self.buf[start.min(self.writer_index)..self.writer_index] {
+            let digit = match (*b as char).to_digit(radix) {
+                Some(digit) => digit,
+                None => break,
+            };
+            val = val
+                .checked_mul(radix as u64)
+                .and_then(|v| v.checked_add(digit as u64))
+                .ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, start))?;
+            len += 1;
+        }
+        if len == 0 {
+            return Err(Error::invalid("expected digits", self.buf, start));
+        }
+        Ok((val, len))
+    }
+}
+
+impl BufViewMut<'_> {
+    /// Write `val` as decimal ASCII digits.
+    /// ```
+    /// use buf_view::BufViewMut;
+    ///
+    /// let mut buf = [0u8; 32];
+    /// let mut buf_view = BufViewMut::wrap(&mut buf);
+    /// buf_view.write_ascii_u64(1234);
+    /// buf_view.write_u8(b' ');
+    /// buf_view.write_ascii_i64(-5);
+    /// buf_view.write_u8(b' ');
+    /// buf_view.write_ascii_hex(0xbeef);
+    /// buf_view.write_u8(b' ');
+    /// buf_view.write_ascii_padded(0o644, 8, 7).unwrap();
+    /// assert_eq!(buf_view.as_slice(), b"1234 -5 beef 0000644");
+    /// ```
+    pub fn write_ascii_u64(&mut self, val: u64) {
+        let mut digits = [0u8; 64];
+        self.write_bytes(fmt_u64(val, 10, &mut digits));
+    }
+
+    /// Write `val` as decimal ASCII digits, with a `-` sign when negative.
+    pub fn write_ascii_i64(&mut self, val: i64) {
+        let mut digits = [0u8; 64];
+        let digits = fmt_u64(val.unsigned_abs(), 10, &mut digits);
+        let end = self.writer_index + digits.len() + (val < 0) as usize;
+        assert!(self.buf.len() >= end);
+        if val < 0 {
+            self.write_u8(b'-');
+        }
+        self.write_bytes(digits);
+    }
+
+    /// Write `val` as lowercase hex ASCII digits, without a prefix.
+    pub fn write_ascii_hex(&mut self, val: u64) {
+        let mut digits = [0u8; 64];
+        self.write_bytes(fmt_u64(val, 16, &mut digits));
+    }
+
+    /// Write `val` in `radix` as exactly `width` ASCII digits, padded with leading zeros.
+    ///
+    /// Fail with `Overflow` when `val` needs more than `width` digits, and with
+    /// `OutOfBounds` when there is no room, nothing is written on failure.
+    pub fn write_ascii_padded(&mut self, val: u64, radix: u32, width: usize) -> Result<()> {
+        self.set_ascii_padded(self.writer_index, val, radix, width)?;
+        self.writer_index += width;
+        Ok(())
+    }
+
+    /// Set `val` in `radix` as exactly `width` ASCII digits at `index`, padded with
+    /// leading zeros, e.g. to back-patch the octal checksum field of a tar header.
+    pub fn set_ascii_padded(
+        &mut self,
+        index: usize,
+        val: u64,
+        radix: u32,
+        width: usize,
+    ) -> Result<()> {
+        let mut digits = [0u8; 64];
+        let digits = fmt_u64(val, radix, &mut digits);
+        if digits.len() > width {
+            return Err(Error::new(ErrorKind::Overflow, self.buf, index));
+        }
+        let end = self.check_get(index, width)?;
+        let pad = width - digits.len();
+        self.buf[index..index + pad].fill(b'0');
+        self.buf[index + pad..end].copy_from_slice(digits);
+        Ok(())
+    }
+}
@@ -0,0 +1,892 @@
+//! ASN.1 BER and DER decoding and DER encoding, behind the `asn1` feature.
+//!
+//! An [`Asn1Reader`] reads the elements of a [`BufView`], an [`Element`] borrows its
+//! content and decodes it on request, and the children of a constructed element are
+//! read with another reader over its content. Error offsets are absolute in the
+//! wrapped buffer.
+//!
+//! [`Mode::Der`] adds the DER checks: definite minimal lengths, `0x00`/`0xff` booleans,
+//! zero bit string padding and the canonical time formats. The checks required by BER
+//! as well, such as minimal integers and tags, are always done. Constructed strings are
+//! not supported.
+//!
+//! Example
+//! ```
+//! use buf_view::asn1::{Asn1Reader, Asn1Writer, Mode, Tag};
+//! use buf_view::{BufView, BufViewMut};
+//!
+//! let mut buf = [0u8; 64];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let mut writer = Asn1Writer::new(&mut buf_view);
+//! let seq = writer.begin(Tag::SEQUENCE).unwrap();
+//! writer.write_oid(&[1, 2, 840, 113549, 1, 1, 11]).unwrap();
+//! writer.write_i64(-129).unwrap();
+//! writer.end(seq).unwrap();
+//! let len = buf_view.writer_index();
+//! assert_eq!(&buf[..4], &[0x30, 15, 0x06, 9]);
+//!
+//! let mut reader = Asn1Reader::new(BufView::wrap(&buf[..len]), Mode::Der);
+//! let mut seq = reader.read_sequence().unwrap();
+//! let oid = seq.read_expect(Tag::OID).unwrap().as_oid().unwrap();
+//! assert_eq!(oid.to_string(), "1.2.840.113549.1.1.11");
+//! assert_eq!(seq.read_element().unwrap().as_i64(), Ok(-129));
+//! assert!(seq.is_empty() && reader.is_empty());
+//! ```
+
+use crate::endian::Endianness;
+use crate::error::{Error, ErrorKind, Result};
+use crate::time::{civil_from_days, days_from_civil, days_in_month, from_unix, to_unix};
+use crate::tlv::{TlvFormat, TlvMark, TlvWriter};
+use crate::{BufView, BufViewMut};
+use std::fmt;
+use std::time::SystemTime;
+
+/// The maximum nesting of indefinite length elements.
+pub const MAX_DEPTH: usize = 64;
+
+/// The encoding rules to check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Ber,
+    Der,
+}
+
+/// The class of a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Class {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+/// The identifier of an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tag {
+    pub class: Class,
+    pub constructed: bool,
+    pub number: u32,
+}
+
+impl Tag {
+    pub const BOOLEAN: Tag = Tag::universal(1);
+    pub const INTEGER: Tag = Tag::universal(2);
+    pub const BIT_STRING: Tag = Tag::universal(3);
+    pub const OCTET_STRING: Tag = Tag::universal(4);
+    pub const NULL: Tag = Tag::universal(5);
+    pub const OID: Tag = Tag::universal(6);
+    pub const ENUMERATED: Tag = Tag::universal(10);
+    pub const UTF8_STRING: Tag = Tag::universal(12);
+    pub const SEQUENCE: Tag = Tag {
+        constructed: true,
+        ..Tag::universal(16)
+    };
+    pub const SET: Tag = Tag {
+        constructed: true,
+        ..Tag::universal(17)
+    };
+    pub const PRINTABLE_STRING: Tag = Tag::universal(19);
+    pub const IA5_STRING: Tag = Tag::universal(22);
+    pub const UTC_TIME: Tag = Tag::universal(23);
+    pub const GENERALIZED_TIME: Tag = Tag::universal(24);
+    pub const VISIBLE_STRING: Tag = Tag::universal(26);
+
+    /// A primitive universal tag.
+    pub const fn universal(number: u32) -> Tag {
+        Tag {
+            class: Class::Universal,
+            constructed: false,
+            number,
+        }
+    }
+
+    /// A context-specific tag such as `[0]`.
+    pub const fn context(number: u32, constructed: bool) -> Tag {
+        Tag {
+            class: Class::ContextSpecific,
+            constructed,
+            number,
+        }
+    }
+
+    pub const fn application(number: u32, constructed: bool) -> Tag {
+        Tag {
+            class: Class::Application,
+            constructed,
+            number,
+        }
+    }
+}
+
+/// An element, borrowed from the wrapped buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Element<'a> {
+    pub tag: Tag,
+    buf: &'a [u8],
+    start: usize,
+    content_start: usize,
+    content_end: usize,
+    end: usize,
+    mode: Mode,
+}
+
+impl<'a> Element<'a> {
+    /// The absolute offset of the element.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    /// The content, without the end-of-contents of an indefinite length.
+    pub fn content(&self) -> &'a [u8] {
+        &self.buf[self.content_start..self.content_end]
+    }
+
+    /// A view of the content with absolute indexes.
+    pub fn view(&self) -> BufView<'a> {
+        BufView::wrap_with(self.buf, self.content_start, self.content_end)
+    }
+
+    /// The whole encoding, identifier and length included, e.g. to verify a signature.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.buf[self.start..self.end]
+    }
+
+    fn invalid(&self, msg: &'static str) -> Error {
+        Error::invalid(msg, self.buf, self.content_start)
+    }
+
+    fn primitive(&self) -> Result<&'a [u8]> {
+        if self.tag.constructed {
+            return Err(Error::invalid(
+                "constructed ASN.1 element",
+                self.buf,
+                self.start,
+            ));
+        }
+        Ok(self.content())
+    }
+
+    /// A reader over the children of a constructed element.
+    pub fn children(&self) -> Result<Asn1Reader<'a>> {
+        if !self.tag.constructed {
+            return Err(Error::invalid(
+                "primitive ASN.1 element",
+                self.buf,
+                self.start,
+            ));
+        }
+        Ok(Asn1Reader::new(self.view(), self.mode))
+    }
+
+    pub fn as_bool(&self) -> Result<bool> {
+        match self.primitive()? {
+            [0] => Ok(false),
+            [0xff] => Ok(true),
+            [_] if self.mode == Mode::Ber => Ok(true),
+            [_] => Err(self.invalid("non-DER ASN.1 BOOLEAN")),
+            _ => Err(self.invalid("bad ASN.1 BOOLEAN length")),
+        }
+    }
+
+    /// The two's complement big endian bytes of an INTEGER or ENUMERATED.
+    pub fn as_integer_bytes(&self) -> Result<&'a [u8]> {
+        let bytes = self.primitive()?;
+        match bytes {
+            [] => Err(self.invalid("empty ASN.1 INTEGER")),
+            [0, b, ..] if b & 0x80 == 0 => Err(self.invalid("non-minimal ASN.1 INTEGER")),
+            [0xff, b, ..] if b & 0x80 != 0 => Err(self.invalid("non-minimal ASN.1 INTEGER")),
+            _ => Ok(bytes),
+        }
+    }
+
+    pub fn as_i64(&self) -> Result<i64> {
+        let bytes = self.as_integer_bytes()?;
+        if bytes.len() > 8 {
+            return Err(Error::new(
+                ErrorKind::Overflow,
+                self.buf,
+                self.content_start,
+            ));
+        }
+        let init = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+        Ok(bytes.iter().fold(init, |v, &b| (v << 8) | b as i64))
+    }
+
+    pub fn as_u64(&self) -> Result<u64> {
+        let bytes = match self.as_integer_bytes()? {
+            [0, rest @ ..] if !rest.is_empty() => rest,
+            bytes if bytes[0] & 0x80 != 0 => {
+                return Err(Error::new(
+                    ErrorKind::Overflow,
+                    self.buf,
+                    self.content_start,
+                ));
+            }
+            bytes => bytes,
+        };
+        if bytes.len() > 8 {
+            return Err(Error::new(
+                ErrorKind::Overflow,
+                self.buf,
+                self.content_start,
+            ));
+        }
+        Ok(bytes.iter().fold(0, |v, &b| (v << 8) | b as u64))
+    }
+
+    pub fn as_bit_string(&self) -> Result<BitString<'a>> {
+        let (&unused_bits, bytes) = match self.primitive()?.split_first() {
+            Some(split) => split,
+            None => return Err(self.invalid("empty ASN.1 BIT STRING")),
+        };
+        if unused_bits > 7 || (bytes.is_empty() && unused_bits != 0) {
+            return Err(self.invalid("bad ASN.1 BIT STRING unused bits"));
+        }
+        if self.mode == Mode::Der {
+            let mask = (1u8 << unused_bits) - 1;
+            if bytes.last().is_some_and(|b| b & mask != 0) {
+                return Err(self.invalid("non-DER ASN.1 BIT STRING padding"));
+            }
+        }
+        Ok(BitString { unused_bits, bytes })
+    }
+
+    pub fn as_octet_string(&self) -> Result<&'a [u8]> {
+        self.primitive()
+    }
+
+    pub fn as_null(&self) -> Result<()> {
+        match self.primitive()? {
+            [] => Ok(()),
+            _ => Err(self.invalid("bad ASN.1 NULL length")),
+        }
+    }
+
+    pub fn as_oid(&self) -> Result<Oid<'a>> {
+        let bytes = self.primitive()?;
+        if bytes.is_empty() || bytes[bytes.len() - 1] & 0x80 != 0 {
+            return Err(self.invalid("bad ASN.1 OID"));
+        }
+        let mut first = true;
+        let mut subid_len = 0;
+        for (i, &b) in bytes.iter().enumerate() {
+            if first && b == 0x80 {
+                return Err(Error::invalid(
+                    "non-minimal ASN.1 OID",
+                    self.buf,
+                    self.content_start + i,
+                ));
+            }
+            subid_len += 1;
+            // 10 bytes hold a u64 when the first carries at most 1 bit
+            if subid_len > 10 || (subid_len == 10 && bytes[i + 1 - 10] > 0x81) {
+                return Err(Error::new(
+                    ErrorKind::Overflow,
+                    self.buf,
+                    self.content_start + i,
+                ));
+            }
+            first = b & 0x80 == 0;
+            if first {
+                subid_len = 0;
+            }
+        }
+        Ok(Oid(bytes))
+    }
+
+    /// The value of a string type, UTF-8 or ASCII.
+    pub fn as_str(&self) -> Result<&'a str> {
+        std::str::from_utf8(self.primitive()?).map_err(|_| self.invalid("bad ASN.1 string"))
+    }
+
+    /// A UTCTime, `YYMMDDhhmm[ss]` then `Z` or an offset, the years 50 to 99 are 19xx.
+    ///
+    /// DER requires the seconds and `Z`.
+    pub fn as_utc_time(&self) -> Result<SystemTime> {
+        let s = self.primitive()?;
+        parse_time(s, false, self.mode).ok_or_else(|| self.invalid("bad ASN.1 UTCTime"))
+    }
+
+    /// A GeneralizedTime, `YYYYMMDDhh[mm[ss[.fff]]]` then `Z`, an offset or nothing for
+    /// the local time which is taken as UTC.
+    ///
+    /// DER requires the minutes, seconds and `Z`, and no trailing zero in the fraction.
+    pub fn as_generalized_time(&self) -> Result<SystemTime> {
+        let s = self.primitive()?;
+        parse_time(s, true, self.mode).ok_or_else(|| self.invalid("bad ASN.1 GeneralizedTime"))
+    }
+}
+
+/// The value of a BIT STRING.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitString<'a> {
+    /// The number of padding bits in the last byte.
+    pub unused_bits: u8,
+    pub bytes: &'a [u8],
+}
+
+impl BitString<'_> {
+    /// The number of bits.
+    pub fn bit_len(&self) -> usize {
+        self.bytes.len() * 8 - self.unused_bits as usize
+    }
+
+    /// The bit `index`, counted from the most significant bit of the first byte.
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        if index >= self.bit_len() {
+            return None;
+        }
+        Some(self.bytes[index / 8] & (0x80 >> (index % 8)) != 0)
+    }
+}
+
+/// A validated OBJECT IDENTIFIER, displayed as dotted arcs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Oid<'a>(&'a [u8]);
+
+impl<'a> Oid<'a> {
+    /// The content bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    /// The arcs, the first subidentifier split into the first two arcs.
+    pub fn arcs(&self) -> OidArcs<'a> {
+        OidArcs {
+            bytes: self.0,
+            first: true,
+            second: None,
+        }
+    }
+}
+
+impl fmt::Display for Oid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, arc) in self.arcs().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{}", arc)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the arcs of an [`Oid`].
+#[derive(Debug, Clone)]
+pub struct OidArcs<'a> {
+    bytes: &'a [u8],
+    first: bool,
+    second: Option<u64>,
+}
+
+impl Iterator for OidArcs<'_> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if let Some(second) = self.second.take() {
+            return Some(second);
+        }
+        let mut subid = 0u64;
+        loop {
+            let (&b, rest) = self.bytes.split_first()?;
+            self.bytes = rest;
+            subid = (subid << 7) | (b & 0x7f) as u64;
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+        if std::mem::take(&mut self.first) {
+            let arc = (subid / 40).min(2);
+            self.second = Some(subid - arc * 40);
+            return Some(arc);
+        }
+        Some(subid)
+    }
+}
+
+fn digits(s: &[u8], pos: &mut usize, n: usize) -> Option<u32> {
+    let part = s.get(*pos..*pos + n)?;
+    if !part.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    *pos += n;
+    Some(part.iter().fold(0, |v, &b| v * 10 + (b - b'0') as u32))
+}
+
+fn parse_time(s: &[u8], generalized: bool, mode: Mode) -> Option<SystemTime> {
+    let der = mode == Mode::Der;
+    let mut pos = 0;
+    let year = if generalized {
+        digits(s, &mut pos, 4)?
+    } else {
+        match digits(s, &mut pos, 2)? {
+            yy @ 0..=49 => 2000 + yy,
+            yy => 1900 + yy,
+        }
+    };
+    let month = digits(s, &mut pos, 2)?;
+    let day = digits(s, &mut pos, 2)?;
+    let hour = digits(s, &mut pos, 2)?;
+    let mut minute = 0;
+    let mut second = 0;
+    let mut nanos = 0;
+    // UTCTime always has the minutes
+    if !generalized || s.get(pos).is_some_and(u8::is_ascii_digit) {
+        minute = digits(s, &mut pos, 2)?;
+        if s.get(pos).is_some_and(u8::is_ascii_digit) {
+            second = digits(s, &mut pos, 2)?;
+            if generalized && matches!(s.get(pos), Some(b'.') | Some(b',')) {
+                if der && s[pos] == b',' {
+                    return None;
+                }
+                pos += 1;
+                let start = pos;
+                while s.get(pos).is_some_and(u8::is_ascii_digit) {
+                    pos += 1;
+                }
+                let fraction = &s[start..pos];
+                if fraction.is_empty() || (der && fraction.last() == Some(&b'0')) {
+                    return None;
+                }
+                for (i, &d) in fraction.iter().take(9).enumerate() {
+                    nanos += (d - b'0') as u32 * 10u32.pow(8 - i as u32);
+                }
+            }
+        } else if der {
+            return None;
+        }
+    } else if der {
+        return None;
+    }
+    let offset = match s.get(pos) {
+        Some(b'Z') if pos + 1 == s.len() => 0,
+        Some(&sign @ (b'+' | b'-')) if !der => {
+            pos += 1;
+            let hh = digits(s, &mut pos, 2)?;
+            let mm = digits(s, &mut pos, 2)?;
+            if pos != s.len() || hh > 23 || mm > 59 {
+                return None;
+            }
+            let offset = (hh * 3600 + mm * 60) as i64;
+            if sign == b'+' {
+                offset
+            } else {
+                -offset
+            }
+        }
+        None if generalized && !der => 0,
+        _ => return None,
+    };
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+    let days = days_from_civil(year as i64, month, day);
+    let secs = days * 86_400 + (hour * 3600 + minute * 60 + second) as i64 - offset;
+    from_unix(secs, nanos)
+}
+
+/// Reads the elements of a view, from the reader index to the writer index.
+///
+/// As an iterator it yields the elements in order, a malformed element yields one error
+/// and ends the iteration.
+#[derive(Debug)]
+pub struct Asn1Reader<'a> {
+    view: BufView<'a>,
+    mode: Mode,
+    done: bool,
+}
+
+impl<'a> Asn1Reader<'a> {
+    pub fn new(view: BufView<'a>, mode: Mode) -> Self {
+        Asn1Reader {
+            view,
+            mode,
+            done: false,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.view.remaining() == 0
+    }
+
+    /// Read the next element, nothing is consumed on failure.
+    pub fn read_element(&mut self) -> Result<Element<'a>> {
+        let start = self.view.reader_index();
+        let element = read_element(&mut self.view, self.mode, 0);
+        if element.is_err() {
+            self.view.set_reader_index(start);
+        }
+        element
+    }
+
+    /// Read the next element if it has `tag`, else nothing is consumed.
+    pub fn read_optional(&mut self, tag: Tag) -> Result<Option<Element<'a>>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        let start = self.view.reader_index();
+        let element = self.read_element()?;
+        if element.tag != tag {
+            self.view.set_reader_index(start);
+            return Ok(None);
+        }
+        Ok(Some(element))
+    }
+
+    /// Read the next element, which must have `tag`.
+    pub fn read_expect(&mut self, tag: Tag) -> Result<Element<'a>> {
+        let start = self.view.reader_index();
+        match self.read_optional(tag)? {
+            Some(element) => Ok(element),
+            None => Err(Error::invalid("unexpected ASN.1 tag", self.view.buf, start)),
+        }
+    }
+
+    /// Read a SEQUENCE and return a reader over its children.
+    pub fn read_sequence(&mut self) -> Result<Asn1Reader<'a>> {
+        self.read_expect(Tag::SEQUENCE)?.children()
+    }
+
+    /// Read a SET and return a reader over its children.
+    pub fn read_set(&mut self) -> Result<Asn1Reader<'a>> {
+        self.read_expect(Tag::SET)?.children()
+    }
+}
+
+impl<'a> Iterator for Asn1Reader<'a> {
+    type Item = Result<Element<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.is_empty() {
+            return None;
+        }
+        let element = self.read_element();
+        self.done = element.is_err();
+        Some(element)
+    }
+}
+
+impl std::iter::FusedIterator for Asn1Reader<'_> {}
+
+fn read_tag(view: &mut BufView) -> Result<Tag> {
+    let start = view.reader_index();
+    let first = view.try_read_u8()?;
+    let class = match first >> 6 {
+        0 => Class::Universal,
+        1 => Class::Application,
+        2 => Class::ContextSpecific,
+        _ => Class::Private,
+    };
+    let mut number = (first & 0x1f) as u32;
+    if number == 0x1f {
+        number = 0;
+        loop {
+            let b = view.try_read_u8()?;
+            if number == 0 && b == 0x80 {
+                return Err(Error::invalid("non-minimal ASN.1 tag", view.buf, start));
+            }
+            if number > u32::MAX >> 7 {
+                return Err(Error::new(ErrorKind::Overflow, view.buf, start));
+            }
+            number = (number << 7) | (b & 0x7f) as u32;
+            if b & 0x80 == 0 {
+                break;
+            }
+        }
+        if number < 0x1f {
+            return Err(Error::invalid("non-minimal ASN.1 tag", view.buf, start));
+        }
+    }
+    Ok(Tag {
+        class,
+        constructed: first & 0x20 != 0,
+        number,
+    })
+}
+
+/// Read the length, None for an indefinite length.
+fn read_length(view: &mut BufView, tag: Tag, mode: Mode) -> Result<Option<usize>> {
+    let start = view.reader_index();
+    let first = view.try_read_u8()?;
+    match first {
+        0..=0x7f => Ok(Some(first as usize)),
+        0x80 if mode == Mode::Der => {
+            Err(Error::invalid("indefinite length in DER", view.buf, start))
+        }
+        0x80 if !tag.constructed => Err(Error::invalid(
+            "indefinite length of a primitive",
+            view.buf,
+            start,
+        )),
+        0x80 => Ok(None),
+        0xff => Err(Error::invalid("reserved ASN.1 length", view.buf, start)),
+        _ => {
+            let n = (first & 0x7f) as usize;
+            if n > 4 {
+                return Err(Error::new(ErrorKind::Overflow, view.buf, start));
+            }
+            let mut len = 0;
+            for _ in 0..n {
+                len = (len << 8) | view.try_read_u8()? as usize;
+            }
+            if mode == Mode::Der && (len < 0x80 || len >> ((n - 1) * 8) == 0) {
+                return Err(Error::invalid("non-minimal DER length", view.buf, start));
+            }
+            Ok(Some(len))
+        }
+    }
+}
+
+fn read_element<'a>(view: &mut BufView<'a>, mode: Mode, depth: usize) -> Result<Element<'a>> {
+    let start = view.reader_index();
+    let tag = read_tag(view)?;
+    let len = read_length(view, tag, mode)?;
+    let content_start = view.reader_index();
+    let (content_end, end) = match len {
+        Some(len) => {
+            let end = view.check_read(len)?;
+            view.set_reader_index(end);
+            (end, end)
+        }
+        None => {
+            if depth >= MAX_DEPTH {
+                return Err(Error::invalid("ASN.1 nesting too deep", view.buf, start));
+            }
+            // the content ends with two zero bytes at the level of its children
+            loop {
+                let pos = view.reader_index();
+                if view.remaining() >= 2 && view.get_endian::<u16>(pos, Endianness::Big) == 0 {
+                    view.set_reader_index(pos + 2);
+                    break (pos, pos + 2);
+                }
+                read_element(view, mode, depth + 1)?;
+            }
+        }
+    };
+    Ok(Element {
+        tag,
+        buf: view.buf,
+        start,
+        content_start,
+        content_end,
+        end,
+        mode,
+    })
+}
+
+/// Writes DER elements at the writer index of a [`BufViewMut`].
+///
+/// Constructed elements opened with `begin` have their definite length back-patched by
+/// `end`, the content moves when the length needs more than one byte.
+#[derive(Debug)]
+pub struct Asn1Writer<'v, 'b> {
+    tlv: TlvWriter<'v, 'b>,
+}
+
+impl<'v, 'b> Asn1Writer<'v, 'b> {
+    pub fn new(view: &'v mut BufViewMut<'b>) -> Self {
+        Asn1Writer {
+            tlv: TlvWriter::new(view, TlvFormat::ber()),
+        }
+    }
+
+    /// The view written to, to write content between `begin` and `end`.
+    pub fn view(&mut self) -> &mut BufViewMut<'b> {
+        self.tlv.view()
+    }
+
+    fn error(&mut self, kind: ErrorKind) -> Error {
+        let view = self.tlv.view();
+        Error::new(kind, view.buf, view.writer_index())
+    }
+
+    /// The identifier bytes of `tag` as a big endian number.
+    fn identifier(&mut self, tag: Tag) -> Result<u32> {
+        let class = match tag.class {
+            Class::Universal => 0,
+            Class::Application => 0x40,
+            Class::ContextSpecific => 0x80,
+            Class::Private => 0xc0,
+        };
+        let first = class | if tag.constructed { 0x20 } else { 0 };
+        match tag.number {
+            0..=0x1e => Ok(first | tag.number),
+            0x1f..=0x7f => Ok((first | 0x1f) << 8 | tag.number),
+            0x80..=0x3fff => {
+                let n = tag.number;
+                Ok((first | 0x1f) << 16 | (0x80 | n >> 7) << 8 | (n & 0x7f))
+            }
+            0x4000..=0x1f_ffff => {
+                let n = tag.number;
+                Ok((first | 0x1f) << 24
+                    | (0x80 | n >> 14) << 16
+                    | (0x80 | (n >> 7) & 0x7f) << 8
+                    | (n & 0x7f))
+            }
+            _ => Err(self.error(ErrorKind::Overflow)),
+        }
+    }
+
+    /// Write the identifier and a placeholder length, the content is written next and
+    /// `end` back-patches the length.
+    pub fn begin(&mut self, tag: Tag) -> Result<TlvMark> {
+        let id = self.identifier(tag)?;
+        self.tlv.begin(id)
+    }
+
+    pub fn end(&mut self, mark: TlvMark) -> Result<()> {
+        self.tlv.end(mark)
+    }
+
+    /// Write an element with the given content.
+    pub fn write(&mut self, tag: Tag, content: &[u8]) -> Result<()> {
+        let id = self.identifier(tag)?;
+        self.tlv.write(id, content)
+    }
+
+    pub fn write_bool(&mut self, val: bool) -> Result<()> {
+        self.write(Tag::BOOLEAN, &[if val { 0xff } else { 0 }])
+    }
+
+    pub fn write_i64(&mut self, val: i64) -> Result<()> {
+        let bytes = val.to_be_bytes();
+        let mut skip = 0;
+        while skip < 7 {
+            let (b, next) = (bytes[skip], bytes[skip + 1]);
+            if (b == 0 && next & 0x80 == 0) || (b == 0xff && next & 0x80 != 0) {
+                skip += 1;
+            } else {
+                break;
+            }
+        }
+        self.write(Tag::INTEGER, &bytes[skip..])
+    }
+
+    pub fn write_u64(&mut self, val: u64) -> Result<()> {
+        self.write_unsigned(&val.to_be_bytes())
+    }
+
+    /// Write a non-negative INTEGER from its big endian magnitude, e.g. an RSA modulus.
+    pub fn write_unsigned(&mut self, magnitude: &[u8]) -> Result<()> {
+        let skip = magnitude.iter().take_while(|&&b| b == 0).count();
+        let magnitude = &magnitude[skip..];
+        let mark = self.begin(Tag::INTEGER)?;
+        if magnitude.first().is_none_or(|&b| b & 0x80 != 0) {
+            self.view().try_write_bytes(&[0])?;
+        }
+        self.view().try_write_bytes(magnitude)?;
+        self.end(mark)
+    }
+
+    pub fn write_null(&mut self) -> Result<()> {
+        self.write(Tag::NULL, &[])
+    }
+
+    /// Write an OBJECT IDENTIFIER from its arcs.
+    pub fn write_oid(&mut self, arcs: &[u64]) -> Result<()> {
+        let first = match arcs {
+            [a @ 0..=1, b @ 0..=39, ..] | [a @ 2, b, ..] => {
+                a.checked_mul(40).and_then(|v| v.checked_add(*b))
+            }
+            _ => None,
+        };
+        let first = match first {
+            Some(first) => first,
+            None => return Err(self.error(ErrorKind::Invalid("bad ASN.1 OID"))),
+        };
+        let mark = self.begin(Tag::OID)?;
+        for subid in std::iter::once(first).chain(arcs[2..].iter().copied()) {
+            let mut bytes = [0u8; 10];
+            let mut i = bytes.len();
+            let mut v = subid;
+            loop {
+                i -= 1;
+                bytes[i] = (v & 0x7f) as u8 | if i == 9 { 0 } else { 0x80 };
+                v >>= 7;
+                if v == 0 {
+                    break;
+                }
+            }
+            self.view().try_write_bytes(&bytes[i..])?;
+        }
+        self.end(mark)
+    }
+
+    /// Write a BIT STRING, the padding bits of the last byte are cleared.
+    pub fn write_bit_string(&mut self, unused_bits: u8, bytes: &[u8]) -> Result<()> {
+        if unused_bits > 7 || (bytes.is_empty() && unused_bits != 0) {
+            return Err(self.error(ErrorKind::Invalid("bad ASN.1 BIT STRING unused bits")));
+        }
+        let mark = self.begin(Tag::BIT_STRING)?;
+        self.view().try_write_bytes(&[unused_bits])?;
+        if let Some((&last, init)) = bytes.split_last() {
+            self.view().try_write_bytes(init)?;
+            self.view()
+                .try_write_bytes(&[last & !((1u8 << unused_bits) - 1)])?;
+        }
+        self.end(mark)
+    }
+
+    pub fn write_octet_string(&mut self, bytes: &[u8]) -> Result<()> {
+        self.write(Tag::OCTET_STRING, bytes)
+    }
+
+    pub fn write_utf8_string(&mut self, s: &str) -> Result<()> {
+        self.write(Tag::UTF8_STRING, s.as_bytes())
+    }
+
+    /// Write a UTCTime `YYMMDDhhmmssZ`, the time must be in the years 1950 to 2049.
+    pub fn write_utc_time(&mut self, t: SystemTime) -> Result<()> {
+        let (secs, _) = to_unix(t).ok_or_else(|| self.error(ErrorKind::Overflow))?;
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        if !(1950..=2049).contains(&year) {
+            return Err(self.error(ErrorKind::Overflow));
+        }
+        let secs = secs.rem_euclid(86_400);
+        let s = format!(
+            "{:02}{:02}{:02}{:02}{:02}{:02}Z",
+            year % 100,
+            month,
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        );
+        self.write(Tag::UTC_TIME, s.as_bytes())
+    }
+
+    /// Write a GeneralizedTime `YYYYMMDDhhmmss[.fff]Z`, the fraction without trailing
+    /// zeros. The time must be in the years 0 to 9999.
+    pub fn write_generalized_time(&mut self, t: SystemTime) -> Result<()> {
+        let (secs, nanos) = to_unix(t).ok_or_else(|| self.error(ErrorKind::Overflow))?;
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        if !(0..=9999).contains(&year) {
+            return Err(self.error(ErrorKind::Overflow));
+        }
+        let secs = secs.rem_euclid(86_400);
+        let mut s = format!(
+            "{:04}{:02}{:02}{:02}{:02}{:02}",
+            year,
+            month,
+            day,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60
+        );
+        if nanos != 0 {
+            let fraction = format!("{:09}", nanos);
+            s.push('.');
+            s.push_str(fraction.trim_end_matches('0'));
+        }
+        s.push('Z');
+        self.write(Tag::GENERALIZED_TIME, s.as_bytes())
+    }
+}
@@ -1,4 +1,6 @@
-use crate::macros::{buf_get_do, buf_read_do};
+use crate::error::{Error, Result};
+use crate::macros::{buf_get_do, buf_read_do, buf_try_get_do, buf_try_read_do};
+use crate::view_read::ViewRead;
 
 /// Wrap a &\[u8\] buffer as read only.
 ///
@@ -45,11 +47,10 @@ use crate::macros::{buf_get_do, buf_read_do};
 /// assert_eq!(buf_view.read_u32(), 0x31323334);
 /// ```
 ///
-#[derive(Debug)]
 pub struct BufView<'a> {
-    buf: &'a [u8],
-    reader_index: usize,
-    writer_index: usize,
+    pub(crate) buf: &'a [u8],
+    pub(crate) reader_index: usize,
+    pub(crate) writer_index: usize,
 }
 
 impl<'a> BufView<'a> {
@@ -178,6 +179,32 @@ impl<'a> BufView<'a> {
         copy_len
     }
 
+    /// Read `len` bytes as a sub view, and advance the reader_index past them.
+    ///
+    /// The sub view wraps the same buffer with its reader_index and writer_index set
+    /// around the `len` bytes, so its indexes and error offsets stay absolute in the
+    /// original buffer.
+    /// ```
+    /// use buf_view::BufView;
+    ///
+    /// let buf = [0, 1, 2, 3, 4, 5];
+    /// let mut buf_view = BufView::wrap(&buf);
+    /// buf_view.read_u8();
+    ///
+    /// let mut sub_view = buf_view.read_view(2);
+    /// assert_eq!(sub_view.reader_index(), 1);
+    /// assert_eq!(sub_view.read_u16(), 0x0102);
+    /// assert_eq!(sub_view.remaining(), 0);
+    /// assert_eq!(buf_view.read_u8(), 3);
+    /// ```
+    pub fn read_view(&mut self, len: usize) -> BufView<'a> {
+        assert!(self.remaining() >= len);
+        let end = self.reader_index + len;
+        let view = BufView::wrap_with(self.buf, self.reader_index, end);
+        self.reader_index = end;
+        view
+    }
+
     pub fn get_u8(&mut self, index: usize) -> u8 {
         assert!(self.buf.len() > index);
         self.buf[index]
@@ -278,6 +305,206 @@ impl<'a> BufView<'a> {
         copy_len
     }
 
+    pub fn try_read_u8(&mut self) -> Result<u8> {
+        let end = self.check_read(1)?;
+        let val = self.buf[self.reader_index];
+        self.reader_index = end;
+        Ok(val)
+    }
+
+    pub fn try_read_i8(&mut self) -> Result<i8> {
+        Ok(self.try_read_u8()? as i8)
+    }
+
+    pub fn try_read_u16(&mut self) -> Result<u16> {
+        buf_try_read_do!(self, u16, be);
+    }
+
+    pub fn try_read_u16_le(&mut self) -> Result<u16> {
+        buf_try_read_do!(self, u16, le);
+    }
+
+    pub fn try_read_i16(&mut self) -> Result<i16> {
+        buf_try_read_do!(self, i16, be);
+    }
+
+    pub fn try_read_i16_le(&mut self) -> Result<i16> {
+        buf_try_read_do!(self, i16, le);
+    }
+
+    pub fn try_read_u32(&mut self) -> Result<u32> {
+        buf_try_read_do!(self, u32, be);
+    }
+
+    pub fn try_read_u32_le(&mut self) -> Result<u32> {
+        buf_try_read_do!(self, u32, le);
+    }
+
+    pub fn try_read_i32(&mut self) -> Result<i32> {
+        buf_try_read_do!(self, i32, be);
+    }
+
+    pub fn try_read_i32_le(&mut self) -> Result<i32> {
+        buf_try_read_do!(self, i32, le);
+    }
+
+    pub fn try_read_u64(&mut self) -> Result<u64> {
+        buf_try_read_do!(self, u64, be);
+    }
+
+    pub fn try_read_u64_le(&mut self) -> Result<u64> {
+        buf_try_read_do!(self, u64, le);
+    }
+
+    pub fn try_read_i64(&mut self) -> Result<i64> {
+        buf_try_read_do!(self, i64, be);
+    }
+
+    pub fn try_read_i64_le(&mut self) -> Result<i64> {
+        buf_try_read_do!(self, i64, le);
+    }
+
+    pub fn try_read_u128(&mut self) -> Result<u128> {
+        buf_try_read_do!(self, u128, be);
+    }
+
+    pub fn try_read_u128_le(&mut self) -> Result<u128> {
+        buf_try_read_do!(self, u128, le);
+    }
+
+    pub fn try_read_i128(&mut self) -> Result<i128> {
+        buf_try_read_do!(self, i128, be);
+    }
+
+    pub fn try_read_i128_le(&mut self) -> Result<i128> {
+        buf_try_read_do!(self, i128, le);
+    }
+
+    pub fn try_read_f32(&mut self) -> Result<f32> {
+        buf_try_read_do!(self, f32, be);
+    }
+
+    pub fn try_read_f32_le(&mut self) -> Result<f32> {
+        buf_try_read_do!(self, f32, le);
+    }
+
+    pub fn try_read_f64(&mut self) -> Result<f64> {
+        buf_try_read_do!(self, f64, be);
+    }
+
+    pub fn try_read_f64_le(&mut self) -> Result<f64> {
+        buf_try_read_do!(self, f64, le);
+    }
+
+    /// Read exactly `dest.len()` bytes.
+    pub fn try_read_bytes(&mut self, dest: &mut [u8]) -> Result<()> {
+        let end = self.check_read(dest.len())?;
+        dest.copy_from_slice(&self.buf[self.reader_index..end]);
+        self.reader_index = end;
+        Ok(())
+    }
+
+    pub fn try_read_view(&mut self, len: usize) -> Result<BufView<'a>> {
+        self.check_read(len)?;
+        Ok(self.read_view(len))
+    }
+
+    pub fn try_get_u8(&self, index: usize) -> Result<u8> {
+        self.check_get(index, 1)?;
+        Ok(self.buf[index])
+    }
+
+    pub fn try_get_i8(&self, index: usize) -> Result<i8> {
+        Ok(self.try_get_u8(index)? as i8)
+    }
+
+    pub fn try_get_u16(&self, index: usize) -> Result<u16> {
+        buf_try_get_do!(self, index, u16, be);
+    }
+
+    pub fn try_get_u16_le(&self, index: usize) -> Result<u16> {
+        buf_try_get_do!(self, index, u16, le);
+    }
+
+    pub fn try_get_i16(&self, index: usize) -> Result<i16> {
+        buf_try_get_do!(self, index, i16, be);
+    }
+
+    pub fn try_get_i16_le(&self, index: usize) -> Result<i16> {
+        buf_try_get_do!(self, index, i16, le);
+    }
+
+    pub fn try_get_u32(&self, index: usize) -> Result<u32> {
+        buf_try_get_do!(self, index, u32, be);
+    }
+
+    pub fn try_get_u32_le(&self, index: usize) -> Result<u32> {
+        buf_try_get_do!(self, index, u32, le);
+    }
+
+    pub fn try_get_i32(&self, index: usize) -> Result<i32> {
+        buf_try_get_do!(self, index, i32, be);
+    }
+
+    pub fn try_get_i32_le(&self, index: usize) -> Result<i32> {
+        buf_try_get_do!(self, index, i32, le);
+    }
+
+    pub fn try_get_u64(&self, index: usize) -> Result<u64> {
+        buf_try_get_do!(self, index, u64, be);
+    }
+
+    pub fn try_get_u64_le(&self, index: usize) -> Result<u64> {
+        buf_try_get_do!(self, index, u64, le);
+    }
+
+    pub fn try_get_i64(&self, index: usize) -> Result<i64> {
+        buf_try_get_do!(self, index, i64, be);
+    }
+
+    pub fn try_get_i64_le(&self, index: usize) -> Result<i64> {
+        buf_try_get_do!(self, index, i64, le);
+    }
+
+    pub fn try_get_u128(&self, index: usize) -> Result<u128> {
+        buf_try_get_do!(self, index, u128, be);
+    }
+
+    pub fn try_get_u128_le(&self, index: usize) -> Result<u128> {
+        buf_try_get_do!(self, index, u128, le);
+    }
+
+    pub fn try_get_i128(&self, index: usize) -> Result<i128> {
+        buf_try_get_do!(self, index, i128, be);
+    }
+
+    pub fn try_get_i128_le(&self, index: usize) -> Result<i128> {
+        buf_try_get_do!(self, index, i128, le);
+    }
+
+    pub fn try_get_f32(&self, index: usize) -> Result<f32> {
+        buf_try_get_do!(self, index, f32, be);
+    }
+
+    pub fn try_get_f32_le(&self, index: usize) -> Result<f32> {
+        buf_try_get_do!(self, index, f32, le);
+    }
+
+    pub fn try_get_f64(&self, index: usize) -> Result<f64> {
+        buf_try_get_do!(self, index, f64, be);
+    }
+
+    pub fn try_get_f64_le(&self, index: usize) -> Result<f64> {
+        buf_try_get_do!(self, index, f64, le);
+    }
+
+    /// Get exactly `dest.len()` bytes from `index`.
+    pub fn try_get_bytes(&self, index: usize, dest: &mut [u8]) -> Result<()> {
+        let end = self.check_get(index, dest.len())?;
+        dest.copy_from_slice(&self.buf[index..end]);
+        Ok(())
+    }
+
     pub fn set_reader_index(&mut self, index: usize) {
         assert!(self.buf.len() >= index && index <= self.writer_index);
         self.reader_index = index;
@@ -322,6 +549,45 @@ impl<'a> BufView<'a> {
     pub fn as_raw_slice(&mut self) -> &[u8] {
         self.buf
     }
+
+    /// Run `f`, and prepend the field `name` to the context path of its error.
+    pub fn context<T>(&mut self, name: &str, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        f(self).map_err(|e| e.context(name))
+    }
+
+    /// Run `f`, and prepend the element `name[index]` to the context path of its error.
+    pub fn context_index<T>(
+        &mut self,
+        name: &str,
+        index: usize,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        f(self).map_err(|e| e.index(index).context(name))
+    }
+
+    pub(crate) fn check_read(&self, len: usize) -> Result<usize> {
+        if self.remaining() < len {
+            return Err(Error::out_of_bounds(
+                self.buf,
+                self.reader_index,
+                len,
+                self.remaining(),
+            ));
+        }
+        Ok(self.reader_index + len)
+    }
+
+    pub(crate) fn check_get(&self, index: usize, len: usize) -> Result<usize> {
+        match index.checked_add(len) {
+            Some(end) if end <= self.buf.len() => Ok(end),
+            _ => Err(Error::out_of_bounds(
+                self.buf,
+                index,
+                len,
+                self.buf.len().saturating_sub(index),
+            )),
+        }
+    }
 }
 
 impl std::fmt::Display for BufView<'_> {
@@ -335,3 +601,17 @@ impl std::fmt::Display for BufView<'_> {
         )
     }
 }
+
+impl ViewRead for BufView<'_> {
+    fn read_bytes(&mut self, dest: &mut [u8]) -> usize {
+        BufView::read_bytes(self, dest)
+    }
+
+    fn remaining(&self) -> usize {
+        BufView::remaining(self)
+    }
+
+    fn reader_index(&self) -> usize {
+        BufView::reader_index(self)
+    }
+}
@@ -1,4 +1,7 @@
-use crate::macros::{buf_get_do, buf_read_do};
+use crate::error::{Error, Result};
+use crate::macros::{buf_get_do, buf_read_do, buf_try_get_do, buf_try_read_do};
+use crate::view_read::ViewRead;
+use crate::view_write::ViewWrite;
 use std::io::{self, Write};
 
 /// Wrap a &mut \[u8\] buffer as read and write.
@@ -39,11 +42,10 @@ use std::io::{self, Write};
 /// assert_eq!(buf_view.get_u16(1), 0x0102);
 /// ```
 ///
-#[derive(Debug)]
 pub struct BufViewMut<'a> {
-    buf: &'a mut [u8],
-    reader_index: usize,
-    writer_index: usize,
+    pub(crate) buf: &'a mut [u8],
+    pub(crate) reader_index: usize,
+    pub(crate) writer_index: usize,
 }
 
 impl<'a> BufViewMut<'a> {
@@ -275,6 +277,201 @@ impl<'a> BufViewMut<'a> {
         copy_len
     }
 
+    pub fn try_read_u8(&mut self) -> Result<u8> {
+        let end = self.check_read(1)?;
+        let val = self.buf[self.reader_index];
+        self.reader_index = end;
+        Ok(val)
+    }
+
+    pub fn try_read_i8(&mut self) -> Result<i8> {
+        Ok(self.try_read_u8()? as i8)
+    }
+
+    pub fn try_read_u16(&mut self) -> Result<u16> {
+        buf_try_read_do!(self, u16, be);
+    }
+
+    pub fn try_read_u16_le(&mut self) -> Result<u16> {
+        buf_try_read_do!(self, u16, le);
+    }
+
+    pub fn try_read_i16(&mut self) -> Result<i16> {
+        buf_try_read_do!(self, i16, be);
+    }
+
+    pub fn try_read_i16_le(&mut self) -> Result<i16> {
+        buf_try_read_do!(self, i16, le);
+    }
+
+    pub fn try_read_u32(&mut self) -> Result<u32> {
+        buf_try_read_do!(self, u32, be);
+    }
+
+    pub fn try_read_u32_le(&mut self) -> Result<u32> {
+        buf_try_read_do!(self, u32, le);
+    }
+
+    pub fn try_read_i32(&mut self) -> Result<i32> {
+        buf_try_read_do!(self, i32, be);
+    }
+
+    pub fn try_read_i32_le(&mut self) -> Result<i32> {
+        buf_try_read_do!(self, i32, le);
+    }
+
+    pub fn try_read_u64(&mut self) -> Result<u64> {
+        buf_try_read_do!(self, u64, be);
+    }
+
+    pub fn try_read_u64_le(&mut self) -> Result<u64> {
+        buf_try_read_do!(self, u64, le);
+    }
+
+    pub fn try_read_i64(&mut self) -> Result<i64> {
+        buf_try_read_do!(self, i64, be);
+    }
+
+    pub fn try_read_i64_le(&mut self) -> Result<i64> {
+        buf_try_read_do!(self, i64, le);
+    }
+
+    pub fn try_read_u128(&mut self) -> Result<u128> {
+        buf_try_read_do!(self, u128, be);
+    }
+
+    pub fn try_read_u128_le(&mut self) -> Result<u128> {
+        buf_try_read_do!(self, u128, le);
+    }
+
+    pub fn try_read_i128(&mut self) -> Result<i128> {
+        buf_try_read_do!(self, i128, be);
+    }
+
+    pub fn try_read_i128_le(&mut self) -> Result<i128> {
+        buf_try_read_do!(self, i128, le);
+    }
+
+    pub fn try_read_f32(&mut self) -> Result<f32> {
+        buf_try_read_do!(self, f32, be);
+    }
+
+    pub fn try_read_f32_le(&mut self) -> Result<f32> {
+        buf_try_read_do!(self, f32, le);
+    }
+
+    pub fn try_read_f64(&mut self) -> Result<f64> {
+        buf_try_read_do!(self, f64, be);
+    }
+
+    pub fn try_read_f64_le(&mut self) -> Result<f64> {
+        buf_try_read_do!(self, f64, le);
+    }
+
+    /// Read exactly `dest.len()` bytes.
+    pub fn try_read_bytes(&mut self, dest: &mut [u8]) -> Result<()> {
+        let end = self.check_read(dest.len())?;
+        dest.copy_from_slice(&self.buf[self.reader_index..end]);
+        self.reader_index = end;
+        Ok(())
+    }
+
+    pub fn try_get_u8(&self, index: usize) -> Result<u8> {
+        self.check_get(index, 1)?;
+        Ok(self.buf[index])
+    }
+
+    pub fn try_get_i8(&self, index: usize) -> Result<i8> {
+        Ok(self.try_get_u8(index)? as i8)
+    }
+
+    pub fn try_get_u16(&self, index: usize) -> Result<u16> {
+        buf_try_get_do!(self, index, u16, be);
+    }
+
+    pub fn try_get_u16_le(&self, index: usize) -> Result<u16> {
+        buf_try_get_do!(self, index, u16, le);
+    }
+
+    pub fn try_get_i16(&self, index: usize) -> Result<i16> {
+        buf_try_get_do!(self, index, i16, be);
+    }
+
+    pub fn try_get_i16_le(&self, index: usize) -> Result<i16> {
+        buf_try_get_do!(self, index, i16, le);
+    }
+
+    pub fn try_get_u32(&self, index: usize) -> Result<u32> {
+        buf_try_get_do!(self, index, u32, be);
+    }
+
+    pub fn try_get_u32_le(&self, index: usize) -> Result<u32> {
+        buf_try_get_do!(self, index, u32, le);
+    }
+
+    pub fn try_get_i32(&self, index: usize) -> Result<i32> {
+        buf_try_get_do!(self, index, i32, be);
+    }
+
+    pub fn try_get_i32_le(&self, index: usize) -> Result<i32> {
+        buf_try_get_do!(self, index, i32, le);
+    }
+
+    pub fn try_get_u64(&self, index: usize) -> Result<u64> {
+        buf_try_get_do!(self, index, u64, be);
+    }
+
+    pub fn try_get_u64_le(&self, index: usize) -> Result<u64> {
+        buf_try_get_do!(self, index, u64, le);
+    }
+
+    pub fn try_get_i64(&self, index: usize) -> Result<i64> {
+        buf_try_get_do!(self, index, i64, be);
+    }
+
+    pub fn try_get_i64_le(&self, index: usize) -> Result<i64> {
+        buf_try_get_do!(self, index, i64, le);
+    }
+
+    pub fn try_get_u128(&self, index: usize) -> Result<u128> {
+        buf_try_get_do!(self, index, u128, be);
+    }
+
+    pub fn try_get_u128_le(&self, index: usize) -> Result<u128> {
+        buf_try_get_do!(self, index, u128, le);
+    }
+
+    pub fn try_get_i128(&self, index: usize) -> Result<i128> {
+        buf_try_get_do!(self, index, i128, be);
+    }
+
+    pub fn try_get_i128_le(&self, index: usize) -> Result<i128> {
+        buf_try_get_do!(self, index, i128, le);
+    }
+
+    pub fn try_get_f32(&self, index: usize) -> Result<f32> {
+        buf_try_get_do!(self, index, f32, be);
+    }
+
+    pub fn try_get_f32_le(&self, index: usize) -> Result<f32> {
+        buf_try_get_do!(self, index, f32, le);
+    }
+
+    pub fn try_get_f64(&self, index: usize) -> Result<f64> {
+        buf_try_get_do!(self, index, f64, be);
+    }
+
+    pub fn try_get_f64_le(&self, index: usize) -> Result<f64> {
+        buf_try_get_do!(self, index, f64, le);
+    }
+
+    /// Get exactly `dest.len()` bytes from `index`.
+    pub fn try_get_bytes(&self, index: usize, dest: &mut [u8]) -> Result<()> {
+        let end = self.check_get(index, dest.len())?;
+        dest.copy_from_slice(&self.buf[index..end]);
+        Ok(())
+    }
+
     pub fn write_u8(&mut self, val: u8) {
         assert!(self.buf.len() >= (self.writer_index + 1));
         self.buf[self.writer_index] = val;
@@ -469,6 +666,19 @@ impl<'a> BufViewMut<'a> {
         self.buf[index..end].copy_from_slice(src);
     }
 
+    pub fn try_write_bytes(&mut self, src: &[u8]) -> Result<()> {
+        let end = self.check_get(self.writer_index, src.len())?;
+        self.buf[self.writer_index..end].copy_from_slice(src);
+        self.writer_index = end;
+        Ok(())
+    }
+
+    pub fn try_set_bytes(&mut self, index: usize, src: &[u8]) -> Result<()> {
+        let end = self.check_get(index, src.len())?;
+        self.buf[index..end].copy_from_slice(src);
+        Ok(())
+    }
+
     pub fn set_reader_index(&mut self, index: usize) {
         assert!(self.buf.len() >= index && index <= self.writer_index);
         self.reader_index = index;
@@ -513,6 +723,45 @@ impl<'a> BufViewMut<'a> {
     pub fn as_raw_slice(&mut self) -> &mut [u8] {
         self.buf
     }
+
+    /// Run `f`, and prepend the field `name` to the context path of its error.
+    pub fn context<T>(&mut self, name: &str, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        f(self).map_err(|e| e.context(name))
+    }
+
+    /// Run `f`, and prepend the element `name[index]` to the context path of its error.
+    pub fn context_index<T>(
+        &mut self,
+        name: &str,
+        index: usize,
+        f: impl FnOnce(&mut Self) -> Result<T>,
+    ) -> Result<T> {
+        f(self).map_err(|e| e.index(index).context(name))
+    }
+
+    pub(crate) fn check_read(&self, len: usize) -> Result<usize> {
+        if self.remaining() < len {
+            return Err(Error::out_of_bounds(
+                self.buf,
+                self.reader_index,
+                len,
+                self.remaining(),
+            ));
+        }
+        Ok(self.reader_index + len)
+    }
+
+    pub(crate) fn check_get(&self, index: usize, len: usize) -> Result<usize> {
+        match index.checked_add(len) {
+            Some(end) if end <= self.buf.len() => Ok(end),
+            _ => Err(Error::out_of_bounds(
+                self.buf,
+                index,
+                len,
+                self.buf.len().saturating_sub(index),
+            )),
+        }
+    }
 }
 
 impl std::fmt::Display for BufViewMut<'_> {
@@ -537,3 +786,31 @@ impl<'a> Write for BufViewMut<'a> {
         Ok(())
     }
 }
+
+impl ViewRead for BufViewMut<'_> {
+    fn read_bytes(&mut self, dest: &mut [u8]) -> usize {
+        BufViewMut::read_bytes(self, dest)
+    }
+
+    fn remaining(&self) -> usize {
+        BufViewMut::remaining(self)
+    }
+
+    fn reader_index(&self) -> usize {
+        BufViewMut::reader_index(self)
+    }
+}
+
+impl ViewWrite for BufViewMut<'_> {
+    fn write_bytes(&mut self, src: &[u8]) {
+        BufViewMut::write_bytes(self, src);
+    }
+
+    fn set_bytes(&mut self, index: usize, src: &[u8]) {
+        BufViewMut::set_bytes(self, index, src);
+    }
+
+    fn writer_index(&self) -> usize {
+        BufViewMut::writer_index(self)
+    }
+}
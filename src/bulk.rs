@@ -0,0 +1,146 @@
+//! Bulk reads and writes of primitive arrays.
+//!
+//! `read_u32_into` and siblings fill a whole slice after a single bounds check, the
+//! per element conversion is a plain loop over `chunks_exact` the compiler vectorizes.
+
+use crate::primitive::Primitive;
+use crate::{BufView, BufViewMut};
+use std::ops::Range;
+
+#[inline]
+fn decode_be<T: Primitive>(src: &[u8], dst: &mut [T]) {
+    for (d, c) in dst.iter_mut().zip(src.chunks_exact(T::SIZE)) {
+        *d = T::from_be_slice(c);
+    }
+}
+
+#[inline]
+fn decode_le<T: Primitive>(src: &[u8], dst: &mut [T]) {
+    for (d, c) in dst.iter_mut().zip(src.chunks_exact(T::SIZE)) {
+        *d = T::from_le_slice(c);
+    }
+}
+
+#[inline]
+fn encode_be<T: Primitive>(src: &[T], dst: &mut [u8]) {
+    for (c, s) in dst.chunks_exact_mut(T::SIZE).zip(src) {
+        s.to_be_slice(c);
+    }
+}
+
+#[inline]
+fn encode_le<T: Primitive>(src: &[T], dst: &mut [u8]) {
+    for (c, s) in dst.chunks_exact_mut(T::SIZE).zip(src) {
+        s.to_le_slice(c);
+    }
+}
+
+macro_rules! bulk_read_methods {
+    ($name:ident; $($typ:ty, $read:ident, $read_le:ident);* $(;)?) => {
+        impl $name<'_> {
+            $(
+                /// Fill `dst` with big endian values, the view must hold all of them.
+                pub fn $read(&mut self, dst: &mut [$typ]) {
+                    let end = self.reader_index + dst.len() * <$typ>::SIZE;
+                    assert!(self.writer_index >= end);
+                    decode_be(&self.buf[self.reader_index..end], dst);
+                    self.reader_index = end;
+                }
+
+                /// Fill `dst` with little endian values, the view must hold all of them.
+                pub fn $read_le(&mut self, dst: &mut [$typ]) {
+                    let end = self.reader_index + dst.len() * <$typ>::SIZE;
+                    assert!(self.writer_index >= end);
+                    decode_le(&self.buf[self.reader_index..end], dst);
+                    self.reader_index = end;
+                }
+            )*
+        }
+    };
+}
+
+macro_rules! bulk_read_impl {
+    ($($name:ident),*) => {
+        $(
+            bulk_read_methods!($name;
+                u16, read_u16_into, read_u16_into_le;
+                i16, read_i16_into, read_i16_into_le;
+                u32, read_u32_into, read_u32_into_le;
+                i32, read_i32_into, read_i32_into_le;
+                u64, read_u64_into, read_u64_into_le;
+                i64, read_i64_into, read_i64_into_le;
+                u128, read_u128_into, read_u128_into_le;
+                i128, read_i128_into, read_i128_into_le;
+                f32, read_f32_into, read_f32_into_le;
+                f64, read_f64_into, read_f64_into_le;
+            );
+        )*
+    };
+}
+
+bulk_read_impl!(BufView, BufViewMut);
+
+macro_rules! bulk_write_methods {
+    ($($typ:ty, $write:ident, $write_le:ident);* $(;)?) => {
+        impl BufViewMut<'_> {
+            $(
+                /// Write every value of `src` big endian.
+                pub fn $write(&mut self, src: &[$typ]) {
+                    let end = self.writer_index + src.len() * <$typ>::SIZE;
+                    assert!(self.buf.len() >= end);
+                    encode_be(src, &mut self.buf[self.writer_index..end]);
+                    self.writer_index = end;
+                }
+
+                /// Write every value of `src` little endian.
+                pub fn $write_le(&mut self, src: &[$typ]) {
+                    let end = self.writer_index + src.len() * <$typ>::SIZE;
+                    assert!(self.buf.len() >= end);
+                    encode_le(src, &mut self.buf[self.writer_index..end]);
+                    self.writer_index = end;
+                }
+            )*
+        }
+    };
+}
+
+bulk_write_methods!(
+    u16, write_u16_slice, write_u16_slice_le;
+    i16, write_i16_slice, write_i16_slice_le;
+    u32, write_u32_slice, write_u32_slice_le;
+    i32, write_i32_slice, write_i32_slice_le;
+    u64, write_u64_slice, write_u64_slice_le;
+    i64, write_i64_slice, write_i64_slice_le;
+    u128, write_u128_slice, write_u128_slice_le;
+    i128, write_i128_slice, write_i128_slice_le;
+    f32, write_f32_slice, write_f32_slice_le;
+    f64, write_f64_slice, write_f64_slice_le;
+);
+
+macro_rules! swap_bytes_methods {
+    ($($typ:ty, $swap:ident);* $(;)?) => {
+        impl BufViewMut<'_> {
+            $(
+                /// Reverse the byte order of every value in `range` of the wrapped buffer.
+                ///
+                /// The range is absolute like the index of `set_*`, its length must be a
+                /// multiple of the value size.
+                pub fn $swap(&mut self, range: Range<usize>) {
+                    const SIZE: usize = std::mem::size_of::<$typ>();
+                    assert!(range.len() % SIZE == 0);
+                    for c in self.buf[range].chunks_exact_mut(SIZE) {
+                        let val = <$typ>::from_ne_bytes(c.try_into().unwrap());
+                        c.copy_from_slice(&val.swap_bytes().to_ne_bytes());
+                    }
+                }
+            )*
+        }
+    };
+}
+
+swap_bytes_methods!(
+    u16, swap_bytes_in_place_u16;
+    u32, swap_bytes_in_place_u32;
+    u64, swap_bytes_in_place_u64;
+    u128, swap_bytes_in_place_u128;
+);
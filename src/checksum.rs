@@ -0,0 +1,442 @@
+//! Checksums over byte slices and views.
+//!
+//! Every algorithm is an incremental hasher implementing [`Checksum`], so the data may
+//! be fed in pieces. They implement [`std::hash::Hasher`] too, which makes them usable
+//! with [`HashingView`](crate::HashingView). BufView and BufViewMut can checksum their
+//! readable window with `checksum` or any range of the wrapped buffer with
+//! `checksum_range`.
+//!
+//! | type              | algorithm                             | used by                 |
+//! |-------------------|---------------------------------------|-------------------------|
+//! | `InternetChecksum`| RFC 1071 ones-complement sum          | IPv4, ICMP, UDP, TCP    |
+//! | `Crc16Arc`        | CRC-16/ARC                            | LHA, ARC                |
+//! | `Crc16Modbus`     | CRC-16/MODBUS                         | Modbus RTU              |
+//! | `Crc16X25`        | CRC-16/X-25 (IBM-SDLC)                | X.25, HDLC, PPP         |
+//! | `Crc16CcittFalse` | CRC-16/CCITT-FALSE                    | many embedded protocols |
+//! | `Crc32`           | CRC-32 (ISO-HDLC), slicing-by-8       | Ethernet, PNG, zip      |
+//! | `Crc32c`          | CRC-32C (Castagnoli), slicing-by-8    | iSCSI, ext4, SCTP       |
+//! | `Adler32`         | Adler-32                              | zlib                    |
+//!
+//! Example
+//! ```
+//! use buf_view::BufView;
+//! use buf_view::checksum::{Adler32, Checksum, Crc32};
+//!
+//! assert_eq!(Crc32::checksum(b"123456789"), 0xcbf43926);
+//!
+//! let mut crc = Crc32::new();
+//! crc.update(b"1234");
+//! crc.update(b"56789");
+//! assert_eq!(crc.finalize(), 0xcbf43926);
+//!
+//! let buf = b"xx123456789";
+//! let buf_view = BufView::wrap_with(buf, 2, buf.len());
+//! assert_eq!(buf_view.checksum::<Crc32>(), 0xcbf43926);
+//! assert_eq!(buf_view.checksum_range::<Adler32>(2..11), 0x091e01de);
+//! ```
+
+use crate::{BufView, BufViewMut};
+use std::hash::Hasher;
+use std::ops::Range;
+
+/// An incremental checksum.
+pub trait Checksum: Default {
+    type Output;
+
+    /// Feed `data` into the checksum.
+    fn update(&mut self, data: &[u8]);
+
+    /// The checksum of all data fed so far, the state is not consumed.
+    fn finalize(&self) -> Self::Output;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compute the checksum of `data` in one call.
+    fn checksum(data: &[u8]) -> Self::Output {
+        let mut c = Self::default();
+        c.update(data);
+        c.finalize()
+    }
+}
+
+/// The RFC 1071 internet checksum.
+///
+/// `finalize` returns the ones-complement of the ones-complement sum, which is the value
+/// stored in the checksum field. Checksumming data that already contains a valid
+/// checksum field yields 0.
+#[derive(Debug, Default, Clone)]
+pub struct InternetChecksum {
+    sum: u64,
+    // the odd trailing byte of the previous update, it is the high byte of the next word
+    pending: Option<u8>,
+}
+
+impl InternetChecksum {
+    /// Add a big endian 16-bit word, e.g. a field of a pseudo header.
+    pub fn add_u16(&mut self, val: u16) {
+        self.update(&val.to_be_bytes());
+    }
+
+    /// Add a big endian 32-bit word, e.g. an address of a pseudo header.
+    pub fn add_u32(&mut self, val: u32) {
+        self.update(&val.to_be_bytes());
+    }
+
+    fn fold(mut sum: u64) -> u16 {
+        while sum > 0xffff {
+            sum = (sum & 0xffff) + (sum >> 16);
+        }
+        sum as u16
+    }
+}
+
+impl Checksum for InternetChecksum {
+    type Output = u16;
+
+    fn update(&mut self, mut data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        if let Some(high) = self.pending.take() {
+            self.sum += u16::from_be_bytes([high, data[0]]) as u64;
+            data = &data[1..];
+        }
+
+        // 2^16 == 1 (mod 2^16 - 1), so summing 32-bit words folds to the same result
+        let mut chunks = data.chunks_exact(4);
+        let mut sum = 0u64;
+        for chunk in &mut chunks {
+            sum += u32::from_be_bytes(chunk.try_into().unwrap()) as u64;
+        }
+        let rest = chunks.remainder();
+        if rest.len() >= 2 {
+            sum += u16::from_be_bytes([rest[0], rest[1]]) as u64;
+        }
+        if rest.len() % 2 == 1 {
+            self.pending = Some(rest[rest.len() - 1]);
+        }
+        self.sum = Self::fold(self.sum + Self::fold(sum) as u64) as u64;
+    }
+
+    fn finalize(&self) -> u16 {
+        let mut sum = self.sum;
+        if let Some(high) = self.pending {
+            sum += (high as u64) << 8;
+        }
+        !Self::fold(sum)
+    }
+}
+
+const fn crc32_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut tables = [[0u32; 256]; 8];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+    let mut t = 1;
+    while t < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[t - 1][i];
+            tables[t][i] = (prev >> 8) ^ tables[0][(prev & 0xff) as usize];
+            i += 1;
+        }
+        t += 1;
+    }
+    tables
+}
+
+fn crc32_update(tables: &[[u32; 256]; 8], mut crc: u32, data: &[u8]) -> u32 {
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let lo = crc ^ u32::from_le_bytes(chunk[..4].try_into().unwrap());
+        let hi = u32::from_le_bytes(chunk[4..].try_into().unwrap());
+        crc = tables[7][(lo & 0xff) as usize]
+            ^ tables[6][((lo >> 8) & 0xff) as usize]
+            ^ tables[5][((lo >> 16) & 0xff) as usize]
+            ^ tables[4][(lo >> 24) as usize]
+            ^ tables[3][(hi & 0xff) as usize]
+            ^ tables[2][((hi >> 8) & 0xff) as usize]
+            ^ tables[1][((hi >> 16) & 0xff) as usize]
+            ^ tables[0][(hi >> 24) as usize];
+    }
+    for b in chunks.remainder() {
+        crc = (crc >> 8) ^ tables[0][((crc ^ *b as u32) & 0xff) as usize];
+    }
+    crc
+}
+
+static CRC32_TABLES: [[u32; 256]; 8] = crc32_tables(0xedb88320);
+static CRC32C_TABLES: [[u32; 256]; 8] = crc32_tables(0x82f63b78);
+
+/// CRC-32 (ISO-HDLC), the CRC of Ethernet, PNG, zip and gzip.
+#[derive(Debug, Clone)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Crc32 { crc: !0 }
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        self.crc = crc32_update(&CRC32_TABLES, self.crc, data);
+    }
+
+    fn finalize(&self) -> u32 {
+        !self.crc
+    }
+}
+
+/// CRC-32C (Castagnoli), the CRC of iSCSI, ext4, btrfs and SCTP.
+#[derive(Debug, Clone)]
+pub struct Crc32c {
+    crc: u32,
+}
+
+impl Default for Crc32c {
+    fn default() -> Self {
+        Crc32c { crc: !0 }
+    }
+}
+
+impl Checksum for Crc32c {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        self.crc = crc32_update(&CRC32C_TABLES, self.crc, data);
+    }
+
+    fn finalize(&self) -> u32 {
+        !self.crc
+    }
+}
+
+const fn crc16_table(poly: u16, reflected: bool) -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc;
+        let mut bit = 0;
+        if reflected {
+            crc = i as u16;
+            while bit < 8 {
+                crc = if crc & 1 != 0 {
+                    (crc >> 1) ^ poly
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+        } else {
+            crc = (i as u16) << 8;
+            while bit < 8 {
+                crc = if crc & 0x8000 != 0 {
+                    (crc << 1) ^ poly
+                } else {
+                    crc << 1
+                };
+                bit += 1;
+            }
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+static CRC16_A001_TABLE: [u16; 256] = crc16_table(0xa001, true);
+static CRC16_8408_TABLE: [u16; 256] = crc16_table(0x8408, true);
+static CRC16_1021_TABLE: [u16; 256] = crc16_table(0x1021, false);
+
+fn crc16_update_reflected(table: &[u16; 256], mut crc: u16, data: &[u8]) -> u16 {
+    for b in data {
+        crc = (crc >> 8) ^ table[((crc ^ *b as u16) & 0xff) as usize];
+    }
+    crc
+}
+
+fn crc16_update(table: &[u16; 256], mut crc: u16, data: &[u8]) -> u16 {
+    for b in data {
+        crc = (crc << 8) ^ table[((crc >> 8) ^ *b as u16) as usize];
+    }
+    crc
+}
+
+macro_rules! crc16_checksum {
+    ($(#[$doc:meta])* $name:ident, $table:ident, $update:ident, $init:expr, $xorout:expr) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone)]
+        pub struct $name {
+            crc: u16,
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name { crc: $init }
+            }
+        }
+
+        impl Checksum for $name {
+            type Output = u16;
+
+            fn update(&mut self, data: &[u8]) {
+                self.crc = $update(&$table, self.crc, data);
+            }
+
+            fn finalize(&self) -> u16 {
+                self.crc ^ $xorout
+            }
+        }
+    };
+}
+
+crc16_checksum!(
+    /// CRC-16/ARC, reflected polynomial 0x8005 with init 0.
+    Crc16Arc,
+    CRC16_A001_TABLE,
+    crc16_update_reflected,
+    0,
+    0
+);
+
+crc16_checksum!(
+    /// CRC-16/MODBUS, reflected polynomial 0x8005 with init 0xffff.
+    ///
+    /// Modbus RTU sends the result low byte first.
+    Crc16Modbus,
+    CRC16_A001_TABLE,
+    crc16_update_reflected,
+    0xffff,
+    0
+);
+
+crc16_checksum!(
+    /// CRC-16/X-25, also known as CRC-16/IBM-SDLC, the FCS of X.25, HDLC and PPP.
+    Crc16X25,
+    CRC16_8408_TABLE,
+    crc16_update_reflected,
+    0xffff,
+    0xffff
+);
+
+crc16_checksum!(
+    /// CRC-16/CCITT-FALSE, non reflected polynomial 0x1021 with init 0xffff.
+    Crc16CcittFalse,
+    CRC16_1021_TABLE,
+    crc16_update,
+    0xffff,
+    0
+);
+
+/// Adler-32, the checksum of zlib streams.
+#[derive(Debug, Clone)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+}
+
+impl Adler32 {
+    const MOD: u32 = 65521;
+    // the largest n such that 255 * n * (n + 1) / 2 + (n + 1) * (MOD - 1) fits in u32
+    const NMAX: usize = 5552;
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, data: &[u8]) {
+        let (mut a, mut b) = (self.a, self.b);
+        for chunk in data.chunks(Self::NMAX) {
+            for byte in chunk {
+                a += *byte as u32;
+                b += a;
+            }
+            a %= Self::MOD;
+            b %= Self::MOD;
+        }
+        self.a = a;
+        self.b = b;
+    }
+
+    fn finalize(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+}
+
+macro_rules! checksum_hasher {
+    ($($name:ident),*) => {
+        $(
+            impl Hasher for $name {
+                fn write(&mut self, bytes: &[u8]) {
+                    self.update(bytes);
+                }
+
+                fn finish(&self) -> u64 {
+                    self.finalize() as u64
+                }
+            }
+        )*
+    };
+}
+
+checksum_hasher!(
+    InternetChecksum,
+    Crc16Arc,
+    Crc16Modbus,
+    Crc16X25,
+    Crc16CcittFalse,
+    Crc32,
+    Crc32c,
+    Adler32
+);
+
+impl BufView<'_> {
+    /// Checksum the readable window, between reader_index and writer_index.
+    pub fn checksum<C: Checksum>(&self) -> C::Output {
+        C::checksum(&self.buf[self.reader_index..self.writer_index])
+    }
+
+    /// Checksum `range` of the wrapped buffer, regardless of reader_index and writer_index.
+    pub fn checksum_range<C: Checksum>(&self, range: Range<usize>) -> C::Output {
+        assert!(range.start <= range.end && self.buf.len() >= range.end);
+        C::checksum(&self.buf[range])
+    }
+}
+
+impl BufViewMut<'_> {
+    /// Checksum the readable window, between reader_index and writer_index.
+    pub fn checksum<C: Checksum>(&self) -> C::Output {
+        C::checksum(&self.buf[self.reader_index..self.writer_index])
+    }
+
+    /// Checksum `range` of the wrapped buffer, regardless of reader_index and writer_index.
+    pub fn checksum_range<C: Checksum>(&self, range: Range<usize>) -> C::Output {
+        assert!(range.start <= range.end && self.buf.len() >= range.end);
+        C::checksum(&self.buf[range])
+    }
+}
@@ -0,0 +1,287 @@
+//! A facade matching the JavaScript `DataView` and typed array APIs, to translate JS
+//! code one to one.
+
+use crate::array_iter::ArrayIter;
+use crate::endian::Endian;
+use crate::error::{Error, Result};
+use crate::float::{f16_to_f32, f32_to_f16};
+use crate::primitive::Primitive;
+use crate::{BufView, BufViewMut};
+use std::marker::PhantomData;
+
+/// The JavaScript `DataView` API over a byte buffer.
+///
+/// Offsets are byte offsets in the buffer and every accessor takes `little_endian` like
+/// its JS counterpart, `false` being big endian. Where JS throws a `RangeError` the
+/// accessors return an `OutOfBounds` error. The setters take the exact Rust type, so
+/// the JS `ToInt32`-style wrapping is an `as` cast at the call site.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, DataView};
+///
+/// // const view = new DataView(new ArrayBuffer(16));
+/// let mut buf = [0u8; 16];
+/// let mut view = DataView::new(&mut buf[..]);
+/// // view.setFloat32(0, 1.5, true); view.setBigUint64(4, 0xffn);
+/// view.set_float32(0, 1.5, true).unwrap();
+/// view.set_big_uint64(4, 0xff, false).unwrap();
+/// assert_eq!(view.get_float32(0, true), Ok(1.5));
+/// assert_eq!(view.get_uint8(11), Ok(0xff));
+/// // view.getUint32(14) throws a RangeError
+/// assert!(view.get_uint32(14, false).is_err());
+///
+/// let buf_view = BufView::wrap(&buf);
+/// assert_eq!(buf_view.data_view().get_int16(4, false), Ok(0));
+/// ```
+#[derive(Debug)]
+pub struct DataView<B> {
+    buf: B,
+}
+
+macro_rules! data_view_get {
+    ($($typ:ty, $get:ident);* $(;)?) => {
+        $(
+            pub fn $get(&self, offset: usize, little_endian: bool) -> Result<$typ> {
+                self.get(offset, little_endian)
+            }
+        )*
+    };
+}
+
+macro_rules! data_view_set {
+    ($($typ:ty, $set:ident);* $(;)?) => {
+        $(
+            pub fn $set(&mut self, offset: usize, val: $typ, little_endian: bool) -> Result<()> {
+                self.set(offset, val, little_endian)
+            }
+        )*
+    };
+}
+
+impl<B: AsRef<[u8]>> DataView<B> {
+    pub fn new(buf: B) -> Self {
+        DataView { buf }
+    }
+
+    pub fn byte_length(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+
+    pub fn get_int8(&self, offset: usize) -> Result<i8> {
+        self.get(offset, false)
+    }
+
+    pub fn get_uint8(&self, offset: usize) -> Result<u8> {
+        self.get(offset, false)
+    }
+
+    data_view_get!(
+        i16, get_int16;
+        u16, get_uint16;
+        i32, get_int32;
+        u32, get_uint32;
+        f32, get_float32;
+        f64, get_float64;
+        i64, get_big_int64;
+        u64, get_big_uint64;
+    );
+
+    /// `getFloat16`, the half precision value widened to f32.
+    pub fn get_float16(&self, offset: usize, little_endian: bool) -> Result<f32> {
+        self.get(offset, little_endian).map(f16_to_f32)
+    }
+
+    fn get<T: Primitive>(&self, offset: usize, little_endian: bool) -> Result<T> {
+        let buf = self.buf.as_ref();
+        let bytes = range_check(buf, offset, T::SIZE)?;
+        Ok(if little_endian {
+            T::from_le_slice(bytes)
+        } else {
+            T::from_be_slice(bytes)
+        })
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> DataView<B> {
+    pub fn set_int8(&mut self, offset: usize, val: i8) -> Result<()> {
+        self.set(offset, val, false)
+    }
+
+    pub fn set_uint8(&mut self, offset: usize, val: u8) -> Result<()> {
+        self.set(offset, val, false)
+    }
+
+    data_view_set!(
+        i16, set_int16;
+        u16, set_uint16;
+        i32, set_int32;
+        u32, set_uint32;
+        f32, set_float32;
+        f64, set_float64;
+        i64, set_big_int64;
+        u64, set_big_uint64;
+    );
+
+    /// `setFloat16`, `val` is rounded to half precision.
+    pub fn set_float16(&mut self, offset: usize, val: f32, little_endian: bool) -> Result<()> {
+        self.set(offset, f32_to_f16(val), little_endian)
+    }
+
+    fn set<T: Primitive>(&mut self, offset: usize, val: T, little_endian: bool) -> Result<()> {
+        range_check(self.buf.as_ref(), offset, T::SIZE)?;
+        let dest = &mut self.buf.as_mut()[offset..offset + T::SIZE];
+        if little_endian {
+            val.to_le_slice(dest);
+        } else {
+            val.to_be_slice(dest);
+        }
+        Ok(())
+    }
+}
+
+fn range_check(buf: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    match offset.checked_add(len) {
+        Some(end) if end <= buf.len() => Ok(&buf[offset..end]),
+        _ => Err(Error::out_of_bounds(
+            buf,
+            offset,
+            len,
+            buf.len().saturating_sub(offset),
+        )),
+    }
+}
+
+/// A JavaScript typed array (`Uint16Array`, `Float32Array`...) over a buffer region,
+/// with elements `T` in the byte order `E`.
+///
+/// Like in JS, `get` returns None out of range and `set` out of range does nothing.
+///
+/// Example
+/// ```
+/// use buf_view::{BufViewMut, LittleEndian};
+///
+/// let mut buf = [0u8; 10];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// // new Uint16Array(buffer, 2, 3)
+/// let mut arr = buf_view.typed_array::<u16, LittleEndian>(2, Some(3)).unwrap();
+/// arr.set(0, 0x0102);
+/// arr.set(3, 7);
+/// assert_eq!(arr.len(), 3);
+/// assert_eq!(arr.get(0), Some(0x0102));
+/// assert_eq!(arr.get(3), None);
+/// assert_eq!(buf, [0, 0, 2, 1, 0, 0, 0, 0, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct TypedArrayView<'a, T, E> {
+    bytes: &'a mut [u8],
+    marker: PhantomData<(T, E)>,
+}
+
+impl<'a, T: Primitive, E: Endian> TypedArrayView<'a, T, E> {
+    /// The typed array of `length` elements at `byte_offset` in `buf`, like
+    /// `new Uint32Array(buffer, byteOffset, length)`.
+    ///
+    /// Without `length` the array extends to the end of `buf`. Fail with `Invalid` when
+    /// `byte_offset`, or the remaining length when `length` is None, is not a multiple
+    /// of the element size, and with `OutOfBounds` when the array does not fit.
+    pub fn new(buf: &'a mut [u8], byte_offset: usize, length: Option<usize>) -> Result<Self> {
+        if !byte_offset.is_multiple_of(T::SIZE) {
+            return Err(Error::invalid(
+                "start offset should be a multiple of the element size",
+                buf,
+                byte_offset,
+            ));
+        }
+        let byte_length = match length {
+            Some(length) => length.saturating_mul(T::SIZE),
+            None => {
+                let byte_length = buf.len().saturating_sub(byte_offset);
+                if !byte_length.is_multiple_of(T::SIZE) {
+                    return Err(Error::invalid(
+                        "buffer length should be a multiple of the element size",
+                        buf,
+                        byte_offset,
+                    ));
+                }
+                byte_length
+            }
+        };
+        range_check(buf, byte_offset, byte_length)?;
+        Ok(TypedArrayView {
+            bytes: &mut buf[byte_offset..byte_offset + byte_length],
+            marker: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn byte_length(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+        let start = index * T::SIZE;
+        Some(E::decode(&self.bytes[start..start + T::SIZE]))
+    }
+
+    /// Set the element at `index`, an out of range `index` is ignored.
+    pub fn set(&mut self, index: usize, val: T) {
+        if index < self.len() {
+            let start = index * T::SIZE;
+            E::encode(val, &mut self.bytes[start..start + T::SIZE]);
+        }
+    }
+
+    pub fn fill(&mut self, val: T) {
+        for c in self.bytes.chunks_exact_mut(T::SIZE) {
+            E::encode(val, c);
+        }
+    }
+
+    pub fn iter(&self) -> ArrayIter<'_, T, E> {
+        ArrayIter::new(self.bytes)
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+impl<'a> BufView<'a> {
+    /// A `DataView` over the whole wrapped buffer, offsets are absolute like the index
+    /// of `get_*`.
+    pub fn data_view(&self) -> DataView<&'a [u8]> {
+        DataView::new(self.buf)
+    }
+}
+
+impl BufViewMut<'_> {
+    /// A `DataView` over the whole wrapped buffer, offsets are absolute like the index
+    /// of `get_*` and `set_*`.
+    pub fn data_view(&mut self) -> DataView<&mut [u8]> {
+        DataView::new(&mut *self.buf)
+    }
+
+    /// A typed array over the wrapped buffer, see `TypedArrayView::new`.
+    pub fn typed_array<T: Primitive, E: Endian>(
+        &mut self,
+        byte_offset: usize,
+        length: Option<usize>,
+    ) -> Result<TypedArrayView<'_, T, E>> {
+        TypedArrayView::new(&mut *self.buf, byte_offset, length)
+    }
+}
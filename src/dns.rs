@@ -0,0 +1,928 @@
+//! DNS message decoding and encoding with name compression, behind the `dns` feature.
+//!
+//! A [`Message`] is validated once when parsed, then its sections are iterated without
+//! errors. Names are views into the message, compression pointers are followed with
+//! random-access gets: a pointer must point before the labels it was reached from, so
+//! pointer loops are rejected, and at most [`MAX_POINTERS`] pointers are followed per
+//! name. Error offsets are relative to the message start.
+//!
+//! The [`Encoder`] writes a message at the writer index of a [`BufViewMut`], names
+//! written through it are compressed against the suffixes of the names written before,
+//! and `finish` back-patches the section counts.
+//!
+//! Example
+//! ```
+//! use buf_view::dns::{class, rtype, Encoder, Message, RData, Section};
+//! use buf_view::{BufView, BufViewMut};
+//! use std::net::Ipv4Addr;
+//!
+//! let mut buf = [0u8; 512];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let mut enc = Encoder::new(&mut buf_view, 0x1234, 0x8180).unwrap();
+//! enc.add_question("www.example.com", rtype::A, class::IN).unwrap();
+//! enc.add_a(Section::Answer, "www.example.com", 300, Ipv4Addr::new(192, 0, 2, 1))
+//!     .unwrap();
+//! let len = enc.finish().unwrap();
+//! // the answer name is a single pointer to the question name
+//! assert_eq!(len, 12 + 17 + 4 + 2 + 10 + 4);
+//!
+//! let mut buf_view = BufView::wrap(&buf[..len]);
+//! let msg = buf_view.read_dns_message().unwrap();
+//! assert_eq!(msg.header().id, 0x1234);
+//! let question = msg.questions().next().unwrap();
+//! assert_eq!(question.name.to_string(), "www.example.com");
+//! let answer = msg.answers().next().unwrap();
+//! assert!(answer.name.eq_str("WWW.Example.com."));
+//! assert_eq!(answer.data(), Ok(RData::A(Ipv4Addr::new(192, 0, 2, 1))));
+//! ```
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::{BufView, BufViewMut};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// The maximum number of compression pointers followed while decoding one name.
+pub const MAX_POINTERS: usize = 32;
+
+/// The maximum length of a name on the wire, length bytes and root label included.
+pub const MAX_NAME_LEN: usize = 255;
+
+/// The maximum length of a label.
+pub const MAX_LABEL_LEN: usize = 63;
+
+const HEADER_LEN: usize = 12;
+
+/// Resource record types.
+pub mod rtype {
+    pub const A: u16 = 1;
+    pub const NS: u16 = 2;
+    pub const CNAME: u16 = 5;
+    pub const SOA: u16 = 6;
+    pub const PTR: u16 = 12;
+    pub const MX: u16 = 15;
+    pub const TXT: u16 = 16;
+    pub const AAAA: u16 = 28;
+    pub const SRV: u16 = 33;
+    pub const OPT: u16 = 41;
+    pub const ANY: u16 = 255;
+}
+
+/// Resource record classes.
+pub mod class {
+    pub const IN: u16 = 1;
+    pub const CH: u16 = 3;
+    pub const ANY: u16 = 255;
+}
+
+/// The fixed 12 bytes header of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DnsHeader {
+    pub id: u16,
+    /// The QR, opcode, AA, TC, RD, RA, Z and rcode bits.
+    pub flags: u16,
+    pub qd_count: u16,
+    pub an_count: u16,
+    pub ns_count: u16,
+    pub ar_count: u16,
+}
+
+impl DnsHeader {
+    /// The message is a response.
+    pub fn qr(&self) -> bool {
+        self.flags & 0x8000 != 0
+    }
+
+    pub fn opcode(&self) -> u8 {
+        ((self.flags >> 11) & 0x0f) as u8
+    }
+
+    /// Authoritative answer.
+    pub fn aa(&self) -> bool {
+        self.flags & 0x0400 != 0
+    }
+
+    /// Truncated.
+    pub fn tc(&self) -> bool {
+        self.flags & 0x0200 != 0
+    }
+
+    /// Recursion desired.
+    pub fn rd(&self) -> bool {
+        self.flags & 0x0100 != 0
+    }
+
+    /// Recursion available.
+    pub fn ra(&self) -> bool {
+        self.flags & 0x0080 != 0
+    }
+
+    pub fn rcode(&self) -> u8 {
+        (self.flags & 0x0f) as u8
+    }
+}
+
+/// A domain name, borrowed from a validated message.
+///
+/// Displayed as dotted labels without the trailing dot, the root name as `.`. Dots,
+/// backslashes and bytes outside printable ASCII in labels are escaped as `\.`, `\\`
+/// and `\DDD`.
+#[derive(Clone, Copy)]
+pub struct Name<'a> {
+    msg: &'a [u8],
+    start: usize,
+}
+
+impl<'a> Name<'a> {
+    /// Validate the name at `start` of `msg` and return it with its length on the wire,
+    /// which stops after the first pointer.
+    fn parse(view: &BufView<'a>, start: usize) -> Result<(Name<'a>, usize)> {
+        let mut pos = start;
+        let mut run_start = start;
+        let mut name_len = 1;
+        let mut pointers = 0;
+        let mut wire_end = None;
+        loop {
+            let len = view.try_get_u8(pos)?;
+            match len & 0xc0 {
+                0x00 if len == 0 => {
+                    let end = *wire_end.get_or_insert(pos + 1);
+                    return Ok((
+                        Name {
+                            msg: view.buf,
+                            start,
+                        },
+                        end - start,
+                    ));
+                }
+                0x00 => {
+                    name_len += len as usize + 1;
+                    if name_len > MAX_NAME_LEN {
+                        return Err(Error::invalid("DNS name too long", view.buf, start));
+                    }
+                    view.check_get(pos + 1, len as usize)?;
+                    pos += len as usize + 1;
+                }
+                0xc0 => {
+                    let target = (view.try_get_u16(pos)? & 0x3fff) as usize;
+                    wire_end.get_or_insert(pos + 2);
+                    pointers += 1;
+                    if pointers > MAX_POINTERS {
+                        return Err(Error::invalid(
+                            "too many DNS compression pointers",
+                            view.buf,
+                            pos,
+                        ));
+                    }
+                    if target >= run_start {
+                        return Err(Error::invalid(
+                            "DNS compression pointer loop",
+                            view.buf,
+                            pos,
+                        ));
+                    }
+                    run_start = target;
+                    pos = target;
+                }
+                _ => return Err(Error::invalid("bad DNS label type", view.buf, pos)),
+            }
+        }
+    }
+
+    /// The labels, from the leftmost one, the root label excluded.
+    pub fn labels(&self) -> Labels<'a> {
+        Labels {
+            msg: self.msg,
+            pos: self.start,
+        }
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.labels().next().is_none()
+    }
+
+    /// Compare with a dotted name ignoring ASCII case, a trailing dot is optional.
+    ///
+    /// Labels of `name` are split on every dot, so labels containing dots never match.
+    pub fn eq_str(&self, name: &str) -> bool {
+        let name = name.strip_suffix('.').unwrap_or(name);
+        let mut labels = self.labels();
+        if name.is_empty() {
+            return labels.next().is_none();
+        }
+        name.split('.')
+            .all(|part| matches!(labels.next(), Some(l) if l.eq_ignore_ascii_case(part.as_bytes())))
+            && labels.next().is_none()
+    }
+}
+
+impl PartialEq for Name<'_> {
+    /// Names are equal when their labels are, ignoring ASCII case.
+    fn eq(&self, other: &Self) -> bool {
+        let mut other = other.labels();
+        self.labels()
+            .all(|l| matches!(other.next(), Some(o) if l.eq_ignore_ascii_case(o)))
+            && other.next().is_none()
+    }
+}
+
+impl Eq for Name<'_> {}
+
+impl fmt::Display for Name<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_root() {
+            return f.write_str(".");
+        }
+        for (i, label) in self.labels().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            for &b in label {
+                match b {
+                    b'.' | b'\\' => write!(f, "\\{}", b as char)?,
+                    0x21..=0x7e => write!(f, "{}", b as char)?,
+                    _ => write!(f, "\\{:03}", b)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Name<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Name({})", self)
+    }
+}
+
+/// An iterator over the labels of a [`Name`], following the compression pointers.
+#[derive(Debug, Clone)]
+pub struct Labels<'a> {
+    msg: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the name was validated when parsed
+        loop {
+            let len = self.msg[self.pos] as usize;
+            if len >= 0xc0 {
+                self.pos = ((len & 0x3f) << 8) | self.msg[self.pos + 1] as usize;
+            } else if len == 0 {
+                return None;
+            } else {
+                let label = &self.msg[self.pos + 1..self.pos + 1 + len];
+                self.pos += 1 + len;
+                return Some(label);
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Labels<'_> {}
+
+/// An entry of the question section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Question<'a> {
+    pub name: Name<'a>,
+    pub qtype: u16,
+    pub qclass: u16,
+}
+
+impl<'a> Question<'a> {
+    fn parse(view: &BufView<'a>, start: usize) -> Result<(Question<'a>, usize)> {
+        let (name, len) = Name::parse(view, start)?;
+        let pos = start + len;
+        view.check_get(pos, 4)?;
+        let question = Question {
+            name,
+            qtype: view.try_get_u16(pos)?,
+            qclass: view.try_get_u16(pos + 2)?,
+        };
+        Ok((question, len + 4))
+    }
+}
+
+/// A resource record of the answer, authority or additional section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub name: Name<'a>,
+    pub rtype: u16,
+    pub class: u16,
+    pub ttl: u32,
+    /// The raw record data.
+    pub rdata: &'a [u8],
+    msg: &'a [u8],
+    rdata_offset: usize,
+}
+
+impl<'a> Record<'a> {
+    fn parse(view: &BufView<'a>, start: usize) -> Result<(Record<'a>, usize)> {
+        let (name, len) = Name::parse(view, start)?;
+        let pos = start + len;
+        view.check_get(pos, 10)?;
+        let rdlength = view.try_get_u16(pos + 8)? as usize;
+        let end = view.check_get(pos + 10, rdlength)?;
+        let record = Record {
+            name,
+            rtype: view.try_get_u16(pos)?,
+            class: view.try_get_u16(pos + 2)?,
+            ttl: view.try_get_u32(pos + 4)?,
+            rdata: &view.buf[pos + 10..end],
+            msg: view.buf,
+            rdata_offset: pos + 10,
+        };
+        Ok((record, end - start))
+    }
+
+    /// The offset of the record data in the message.
+    pub fn rdata_offset(&self) -> usize {
+        self.rdata_offset
+    }
+
+    /// Decode the record data of the common types, other types are [`RData::Other`].
+    ///
+    /// Names in the record data may be compressed, they must end within the record data.
+    pub fn data(&self) -> Result<RData<'a>> {
+        let view = BufView::wrap(&self.msg[..self.rdata_offset + self.rdata.len()]);
+        let start = self.rdata_offset;
+        let end = start + self.rdata.len();
+        let fixed = |len: usize| {
+            if self.rdata.len() == len {
+                Ok(())
+            } else {
+                Err(Error::invalid(
+                    "bad DNS record data length",
+                    view.buf,
+                    start,
+                ))
+            }
+        };
+        let name_at = |pos: usize| -> Result<(Name<'a>, usize)> {
+            let (name, len) = Name::parse(&view, pos)?;
+            Ok((name, pos + len))
+        };
+        let done = |pos: usize| {
+            if pos == end {
+                Ok(())
+            } else {
+                Err(Error::invalid("bad DNS record data length", view.buf, pos))
+            }
+        };
+        let data = match self.rtype {
+            rtype::A => {
+                fixed(4)?;
+                RData::A(Ipv4Addr::from(view.try_get_u32(start)?))
+            }
+            rtype::AAAA => {
+                fixed(16)?;
+                let mut octets = [0u8; 16];
+                view.try_get_bytes(start, &mut octets)?;
+                RData::Aaaa(Ipv6Addr::from(octets))
+            }
+            rtype::NS | rtype::CNAME | rtype::PTR => {
+                let (name, pos) = name_at(start)?;
+                done(pos)?;
+                match self.rtype {
+                    rtype::NS => RData::Ns(name),
+                    rtype::CNAME => RData::Cname(name),
+                    _ => RData::Ptr(name),
+                }
+            }
+            rtype::MX => {
+                let preference = view.try_get_u16(start)?;
+                let (exchange, pos) = name_at(start + 2)?;
+                done(pos)?;
+                RData::Mx {
+                    preference,
+                    exchange,
+                }
+            }
+            rtype::TXT => {
+                let mut pos = start;
+                while pos < end {
+                    let len = view.try_get_u8(pos)? as usize;
+                    pos = view.check_get(pos + 1, len)?;
+                }
+                RData::Txt(TxtStrings { rdata: self.rdata })
+            }
+            rtype::SOA => {
+                let (mname, pos) = name_at(start)?;
+                let (rname, pos) = name_at(pos)?;
+                view.check_get(pos, 20)?;
+                done(pos + 20)?;
+                RData::Soa {
+                    mname,
+                    rname,
+                    serial: view.try_get_u32(pos)?,
+                    refresh: view.try_get_u32(pos + 4)?,
+                    retry: view.try_get_u32(pos + 8)?,
+                    expire: view.try_get_u32(pos + 12)?,
+                    minimum: view.try_get_u32(pos + 16)?,
+                }
+            }
+            rtype::SRV => {
+                view.check_get(start, 6)?;
+                let (target, pos) = name_at(start + 6)?;
+                done(pos)?;
+                RData::Srv {
+                    priority: view.try_get_u16(start)?,
+                    weight: view.try_get_u16(start + 2)?,
+                    port: view.try_get_u16(start + 4)?,
+                    target,
+                }
+            }
+            _ => RData::Other(self.rdata),
+        };
+        Ok(data)
+    }
+}
+
+/// Decoded record data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RData<'a> {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Ns(Name<'a>),
+    Cname(Name<'a>),
+    Ptr(Name<'a>),
+    Mx {
+        preference: u16,
+        exchange: Name<'a>,
+    },
+    Txt(TxtStrings<'a>),
+    Soa {
+        mname: Name<'a>,
+        rname: Name<'a>,
+        serial: u32,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum: u32,
+    },
+    Srv {
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: Name<'a>,
+    },
+    /// The raw data of another type.
+    Other(&'a [u8]),
+}
+
+/// The character strings of a validated TXT record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxtStrings<'a> {
+    rdata: &'a [u8],
+}
+
+impl<'a> Iterator for TxtStrings<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&len, rest) = self.rdata.split_first()?;
+        let (s, rest) = rest.split_at(len as usize);
+        self.rdata = rest;
+        Some(s)
+    }
+}
+
+/// A validated DNS message.
+#[derive(Debug, Clone, Copy)]
+pub struct Message<'a> {
+    msg: &'a [u8],
+    header: DnsHeader,
+    sections: [usize; 4],
+    len: usize,
+}
+
+impl<'a> Message<'a> {
+    /// Parse and validate the message at the start of `msg`, the bytes after the last
+    /// record are ignored.
+    pub fn parse(msg: &'a [u8]) -> Result<Self> {
+        let view = BufView::wrap(msg);
+        view.check_get(0, HEADER_LEN)?;
+        let header = DnsHeader {
+            id: view.try_get_u16(0)?,
+            flags: view.try_get_u16(2)?,
+            qd_count: view.try_get_u16(4)?,
+            an_count: view.try_get_u16(6)?,
+            ns_count: view.try_get_u16(8)?,
+            ar_count: view.try_get_u16(10)?,
+        };
+        let mut pos = HEADER_LEN;
+        let mut sections = [0; 4];
+        sections[0] = pos;
+        for _ in 0..header.qd_count {
+            pos += Question::parse(&view, pos)?.1;
+        }
+        let counts = [header.an_count, header.ns_count, header.ar_count];
+        for (i, &count) in counts.iter().enumerate() {
+            sections[i + 1] = pos;
+            for _ in 0..count {
+                pos += Record::parse(&view, pos)?.1;
+            }
+        }
+        Ok(Message {
+            msg,
+            header,
+            sections,
+            len: pos,
+        })
+    }
+
+    pub fn header(&self) -> &DnsHeader {
+        &self.header
+    }
+
+    /// The length of the message on the wire, up to the end of the last record.
+    pub fn wire_len(&self) -> usize {
+        self.len
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.msg[..self.len]
+    }
+
+    pub fn questions(&self) -> Questions<'a> {
+        Questions {
+            msg: self.msg,
+            pos: self.sections[0],
+            remaining: self.header.qd_count,
+        }
+    }
+
+    pub fn answers(&self) -> Records<'a> {
+        self.records(1, self.header.an_count)
+    }
+
+    pub fn authorities(&self) -> Records<'a> {
+        self.records(2, self.header.ns_count)
+    }
+
+    pub fn additionals(&self) -> Records<'a> {
+        self.records(3, self.header.ar_count)
+    }
+
+    fn records(&self, section: usize, count: u16) -> Records<'a> {
+        Records {
+            msg: self.msg,
+            pos: self.sections[section],
+            remaining: count,
+        }
+    }
+}
+
+/// An iterator over the question section of a [`Message`].
+#[derive(Debug, Clone)]
+pub struct Questions<'a> {
+    msg: &'a [u8],
+    pos: usize,
+    remaining: u16,
+}
+
+impl<'a> Iterator for Questions<'a> {
+    type Item = Question<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (question, len) =
+            Question::parse(&BufView::wrap(self.msg), self.pos).expect("validated");
+        self.pos += len;
+        Some(question)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ExactSizeIterator for Questions<'_> {}
+
+/// An iterator over a record section of a [`Message`].
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    msg: &'a [u8],
+    pos: usize,
+    remaining: u16,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Record<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let (record, len) = Record::parse(&BufView::wrap(self.msg), self.pos).expect("validated");
+        self.pos += len;
+        Some(record)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as usize, Some(self.remaining as usize))
+    }
+}
+
+impl ExactSizeIterator for Records<'_> {}
+
+impl<'a> BufView<'a> {
+    /// Parse the DNS message starting at the reader index and consume it, nothing is
+    /// consumed on failure. Compression offsets are relative to the reader index, error
+    /// offsets are absolute.
+    pub fn read_dns_message(&mut self) -> Result<Message<'a>> {
+        let msg = &self.buf[self.reader_index..self.writer_index];
+        let message = Message::parse(msg)
+            .map_err(|e| Error::new(e.kind().clone(), self.buf, self.reader_index + e.offset()))?;
+        self.reader_index += message.wire_len();
+        Ok(message)
+    }
+}
+
+/// The record sections, in the order they must be written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Section {
+    Answer,
+    Authority,
+    Additional,
+}
+
+/// Writes a DNS message into a [`BufViewMut`] with name compression.
+///
+/// Questions must be added before the records, and records in the section order. A
+/// failed write leaves the writer index and the message as they were, so entries can
+/// be added until one fails with `OutOfBounds` and the message finished with what fit.
+#[derive(Debug)]
+pub struct Encoder<'v, 'b> {
+    view: &'v mut BufViewMut<'b>,
+    start: usize,
+    /// Lowercase dotted suffixes to their offset from the message start.
+    names: HashMap<String, u16>,
+    counts: [u16; 4],
+    section: usize,
+    /// The offsets of the begun record and of its data.
+    record_start: Option<(usize, usize)>,
+}
+
+impl<'v, 'b> Encoder<'v, 'b> {
+    /// Start a message at the writer index, its header counts are written by `finish`.
+    pub fn new(view: &'v mut BufViewMut<'b>, id: u16, flags: u16) -> Result<Self> {
+        let start = view.writer_index();
+        let mut header = [0u8; HEADER_LEN];
+        header[..2].copy_from_slice(&id.to_be_bytes());
+        header[2..4].copy_from_slice(&flags.to_be_bytes());
+        view.try_write_bytes(&header)?;
+        Ok(Encoder {
+            view,
+            start,
+            names: HashMap::new(),
+            counts: [0; 4],
+            section: 0,
+            record_start: None,
+        })
+    }
+
+    /// The view written to, to write record data between `begin_record` and
+    /// `end_record`.
+    pub fn view(&mut self) -> &mut BufViewMut<'b> {
+        self.view
+    }
+
+    fn invalid(&self, msg: &'static str) -> Error {
+        Error::invalid(msg, self.view.buf, self.view.writer_index())
+    }
+
+    /// Drop what was written from `mark` and the names remembered there.
+    fn truncate(&mut self, mark: usize) {
+        self.view.set_writer_index(mark);
+        let offset = mark - self.start;
+        self.names.retain(|_, name| (*name as usize) < offset);
+    }
+
+    /// Run `write`, undoing its writes and state changes when it fails.
+    fn undo_on_error(&mut self, write: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let (mark, section, record_start) =
+            (self.view.writer_index(), self.section, self.record_start);
+        let result = write(self);
+        if result.is_err() {
+            self.truncate(mark);
+            self.section = section;
+            self.record_start = record_start;
+        }
+        result
+    }
+
+    /// Write a dotted name, a trailing dot is optional and `""` or `"."` is the root.
+    ///
+    /// The longest suffix already written is replaced by a compression pointer, and the
+    /// new suffixes are remembered when their offset fits in a pointer.
+    pub fn write_name(&mut self, name: &str) -> Result<()> {
+        self.undo_on_error(|enc| enc.write_labels(name))
+    }
+
+    fn write_labels(&mut self, name: &str) -> Result<()> {
+        let name = name.strip_suffix('.').unwrap_or(name);
+        let labels: Vec<&str> = if name.is_empty() {
+            Vec::new()
+        } else {
+            name.split('.').collect()
+        };
+        let mut wire_len = 1;
+        for label in &labels {
+            if label.is_empty() {
+                return Err(self.invalid("empty DNS label"));
+            }
+            if label.len() > MAX_LABEL_LEN {
+                return Err(self.invalid("DNS label too long"));
+            }
+            wire_len += label.len() + 1;
+        }
+        if wire_len > MAX_NAME_LEN {
+            return Err(self.invalid("DNS name too long"));
+        }
+        for i in 0..labels.len() {
+            let suffix = labels[i..].join(".").to_ascii_lowercase();
+            if let Some(&offset) = self.names.get(&suffix) {
+                return self.view.try_write_bytes(&(0xc000 | offset).to_be_bytes());
+            }
+            let offset = self.view.writer_index() - self.start;
+            self.view.try_write_bytes(&[labels[i].len() as u8])?;
+            self.view.try_write_bytes(labels[i].as_bytes())?;
+            if offset < 0x4000 {
+                self.names.insert(suffix, offset as u16);
+            }
+        }
+        self.view.try_write_bytes(&[0])
+    }
+
+    pub fn add_question(&mut self, name: &str, qtype: u16, qclass: u16) -> Result<()> {
+        if self.section > 0 || self.record_start.is_some() {
+            return Err(self.invalid("DNS question after the records"));
+        }
+        self.undo_on_error(|enc| {
+            enc.write_name(name)?;
+            enc.view.try_write_bytes(&qtype.to_be_bytes())?;
+            enc.view.try_write_bytes(&qclass.to_be_bytes())?;
+            enc.count(0)
+        })
+    }
+
+    /// Write the record fields up to the data length, the record data is written next
+    /// and `end_record` back-patches its length.
+    pub fn begin_record(
+        &mut self,
+        section: Section,
+        name: &str,
+        rtype: u16,
+        class: u16,
+        ttl: u32,
+    ) -> Result<()> {
+        let section = section as usize + 1;
+        if self.record_start.is_some() {
+            return Err(self.invalid("DNS record not ended"));
+        }
+        if section < self.section {
+            return Err(self.invalid("DNS sections out of order"));
+        }
+        self.undo_on_error(|enc| {
+            let record = enc.view.writer_index();
+            enc.section = section;
+            enc.write_name(name)?;
+            enc.view.try_write_bytes(&rtype.to_be_bytes())?;
+            enc.view.try_write_bytes(&class.to_be_bytes())?;
+            enc.view.try_write_bytes(&ttl.to_be_bytes())?;
+            enc.view.try_write_bytes(&[0, 0])?;
+            enc.record_start = Some((record, enc.view.writer_index()));
+            Ok(())
+        })
+    }
+
+    /// Back-patch the record data length, a failure drops the whole record.
+    pub fn end_record(&mut self) -> Result<()> {
+        let (record, start) = match self.record_start.take() {
+            Some(start) => start,
+            None => return Err(self.invalid("DNS record not begun")),
+        };
+        let len = self.view.writer_index() - start;
+        let result = if len > u16::MAX as usize {
+            Err(Error::new(ErrorKind::Overflow, self.view.buf, start - 2))
+        } else {
+            self.view.set_u16(start - 2, len as u16);
+            self.count(self.section)
+        };
+        if result.is_err() {
+            self.truncate(record);
+        }
+        result
+    }
+
+    fn count(&mut self, section: usize) -> Result<()> {
+        match self.counts[section].checked_add(1) {
+            Some(count) => self.counts[section] = count,
+            None => return Err(Error::new(ErrorKind::Overflow, self.view.buf, self.start)),
+        }
+        Ok(())
+    }
+
+    /// Add a record with raw data.
+    pub fn add_record(
+        &mut self,
+        section: Section,
+        name: &str,
+        rtype: u16,
+        class: u16,
+        ttl: u32,
+        rdata: &[u8],
+    ) -> Result<()> {
+        self.undo_on_error(|enc| {
+            enc.begin_record(section, name, rtype, class, ttl)?;
+            enc.view.try_write_bytes(rdata)?;
+            enc.end_record()
+        })
+    }
+
+    pub fn add_a(&mut self, section: Section, name: &str, ttl: u32, addr: Ipv4Addr) -> Result<()> {
+        self.add_record(section, name, rtype::A, class::IN, ttl, &addr.octets())
+    }
+
+    pub fn add_aaaa(
+        &mut self,
+        section: Section,
+        name: &str,
+        ttl: u32,
+        addr: Ipv6Addr,
+    ) -> Result<()> {
+        self.add_record(section, name, rtype::AAAA, class::IN, ttl, &addr.octets())
+    }
+
+    /// Add a record whose data is a compressed name, such as NS, CNAME or PTR.
+    pub fn add_name_record(
+        &mut self,
+        section: Section,
+        name: &str,
+        rtype: u16,
+        ttl: u32,
+        target: &str,
+    ) -> Result<()> {
+        self.undo_on_error(|enc| {
+            enc.begin_record(section, name, rtype, class::IN, ttl)?;
+            enc.write_name(target)?;
+            enc.end_record()
+        })
+    }
+
+    pub fn add_mx(
+        &mut self,
+        section: Section,
+        name: &str,
+        ttl: u32,
+        preference: u16,
+        exchange: &str,
+    ) -> Result<()> {
+        self.undo_on_error(|enc| {
+            enc.begin_record(section, name, rtype::MX, class::IN, ttl)?;
+            enc.view.try_write_bytes(&preference.to_be_bytes())?;
+            enc.write_name(exchange)?;
+            enc.end_record()
+        })
+    }
+
+    /// Add a TXT record, each string is at most 255 bytes.
+    pub fn add_txt(
+        &mut self,
+        section: Section,
+        name: &str,
+        ttl: u32,
+        strings: &[&[u8]],
+    ) -> Result<()> {
+        self.undo_on_error(|enc| {
+            enc.begin_record(section, name, rtype::TXT, class::IN, ttl)?;
+            for s in strings {
+                if s.len() > u8::MAX as usize {
+                    return Err(enc.invalid("DNS character string too long"));
+                }
+                enc.view.try_write_bytes(&[s.len() as u8])?;
+                enc.view.try_write_bytes(s)?;
+            }
+            enc.end_record()
+        })
+    }
+
+    /// Write the section counts into the header and return the message length.
+    pub fn finish(self) -> Result<usize> {
+        if self.record_start.is_some() {
+            return Err(self.invalid("DNS record not ended"));
+        }
+        for (i, count) in self.counts.iter().enumerate() {
+            self.view.set_u16(self.start + 4 + i * 2, *count);
+        }
+        Ok(self.view.writer_index() - self.start)
+    }
+}
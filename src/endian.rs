@@ -0,0 +1,155 @@
+use crate::error::Result;
+use crate::primitive::Primitive;
+use crate::{BufView, BufViewMut};
+
+/// A byte order known at compile time, used as a type parameter by the generic readers.
+pub trait Endian: Copy + 'static {
+    /// Decode a value from exactly `T::SIZE` bytes.
+    fn decode<T: Primitive>(bytes: &[u8]) -> T;
+
+    /// Encode a value to exactly `T::SIZE` bytes.
+    fn encode<T: Primitive>(val: T, dest: &mut [u8]);
+}
+
+/// Big endian (network) byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BigEndian;
+
+/// Little endian byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LittleEndian;
+
+impl Endian for BigEndian {
+    #[inline]
+    fn decode<T: Primitive>(bytes: &[u8]) -> T {
+        T::from_be_slice(bytes)
+    }
+
+    #[inline]
+    fn encode<T: Primitive>(val: T, dest: &mut [u8]) {
+        val.to_be_slice(dest)
+    }
+}
+
+impl Endian for LittleEndian {
+    #[inline]
+    fn decode<T: Primitive>(bytes: &[u8]) -> T {
+        T::from_le_slice(bytes)
+    }
+
+    #[inline]
+    fn encode<T: Primitive>(val: T, dest: &mut [u8]) {
+        val.to_le_slice(dest)
+    }
+}
+
+/// A byte order known at run time, e.g. detected from a file magic.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, BufViewMut, Endianness};
+///
+/// let mut buf = [0u8; 6];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// buf_view.write_endian(0x0102u16, Endianness::Little);
+/// buf_view.write_endian(0x03040506u32, Endianness::Big);
+///
+/// let mut buf_view = BufView::wrap(&buf);
+/// assert_eq!(buf_view.read_endian::<u16>(Endianness::Big), 0x0201);
+/// assert_eq!(buf_view.get_endian::<u32>(2, Endianness::Little), 0x06050403);
+/// assert!(buf_view.try_read_endian::<u64>(Endianness::Big).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Big,
+    Little,
+}
+
+impl Endianness {
+    /// The byte order of the target.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Endianness = Endianness::Big;
+
+    /// The byte order of the target.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Endianness = Endianness::Little;
+
+    /// Decode a value from exactly `T::SIZE` bytes.
+    #[inline]
+    pub fn decode<T: Primitive>(self, bytes: &[u8]) -> T {
+        match self {
+            Endianness::Big => BigEndian::decode(bytes),
+            Endianness::Little => LittleEndian::decode(bytes),
+        }
+    }
+
+    /// Encode a value to exactly `T::SIZE` bytes.
+    #[inline]
+    pub fn encode<T: Primitive>(self, val: T, dest: &mut [u8]) {
+        match self {
+            Endianness::Big => BigEndian::encode(val, dest),
+            Endianness::Little => LittleEndian::encode(val, dest),
+        }
+    }
+}
+
+macro_rules! endianness_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            pub fn read_endian<T: Primitive>(&mut self, endianness: Endianness) -> T {
+                let end = self.reader_index + T::SIZE;
+                assert!(self.writer_index >= end);
+                let val = endianness.decode(&self.buf[self.reader_index..end]);
+                self.reader_index = end;
+                val
+            }
+
+            pub fn try_read_endian<T: Primitive>(&mut self, endianness: Endianness) -> Result<T> {
+                let end = self.check_read(T::SIZE)?;
+                let val = endianness.decode(&self.buf[self.reader_index..end]);
+                self.reader_index = end;
+                Ok(val)
+            }
+
+            pub fn get_endian<T: Primitive>(&self, index: usize, endianness: Endianness) -> T {
+                let end = index + T::SIZE;
+                assert!(self.buf.len() >= end);
+                endianness.decode(&self.buf[index..end])
+            }
+
+            pub fn try_get_endian<T: Primitive>(
+                &self,
+                index: usize,
+                endianness: Endianness,
+            ) -> Result<T> {
+                let end = self.check_get(index, T::SIZE)?;
+                Ok(endianness.decode(&self.buf[index..end]))
+            }
+        }
+    };
+}
+
+endianness_read_methods!(BufView);
+endianness_read_methods!(BufViewMut);
+
+impl BufViewMut<'_> {
+    pub fn write_endian<T: Primitive>(&mut self, val: T, endianness: Endianness) {
+        let end = self.writer_index + T::SIZE;
+        assert!(self.buf.len() >= end);
+        endianness.encode(val, &mut self.buf[self.writer_index..end]);
+        self.writer_index = end;
+    }
+
+    pub fn try_write_endian<T: Primitive>(&mut self, val: T, endianness: Endianness) -> Result<()> {
+        let end = self.check_get(self.writer_index, T::SIZE)?;
+        endianness.encode(val, &mut self.buf[self.writer_index..end]);
+        self.writer_index = end;
+        Ok(())
+    }
+
+    pub fn set_endian<T: Primitive>(&mut self, index: usize, val: T, endianness: Endianness) {
+        let end = index + T::SIZE;
+        assert!(self.buf.len() >= end);
+        endianness.encode(val, &mut self.buf[index..end]);
+    }
+}
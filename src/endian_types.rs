@@ -0,0 +1,165 @@
+//! Byte order aware integer and float types with alignment 1, to describe packed
+//! structures read in place with `BufView::read_ref` and `BufViewMut::ref_mut_at`.
+
+use crate::{BufView, BufViewMut};
+use std::fmt;
+
+/// A type that can be reinterpreted from any byte slice of its size.
+///
+/// # Safety
+///
+/// Implementors must have alignment 1, no padding bytes and accept every bit pattern,
+/// which holds for `#[repr(C)]` or `#[repr(transparent)]` structs made only of `Pod`
+/// fields such as `u8`, byte arrays and the endian types of this crate.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, BufViewMut, Pod, U16Be, U32Be};
+///
+/// #[derive(Clone, Copy)]
+/// #[repr(C)]
+/// struct UdpHeader {
+///     src_port: U16Be,
+///     dst_port: U16Be,
+///     length: U16Be,
+///     checksum: U16Be,
+/// }
+///
+/// unsafe impl Pod for UdpHeader {}
+///
+/// let mut buf = [0x00, 0x35, 0xc0, 0x01, 0x00, 0x0c, 0x00, 0x00, 1, 2, 3, 4];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// buf_view.ref_mut_at::<UdpHeader>(0).checksum.set(0xbeef);
+/// buf_view.ref_mut_at::<U32Be>(8).set(7);
+///
+/// let mut buf_view = BufView::wrap(&buf);
+/// let header = buf_view.read_ref::<UdpHeader>();
+/// assert_eq!(header.src_port.get(), 53);
+/// assert_eq!(header.dst_port.get(), 0xc001);
+/// assert_eq!(header.checksum.get(), 0xbeef);
+/// assert_eq!(buf_view.read_u32(), 7);
+/// ```
+pub unsafe trait Pod: Copy + 'static {}
+
+unsafe impl Pod for u8 {}
+unsafe impl Pod for i8 {}
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+fn cast<T: Pod>(bytes: &[u8]) -> &T {
+    const { assert!(std::mem::align_of::<T>() == 1) };
+    assert_eq!(bytes.len(), std::mem::size_of::<T>());
+    // SAFETY: the size matches, T has alignment 1 and accepts any bit pattern
+    unsafe { &*(bytes.as_ptr() as *const T) }
+}
+
+fn cast_mut<T: Pod>(bytes: &mut [u8]) -> &mut T {
+    const { assert!(std::mem::align_of::<T>() == 1) };
+    assert_eq!(bytes.len(), std::mem::size_of::<T>());
+    // SAFETY: the size matches, T has alignment 1, no padding and accepts any bit pattern
+    unsafe { &mut *(bytes.as_mut_ptr() as *mut T) }
+}
+
+macro_rules! endian_type {
+    ($($name:ident, $typ:ty, $from:ident, $to:ident, $order:literal);* $(;)?) => {
+        $(
+            #[doc = concat!("A ", $order, " `", stringify!($typ), "` stored as bytes, with alignment 1.")]
+            #[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+            #[repr(transparent)]
+            pub struct $name([u8; std::mem::size_of::<$typ>()]);
+
+            impl $name {
+                pub const fn new(val: $typ) -> Self {
+                    $name(val.$to())
+                }
+
+                pub const fn from_bytes(bytes: [u8; std::mem::size_of::<$typ>()]) -> Self {
+                    $name(bytes)
+                }
+
+                pub const fn to_bytes(self) -> [u8; std::mem::size_of::<$typ>()] {
+                    self.0
+                }
+
+                pub const fn get(self) -> $typ {
+                    <$typ>::$from(self.0)
+                }
+
+                pub fn set(&mut self, val: $typ) {
+                    self.0 = val.$to();
+                }
+            }
+
+            unsafe impl Pod for $name {}
+
+            impl From<$typ> for $name {
+                fn from(val: $typ) -> Self {
+                    $name::new(val)
+                }
+            }
+
+            impl From<$name> for $typ {
+                fn from(val: $name) -> Self {
+                    val.get()
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Debug::fmt(&self.get(), f)
+                }
+            }
+        )*
+    };
+}
+
+endian_type!(
+    U16Be, u16, from_be_bytes, to_be_bytes, "big endian";
+    U16Le, u16, from_le_bytes, to_le_bytes, "little endian";
+    I16Be, i16, from_be_bytes, to_be_bytes, "big endian";
+    I16Le, i16, from_le_bytes, to_le_bytes, "little endian";
+    U32Be, u32, from_be_bytes, to_be_bytes, "big endian";
+    U32Le, u32, from_le_bytes, to_le_bytes, "little endian";
+    I32Be, i32, from_be_bytes, to_be_bytes, "big endian";
+    I32Le, i32, from_le_bytes, to_le_bytes, "little endian";
+    U64Be, u64, from_be_bytes, to_be_bytes, "big endian";
+    U64Le, u64, from_le_bytes, to_le_bytes, "little endian";
+    I64Be, i64, from_be_bytes, to_be_bytes, "big endian";
+    I64Le, i64, from_le_bytes, to_le_bytes, "little endian";
+    U128Be, u128, from_be_bytes, to_be_bytes, "big endian";
+    U128Le, u128, from_le_bytes, to_le_bytes, "little endian";
+    I128Be, i128, from_be_bytes, to_be_bytes, "big endian";
+    I128Le, i128, from_le_bytes, to_le_bytes, "little endian";
+    F32Be, f32, from_be_bytes, to_be_bytes, "big endian";
+    F32Le, f32, from_le_bytes, to_le_bytes, "little endian";
+    F64Be, f64, from_be_bytes, to_be_bytes, "big endian";
+    F64Le, f64, from_le_bytes, to_le_bytes, "little endian";
+);
+
+impl<'a> BufView<'a> {
+    /// Reinterpret the next `size_of::<T>()` bytes as a `T` in place and consume them.
+    pub fn read_ref<T: Pod>(&mut self) -> &'a T {
+        let end = self.reader_index + std::mem::size_of::<T>();
+        assert!(self.writer_index >= end);
+        let val = cast(&self.buf[self.reader_index..end]);
+        self.reader_index = end;
+        val
+    }
+
+    /// Reinterpret the `size_of::<T>()` bytes at `index` as a `T` in place.
+    pub fn ref_at<T: Pod>(&self, index: usize) -> &'a T {
+        cast(&self.buf[index..index + std::mem::size_of::<T>()])
+    }
+}
+
+impl BufViewMut<'_> {
+    /// Reinterpret the `size_of::<T>()` bytes at `index` as a `T` in place.
+    pub fn ref_at<T: Pod>(&self, index: usize) -> &T {
+        cast(&self.buf[index..index + std::mem::size_of::<T>()])
+    }
+
+    /// Reinterpret the `size_of::<T>()` bytes at `index` as a mutable `T` in place,
+    /// the reader and writer indexes do not move.
+    pub fn ref_mut_at<T: Pod>(&mut self, index: usize) -> &mut T {
+        cast_mut(&mut self.buf[index..index + std::mem::size_of::<T>()])
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+
+/// The number of bytes captured before and after the failing offset.
+const AROUND: usize = 8;
+
+/// What went wrong, see [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Needed `needed` bytes but only `available` bytes are left.
+    OutOfBounds { needed: usize, available: usize },
+    /// A number does not fit in the target type or field.
+    Overflow,
+    /// The data is malformed.
+    Invalid(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Name(String),
+    Index(usize),
+}
+
+/// The error of the fallible `try_*` methods.
+///
+/// Besides the [`ErrorKind`], an Error records the absolute offset of the failure in the
+/// wrapped buffer, the bytes around it and the context path. The path is built while the
+/// error returns through [`BufView::context`](crate::BufView::context) calls, the
+/// innermost context comes last.
+///
+/// `{}` prints a single line like `frame.headers[3].value: need 4 bytes at 0x1f2, have 1`,
+/// `{:#}` appends the bytes around the offset.
+///
+/// Example
+/// ```
+/// use buf_view::BufView;
+///
+/// let buf = [0, 2, 0, 1, 0, 0, 0];
+/// let mut buf_view = BufView::wrap(&buf);
+///
+/// let err = buf_view
+///     .context("frame", |v| {
+///         let count = v.try_read_u16()?;
+///         for i in 0..count as usize {
+///             v.context_index("headers", i, |v| v.context("value", |v| v.try_read_u32()))?;
+///         }
+///         Ok(())
+///     })
+///     .unwrap_err();
+///
+/// assert_eq!(err.to_string(), "frame.headers[1].value: need 4 bytes at 0x6, have 1");
+/// assert_eq!(err.offset(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    offset: usize,
+    around_start: usize,
+    around: Vec<u8>,
+    // innermost segment first
+    path: Vec<Segment>,
+}
+
+/// The result of the fallible `try_*` methods.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// An error at `offset` of `buf`, capturing the bytes around it.
+    pub fn new(kind: ErrorKind, buf: &[u8], offset: usize) -> Self {
+        let around_start = offset.saturating_sub(AROUND).min(buf.len());
+        let around_end = offset.saturating_add(AROUND).min(buf.len());
+        Error {
+            kind,
+            offset,
+            around_start,
+            around: buf[around_start..around_end].to_vec(),
+            path: Vec::new(),
+        }
+    }
+
+    pub(crate) fn out_of_bounds(
+        buf: &[u8],
+        offset: usize,
+        needed: usize,
+        available: usize,
+    ) -> Self {
+        Error::new(ErrorKind::OutOfBounds { needed, available }, buf, offset)
+    }
+
+    pub(crate) fn invalid(msg: &'static str, buf: &[u8], offset: usize) -> Self {
+        Error::new(ErrorKind::Invalid(msg), buf, offset)
+    }
+
+    /// Prepend the field `name` to the context path.
+    pub fn context(mut self, name: &str) -> Self {
+        self.path.push(Segment::Name(name.to_string()));
+        self
+    }
+
+    /// Prepend the element `index` to the context path.
+    pub fn index(mut self, index: usize) -> Self {
+        self.path.push(Segment::Index(index));
+        self
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// The absolute offset of the failure in the wrapped buffer.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The context path, e.g. `frame.headers[3].value`.
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        for segment in self.path.iter().rev() {
+            match segment {
+                Segment::Name(name) => {
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(name);
+                }
+                Segment::Index(index) => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+
+    /// The bytes around the failure, and the absolute offset of the first of them.
+    pub fn around(&self) -> (usize, &[u8]) {
+        (self.around_start, &self.around)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path())?;
+        }
+        match &self.kind {
+            ErrorKind::OutOfBounds { needed, available } => write!(
+                f,
+                "need {} bytes at 0x{:x}, have {}",
+                needed, self.offset, available
+            )?,
+            ErrorKind::Overflow => write!(f, "overflow at 0x{:x}", self.offset)?,
+            ErrorKind::Invalid(msg) => write!(f, "{} at 0x{:x}", msg, self.offset)?,
+        }
+
+        if f.alternate() {
+            write!(f, "\n  0x{:x}:", self.around_start)?;
+            for (i, b) in self.around.iter().enumerate() {
+                let sep = if self.around_start + i == self.offset {
+                    '>'
+                } else {
+                    ' '
+                };
+                write!(f, "{}{:02x}", sep, b)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,300 @@
+use crate::error::{Error, ErrorKind, Result};
+use crate::primitive::Primitive;
+use crate::{BufView, BufViewMut};
+
+/// An integer type usable as the raw representation of a [`Fixed`] number.
+pub trait FixedInt: Primitive {
+    fn to_i128(self) -> i128;
+
+    /// Return None when `val` does not fit in Self.
+    fn from_i128(val: i128) -> Option<Self>;
+}
+
+macro_rules! fixed_int_impl {
+    ($($typ:ty),*) => {
+        $(
+            impl FixedInt for $typ {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(val: i128) -> Option<Self> {
+                    <$typ>::try_from(val).ok()
+                }
+            }
+        )*
+    };
+}
+
+fixed_int_impl!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+/// A binary fixed-point number, the raw integer `I` scaled by `2^-FRAC_BITS`.
+///
+/// Q15 is `Fixed<i16, 15>`, Q31 is `Fixed<i32, 31>` and Q16.16 is `Fixed<i32, 16>`.
+/// The value is exactly `numerator() / denominator()`. `FRAC_BITS` must be below 128,
+/// a larger one fails to compile.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, BufViewMut};
+///
+/// let mut buf = [0u8; 8];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// buf_view.write_fixed::<i16, 15>(-0.5).unwrap();
+/// buf_view.write_fixed_le::<i32, 16>(3.25).unwrap();
+/// assert!(buf_view.write_fixed::<i16, 15>(1.0).is_err());
+///
+/// let mut buf_view = BufView::wrap(&buf);
+/// assert_eq!(buf_view.read_fixed::<i16, 15>(), -0.5);
+/// let q = buf_view.read_fixed_raw_le::<i32, 16>();
+/// assert_eq!(q.raw(), 0x0003_4000);
+/// assert_eq!((q.numerator(), q.denominator()), (0x0003_4000, 0x1_0000));
+/// assert_eq!(q.to_f64(), 3.25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixed<I, const FRAC_BITS: u32> {
+    raw: I,
+}
+
+impl<I: FixedInt, const FRAC_BITS: u32> Fixed<I, FRAC_BITS> {
+    pub fn from_raw(raw: I) -> Self {
+        const { assert!(FRAC_BITS < 128) };
+        Fixed { raw }
+    }
+
+    /// Round `val` to the nearest representable value, return None when out of range.
+    pub fn from_f64(val: f64) -> Option<Self> {
+        let scaled = (val * 2f64.powi(FRAC_BITS as i32)).round();
+        if !scaled.is_finite() || scaled.abs() >= 2f64.powi(127) {
+            return None;
+        }
+        I::from_i128(scaled as i128).map(Fixed::from_raw)
+    }
+
+    pub fn raw(&self) -> I {
+        self.raw
+    }
+
+    pub fn numerator(&self) -> i128 {
+        self.raw.to_i128()
+    }
+
+    pub fn denominator(&self) -> u128 {
+        const { assert!(FRAC_BITS < 128) };
+        1 << FRAC_BITS
+    }
+
+    /// The value as f64, rounded once when the raw integer has more than 53 bits.
+    pub fn to_f64(&self) -> f64 {
+        self.raw.to_i128() as f64 * 2f64.powi(-(FRAC_BITS as i32))
+    }
+}
+
+/// The nibble order of packed BCD digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NibbleOrder {
+    /// The first digit is in the high nibble, like ISO 8583 and most devices. An odd
+    /// number of digits is padded with a leading nibble.
+    HighFirst,
+    /// The first digit is in the low nibble, like telephony TBCD. An odd number of
+    /// digits is padded with a trailing nibble.
+    LowFirst,
+}
+
+fn decode_bcd(
+    bytes: &[u8],
+    n_digits: usize,
+    order: NibbleOrder,
+) -> std::result::Result<u64, ErrorKind> {
+    let pad = bytes.len() * 2 - n_digits;
+    let mut val = 0u64;
+    for i in 0..n_digits {
+        let pos = match order {
+            NibbleOrder::HighFirst => i + pad,
+            NibbleOrder::LowFirst => i,
+        };
+        let byte = bytes[pos / 2];
+        let high = match order {
+            NibbleOrder::HighFirst => pos % 2 == 0,
+            NibbleOrder::LowFirst => pos % 2 == 1,
+        };
+        let digit = if high { byte >> 4 } else { byte & 0x0f };
+        if digit > 9 {
+            return Err(ErrorKind::Invalid("invalid BCD digit"));
+        }
+        val = val
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(digit as u64))
+            .ok_or(ErrorKind::Overflow)?;
+    }
+    Ok(val)
+}
+
+fn encode_bcd(mut val: u64, dest: &mut [u8], n_digits: usize, order: NibbleOrder) {
+    let pad = dest.len() * 2 - n_digits;
+    dest.fill(0);
+    for i in (0..n_digits).rev() {
+        let digit = (val % 10) as u8;
+        val /= 10;
+        let pos = match order {
+            NibbleOrder::HighFirst => i + pad,
+            NibbleOrder::LowFirst => i,
+        };
+        let high = match order {
+            NibbleOrder::HighFirst => pos % 2 == 0,
+            NibbleOrder::LowFirst => pos % 2 == 1,
+        };
+        dest[pos / 2] |= if high { digit << 4 } else { digit };
+    }
+}
+
+macro_rules! fixed_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            pub fn read_fixed<I: FixedInt, const FRAC_BITS: u32>(&mut self) -> f64 {
+                self.read_fixed_raw::<I, FRAC_BITS>().to_f64()
+            }
+
+            pub fn read_fixed_le<I: FixedInt, const FRAC_BITS: u32>(&mut self) -> f64 {
+                self.read_fixed_raw_le::<I, FRAC_BITS>().to_f64()
+            }
+
+            pub fn read_fixed_raw<I: FixedInt, const FRAC_BITS: u32>(
+                &mut self,
+            ) -> Fixed<I, FRAC_BITS> {
+                assert!(self.remaining() >= I::SIZE);
+                let end = self.reader_index + I::SIZE;
+                let raw = I::from_be_slice(&self.buf[self.reader_index..end]);
+                self.reader_index = end;
+                Fixed::from_raw(raw)
+            }
+
+            pub fn read_fixed_raw_le<I: FixedInt, const FRAC_BITS: u32>(
+                &mut self,
+            ) -> Fixed<I, FRAC_BITS> {
+                assert!(self.remaining() >= I::SIZE);
+                let end = self.reader_index + I::SIZE;
+                let raw = I::from_le_slice(&self.buf[self.reader_index..end]);
+                self.reader_index = end;
+                Fixed::from_raw(raw)
+            }
+
+            pub fn get_fixed<I: FixedInt, const FRAC_BITS: u32>(&self, index: usize) -> f64 {
+                assert!(self.buf.len() >= index + I::SIZE);
+                let raw = I::from_be_slice(&self.buf[index..index + I::SIZE]);
+                Fixed::<I, FRAC_BITS>::from_raw(raw).to_f64()
+            }
+
+            pub fn get_fixed_le<I: FixedInt, const FRAC_BITS: u32>(&self, index: usize) -> f64 {
+                assert!(self.buf.len() >= index + I::SIZE);
+                let raw = I::from_le_slice(&self.buf[index..index + I::SIZE]);
+                Fixed::<I, FRAC_BITS>::from_raw(raw).to_f64()
+            }
+
+            /// Read `n_digits` packed BCD digits, from `n_digits.div_ceil(2)` bytes.
+            ///
+            /// The padding nibble of an odd `n_digits` is ignored. Fail with `Invalid` on a
+            /// nibble above 9 and with `Overflow` when the number does not fit in u64,
+            /// nothing is consumed on failure.
+            /// ```
+            /// use buf_view::{BufView, NibbleOrder};
+            ///
+            /// let buf = [0x01, 0x23, 0x45, 0x21, 0xf3];
+            /// let mut buf_view = BufView::wrap(&buf);
+            /// assert_eq!(buf_view.read_bcd(5, NibbleOrder::HighFirst), Ok(12345));
+            /// assert_eq!(buf_view.read_bcd(3, NibbleOrder::LowFirst), Ok(123));
+            /// ```
+            pub fn read_bcd(&mut self, n_digits: usize, order: NibbleOrder) -> Result<u64> {
+                let len = n_digits.div_ceil(2);
+                let end = self.check_read(len)?;
+                let val = decode_bcd(&self.buf[self.reader_index..end], n_digits, order)
+                    .map_err(|kind| Error::new(kind, self.buf, self.reader_index))?;
+                self.reader_index = end;
+                Ok(val)
+            }
+
+            pub fn get_bcd(
+                &self,
+                index: usize,
+                n_digits: usize,
+                order: NibbleOrder,
+            ) -> Result<u64> {
+                let end = self.check_get(index, n_digits.div_ceil(2))?;
+                decode_bcd(&self.buf[index..end], n_digits, order)
+                    .map_err(|kind| Error::new(kind, self.buf, index))
+            }
+        }
+    };
+}
+
+fixed_read_methods!(BufView);
+fixed_read_methods!(BufViewMut);
+
+impl BufViewMut<'_> {
+    /// Write `val` rounded to the nearest `Fixed<I, FRAC_BITS>`, fail with `Overflow`
+    /// when it is out of range.
+    pub fn write_fixed<I: FixedInt, const FRAC_BITS: u32>(&mut self, val: f64) -> Result<()> {
+        let raw = self.fixed_raw::<I, FRAC_BITS>(self.writer_index, val)?;
+        let mut bytes = [0u8; 16];
+        raw.to_be_slice(&mut bytes[..I::SIZE]);
+        self.try_write_bytes(&bytes[..I::SIZE])
+    }
+
+    pub fn write_fixed_le<I: FixedInt, const FRAC_BITS: u32>(&mut self, val: f64) -> Result<()> {
+        let raw = self.fixed_raw::<I, FRAC_BITS>(self.writer_index, val)?;
+        let mut bytes = [0u8; 16];
+        raw.to_le_slice(&mut bytes[..I::SIZE]);
+        self.try_write_bytes(&bytes[..I::SIZE])
+    }
+
+    pub fn set_fixed<I: FixedInt, const FRAC_BITS: u32>(
+        &mut self,
+        index: usize,
+        val: f64,
+    ) -> Result<()> {
+        let raw = self.fixed_raw::<I, FRAC_BITS>(index, val)?;
+        let end = self.check_get(index, I::SIZE)?;
+        raw.to_be_slice(&mut self.buf[index..end]);
+        Ok(())
+    }
+
+    pub fn set_fixed_le<I: FixedInt, const FRAC_BITS: u32>(
+        &mut self,
+        index: usize,
+        val: f64,
+    ) -> Result<()> {
+        let raw = self.fixed_raw::<I, FRAC_BITS>(index, val)?;
+        let end = self.check_get(index, I::SIZE)?;
+        raw.to_le_slice(&mut self.buf[index..end]);
+        Ok(())
+    }
+
+    /// Write `val` as `n_digits` packed BCD digits, padding an odd count with a zero
+    /// nibble. Fail with `Overflow` when `val` has more than `n_digits` digits.
+    pub fn write_bcd(&mut self, val: u64, n_digits: usize, order: NibbleOrder) -> Result<()> {
+        self.set_bcd(self.writer_index, val, n_digits, order)?;
+        self.writer_index += n_digits.div_ceil(2);
+        Ok(())
+    }
+
+    pub fn set_bcd(
+        &mut self,
+        index: usize,
+        val: u64,
+        n_digits: usize,
+        order: NibbleOrder,
+    ) -> Result<()> {
+        let end = self.check_get(index, n_digits.div_ceil(2))?;
+        if n_digits < 20 && val >= 10u64.pow(n_digits as u32) {
+            return Err(Error::new(ErrorKind::Overflow, self.buf, index));
+        }
+        encode_bcd(val, &mut self.buf[index..end], n_digits, order);
+        Ok(())
+    }
+
+    fn fixed_raw<I: FixedInt, const FRAC_BITS: u32>(&self, index: usize, val: f64) -> Result<I> {
+        Fixed::<I, FRAC_BITS>::from_f64(val)
+            .map(|f| f.raw())
+            .ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))
+    }
+}
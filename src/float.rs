@@ -0,0 +1,450 @@
+//! Conversions between f32/f64 and the half precision, bfloat16, x87 80-bit extended
+//! and IBM System/360 hexadecimal float formats.
+//!
+//! Narrowing conversions round to nearest, ties to even. NaN payloads are kept as far as
+//! they fit and NaNs are made quiet, subnormals are supported in both directions. The IBM
+//! formats have neither infinity nor NaN, converting them to IBM saturates to the largest
+//! magnitude, NaN becomes `0x7fff_ffff` (single) or `0x7fff_ffff_ffff_ffff` (double).
+//!
+//! BufView and BufViewMut read and get these formats with `read_f16`, `read_bf16`,
+//! `read_f80`, `read_ibm_f32`, `read_ibm_f64` and the `_le` and `get_` variants,
+//! BufViewMut writes and sets them with the matching `write_` and `set_` methods.
+//!
+//! Example
+//! ```
+//! use buf_view::{BufView, BufViewMut};
+//!
+//! let mut buf = [0u8; 26];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! buf_view.write_f16(1.5);
+//! buf_view.write_bf16_le(-2.0);
+//! // the sample rate field of an AIFF COMM chunk
+//! buf_view.write_f80(44100.0);
+//! buf_view.write_ibm_f32(-118.625);
+//! buf_view.write_ibm_f64(0.1);
+//!
+//! assert_eq!(&buf[..2], &[0x3e, 0x00]);
+//! assert_eq!(&buf[4..14], &[0x40, 0x0e, 0xac, 0x44, 0, 0, 0, 0, 0, 0]);
+//! assert_eq!(&buf[14..18], &[0xc2, 0x76, 0xa0, 0x00]);
+//!
+//! let mut buf_view = BufView::wrap(&buf);
+//! assert_eq!(buf_view.read_f16(), 1.5);
+//! assert_eq!(buf_view.read_bf16_le(), -2.0);
+//! assert_eq!(buf_view.read_f80(), 44100.0);
+//! assert_eq!(buf_view.read_ibm_f32(), -118.625);
+//! assert_eq!(buf_view.read_ibm_f64(), 0.1);
+//! ```
+
+use crate::{BufView, BufViewMut};
+
+/// Shift `m` right by `shift` bits, rounding to nearest, ties to even.
+fn round_shr(m: u128, shift: u32) -> u128 {
+    if shift == 0 {
+        return m;
+    }
+    if shift > 128 {
+        return 0;
+    }
+    let (r, rem, half) = if shift == 128 {
+        (0, m, 1u128 << 127)
+    } else {
+        (m >> shift, m & ((1u128 << shift) - 1), 1u128 << (shift - 1))
+    };
+    if rem > half || (rem == half && r & 1 == 1) {
+        r + 1
+    } else {
+        r
+    }
+}
+
+/// 2^exp as f64, `exp` must be in the normal range.
+fn pow2(exp: i32) -> f64 {
+    debug_assert!((-1022..=1023).contains(&exp));
+    f64::from_bits(((exp + 1023) as u64) << 52)
+}
+
+/// Split a finite non zero f64 into `m * 2^exp` with an integer `m`.
+fn decompose(val: f64) -> (u64, i32) {
+    let bits = val.to_bits();
+    let exp = ((bits >> 52) & 0x7ff) as i32;
+    let frac = bits & ((1 << 52) - 1);
+    if exp == 0 {
+        (frac, -1074)
+    } else {
+        (frac | (1 << 52), exp - 1075)
+    }
+}
+
+/// Convert IEEE 754 half precision bits to f32, exactly.
+pub fn f16_to_f32(half: u16) -> f32 {
+    let sign = ((half & 0x8000) as u32) << 16;
+    let exp = ((half >> 10) & 0x1f) as u32;
+    let man = (half & 0x3ff) as u32;
+    let bits = match exp {
+        0 if man == 0 => sign,
+        // subnormal, man * 2^-24 is exact in f32
+        0 => sign | (man as f32 * f32::from_bits(0x3380_0000)).to_bits(),
+        0x1f if man == 0 => sign | 0x7f80_0000,
+        0x1f => sign | 0x7fc0_0000 | (man << 13),
+        _ => sign | ((exp + 112) << 23) | (man << 13),
+    };
+    f32::from_bits(bits)
+}
+
+/// Convert f32 to IEEE 754 half precision bits.
+pub fn f32_to_f16(val: f32) -> u16 {
+    let bits = val.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exp = ((bits >> 23) & 0xff) as i32;
+    let man = bits & 0x7f_ffff;
+    if exp == 0xff {
+        return if man == 0 {
+            sign | 0x7c00
+        } else {
+            sign | 0x7e00 | (man >> 13) as u16
+        };
+    }
+
+    let e = exp - 127 + 15;
+    if e >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if e <= 0 {
+        if e < -10 {
+            return sign;
+        }
+        let r = round_shr((man | 0x80_0000) as u128, (14 - e) as u32);
+        return sign | r as u16;
+    }
+    // a carry out of the mantissa correctly bumps the exponent, up to infinity
+    let r = round_shr((((e as u32) << 23) | man) as u128, 13);
+    sign | r as u16
+}
+
+/// Convert bfloat16 bits to f32, exactly.
+pub fn bf16_to_f32(val: u16) -> f32 {
+    f32::from_bits((val as u32) << 16)
+}
+
+/// Convert f32 to bfloat16 bits.
+pub fn f32_to_bf16(val: f32) -> u16 {
+    let bits = val.to_bits();
+    if val.is_nan() {
+        return (bits >> 16) as u16 | 0x0040;
+    }
+    round_shr(bits as u128, 16) as u16
+}
+
+/// Convert the x87 80-bit extended format to f64.
+///
+/// `sign_exp` holds the sign bit and the 15-bit exponent, `mantissa` the 64-bit
+/// significand with its explicit integer bit.
+pub fn f80_to_f64(sign_exp: u16, mantissa: u64) -> f64 {
+    let sign = ((sign_exp & 0x8000) as u64) << 48;
+    let exp = (sign_exp & 0x7fff) as i32;
+    if exp == 0x7fff {
+        return if mantissa << 1 == 0 {
+            f64::from_bits(sign | 0x7ff0_0000_0000_0000)
+        } else {
+            f64::from_bits(sign | 0x7ff8_0000_0000_0000 | ((mantissa << 1) >> 12))
+        };
+    }
+    if mantissa == 0 {
+        return f64::from_bits(sign);
+    }
+
+    // normalize, `e` is the unbiased exponent of the leading bit
+    let lz = mantissa.leading_zeros();
+    let m = mantissa << lz;
+    let e = exp.max(1) - 16383 - lz as i32;
+    if e > 1023 {
+        return f64::from_bits(sign | 0x7ff0_0000_0000_0000);
+    }
+    if e < -1022 {
+        let r = round_shr(m as u128, (11 + (-1022 - e)) as u32) as u64;
+        // a carry into bit 52 yields the smallest normal
+        return f64::from_bits(sign | r);
+    }
+    let r = round_shr(m as u128, 11) as u64;
+    let bits = (((e + 1023) as u64) << 52) + (r - (1 << 52));
+    // a carry out of the mantissa bumps the exponent, up to infinity
+    f64::from_bits(sign | bits.min(0x7ff0_0000_0000_0000))
+}
+
+/// Convert f64 to the x87 80-bit extended format, exactly.
+///
+/// Return the sign and exponent bits and the 64-bit significand.
+pub fn f64_to_f80(val: f64) -> (u16, u64) {
+    let bits = val.to_bits();
+    let sign = ((bits >> 48) & 0x8000) as u16;
+    if val.is_nan() {
+        return (sign | 0x7fff, 0xc000_0000_0000_0000 | (bits << 11));
+    }
+    if val.is_infinite() {
+        return (sign | 0x7fff, 0x8000_0000_0000_0000);
+    }
+    if val == 0.0 {
+        return (sign, 0);
+    }
+    let (m, e) = decompose(val);
+    let lz = m.leading_zeros();
+    let exp = e + (63 - lz as i32) + 16383;
+    (sign | exp as u16, m << lz)
+}
+
+fn ibm_to_f64(bits: u64, frac_bits: u32) -> f64 {
+    let sign = if bits >> (frac_bits + 7) & 1 == 1 {
+        -1.0
+    } else {
+        1.0
+    };
+    let exp = ((bits >> frac_bits) & 0x7f) as i32 - 64;
+    let frac = bits & ((1 << frac_bits) - 1);
+    // `frac as f64` is the only rounding, the power of two is exact
+    sign * (frac as f64) * pow2(4 * exp - frac_bits as i32)
+}
+
+fn f64_to_ibm(val: f64, frac_bits: u32) -> u64 {
+    let sign = (val.is_sign_negative() as u64) << (frac_bits + 7);
+    let max = (0x7f << frac_bits) | ((1 << frac_bits) - 1);
+    if val.is_nan() {
+        return max;
+    }
+    if val.is_infinite() {
+        return sign | max;
+    }
+    if val == 0.0 {
+        return sign;
+    }
+
+    let (m, e2) = decompose(val);
+    // val is in [2^(p-1), 2^p), pick the smallest hex exponent with 16^exp >= 2^p
+    let p = e2 + 64 - m.leading_zeros() as i32;
+    let mut exp = p.div_euclid(4) + (p.rem_euclid(4) != 0) as i32;
+    // below the smallest exponent the fraction is left unnormalized
+    exp = exp.max(-64);
+    let shift = e2 - 4 * exp + frac_bits as i32;
+    let mut frac = if shift >= 0 {
+        (m as u128) << shift
+    } else {
+        round_shr(m as u128, (-shift) as u32)
+    };
+    if frac >> frac_bits != 0 {
+        frac >>= 4;
+        exp += 1;
+    }
+    if exp > 63 {
+        return sign | max;
+    }
+    sign | (((exp + 64) as u64) << frac_bits) | frac as u64
+}
+
+/// Convert IBM System/360 single precision hex float bits to f64, exactly.
+pub fn ibm32_to_f64(val: u32) -> f64 {
+    ibm_to_f64(val as u64, 24)
+}
+
+/// Convert f64 to IBM System/360 single precision hex float bits.
+pub fn f64_to_ibm32(val: f64) -> u32 {
+    f64_to_ibm(val, 24) as u32
+}
+
+/// Convert IBM System/360 double precision hex float bits to f64.
+pub fn ibm64_to_f64(val: u64) -> f64 {
+    ibm_to_f64(val, 56)
+}
+
+/// Convert f64 to IBM System/360 double precision hex float bits, exactly for every
+/// value in range.
+pub fn f64_to_ibm64(val: f64) -> u64 {
+    f64_to_ibm(val, 56)
+}
+
+macro_rules! float_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            pub fn read_f16(&mut self) -> f32 {
+                f16_to_f32(self.read_u16())
+            }
+
+            pub fn read_f16_le(&mut self) -> f32 {
+                f16_to_f32(self.read_u16_le())
+            }
+
+            pub fn read_bf16(&mut self) -> f32 {
+                bf16_to_f32(self.read_u16())
+            }
+
+            pub fn read_bf16_le(&mut self) -> f32 {
+                bf16_to_f32(self.read_u16_le())
+            }
+
+            pub fn read_f80(&mut self) -> f64 {
+                assert!(self.remaining() >= 10);
+                let sign_exp = self.read_u16();
+                f80_to_f64(sign_exp, self.read_u64())
+            }
+
+            pub fn read_f80_le(&mut self) -> f64 {
+                assert!(self.remaining() >= 10);
+                let mantissa = self.read_u64_le();
+                f80_to_f64(self.read_u16_le(), mantissa)
+            }
+
+            pub fn read_ibm_f32(&mut self) -> f64 {
+                ibm32_to_f64(self.read_u32())
+            }
+
+            pub fn read_ibm_f32_le(&mut self) -> f64 {
+                ibm32_to_f64(self.read_u32_le())
+            }
+
+            pub fn read_ibm_f64(&mut self) -> f64 {
+                ibm64_to_f64(self.read_u64())
+            }
+
+            pub fn read_ibm_f64_le(&mut self) -> f64 {
+                ibm64_to_f64(self.read_u64_le())
+            }
+
+            pub fn get_f16(&mut self, index: usize) -> f32 {
+                f16_to_f32(self.get_u16(index))
+            }
+
+            pub fn get_f16_le(&mut self, index: usize) -> f32 {
+                f16_to_f32(self.get_u16_le(index))
+            }
+
+            pub fn get_bf16(&mut self, index: usize) -> f32 {
+                bf16_to_f32(self.get_u16(index))
+            }
+
+            pub fn get_bf16_le(&mut self, index: usize) -> f32 {
+                bf16_to_f32(self.get_u16_le(index))
+            }
+
+            pub fn get_f80(&mut self, index: usize) -> f64 {
+                f80_to_f64(self.get_u16(index), self.get_u64(index + 2))
+            }
+
+            pub fn get_f80_le(&mut self, index: usize) -> f64 {
+                f80_to_f64(self.get_u16_le(index + 8), self.get_u64_le(index))
+            }
+
+            pub fn get_ibm_f32(&mut self, index: usize) -> f64 {
+                ibm32_to_f64(self.get_u32(index))
+            }
+
+            pub fn get_ibm_f32_le(&mut self, index: usize) -> f64 {
+                ibm32_to_f64(self.get_u32_le(index))
+            }
+
+            pub fn get_ibm_f64(&mut self, index: usize) -> f64 {
+                ibm64_to_f64(self.get_u64(index))
+            }
+
+            pub fn get_ibm_f64_le(&mut self, index: usize) -> f64 {
+                ibm64_to_f64(self.get_u64_le(index))
+            }
+        }
+    };
+}
+
+float_read_methods!(BufView);
+float_read_methods!(BufViewMut);
+
+impl BufViewMut<'_> {
+    pub fn write_f16(&mut self, val: f32) {
+        self.write_u16(f32_to_f16(val));
+    }
+
+    pub fn write_f16_le(&mut self, val: f32) {
+        self.write_u16_le(f32_to_f16(val));
+    }
+
+    pub fn write_bf16(&mut self, val: f32) {
+        self.write_u16(f32_to_bf16(val));
+    }
+
+    pub fn write_bf16_le(&mut self, val: f32) {
+        self.write_u16_le(f32_to_bf16(val));
+    }
+
+    pub fn write_f80(&mut self, val: f64) {
+        let (sign_exp, mantissa) = f64_to_f80(val);
+        let mut bytes = [0u8; 10];
+        bytes[..2].copy_from_slice(&sign_exp.to_be_bytes());
+        bytes[2..].copy_from_slice(&mantissa.to_be_bytes());
+        self.write_bytes(&bytes);
+    }
+
+    pub fn write_f80_le(&mut self, val: f64) {
+        let (sign_exp, mantissa) = f64_to_f80(val);
+        let mut bytes = [0u8; 10];
+        bytes[..8].copy_from_slice(&mantissa.to_le_bytes());
+        bytes[8..].copy_from_slice(&sign_exp.to_le_bytes());
+        self.write_bytes(&bytes);
+    }
+
+    pub fn write_ibm_f32(&mut self, val: f64) {
+        self.write_u32(f64_to_ibm32(val));
+    }
+
+    pub fn write_ibm_f32_le(&mut self, val: f64) {
+        self.write_u32_le(f64_to_ibm32(val));
+    }
+
+    pub fn write_ibm_f64(&mut self, val: f64) {
+        self.write_u64(f64_to_ibm64(val));
+    }
+
+    pub fn write_ibm_f64_le(&mut self, val: f64) {
+        self.write_u64_le(f64_to_ibm64(val));
+    }
+
+    pub fn set_f16(&mut self, index: usize, val: f32) {
+        self.set_u16(index, f32_to_f16(val));
+    }
+
+    pub fn set_f16_le(&mut self, index: usize, val: f32) {
+        self.set_u16_le(index, f32_to_f16(val));
+    }
+
+    pub fn set_bf16(&mut self, index: usize, val: f32) {
+        self.set_u16(index, f32_to_bf16(val));
+    }
+
+    pub fn set_bf16_le(&mut self, index: usize, val: f32) {
+        self.set_u16_le(index, f32_to_bf16(val));
+    }
+
+    pub fn set_f80(&mut self, index: usize, val: f64) {
+        let (sign_exp, mantissa) = f64_to_f80(val);
+        assert!(self.buf.len() >= index + 10);
+        self.set_u16(index, sign_exp);
+        self.set_u64(index + 2, mantissa);
+    }
+
+    pub fn set_f80_le(&mut self, index: usize, val: f64) {
+        let (sign_exp, mantissa) = f64_to_f80(val);
+        assert!(self.buf.len() >= index + 10);
+        self.set_u64_le(index, mantissa);
+        self.set_u16_le(index + 8, sign_exp);
+    }
+
+    pub fn set_ibm_f32(&mut self, index: usize, val: f64) {
+        self.set_u32(index, f64_to_ibm32(val));
+    }
+
+    pub fn set_ibm_f32_le(&mut self, index: usize, val: f64) {
+        self.set_u32_le(index, f64_to_ibm32(val));
+    }
+
+    pub fn set_ibm_f64(&mut self, index: usize, val: f64) {
+        self.set_u64(index, f64_to_ibm64(val));
+    }
+
+    pub fn set_ibm_f64_le(&mut self, index: usize, val: f64) {
+        self.set_u64_le(index, f64_to_ibm64(val));
+    }
+}
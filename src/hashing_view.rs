@@ -0,0 +1,94 @@
+use crate::view_read::ViewRead;
+use crate::view_write::ViewWrite;
+use std::hash::Hasher;
+
+/// Wrap a view and hash every byte passing through it.
+///
+/// HashingView feeds the bytes consumed by `read_*` and the bytes produced by `write_*`
+/// into a running [`Hasher`]. Random access `get_*` and `set_*` are not sequential data,
+/// so `set_*` passes through without hashing. Every checksum of the
+/// [`checksum`](crate::checksum) module implements `Hasher`, with `finish` returning the
+/// finalized checksum.
+///
+/// Use `inner_mut` to read or write bytes which must not be hashed, e.g. the trailing
+/// checksum itself.
+///
+/// Example
+/// ```
+/// use buf_view::checksum::Crc32;
+/// use buf_view::{BufView, HashingView, ViewRead};
+/// use std::hash::Hasher;
+///
+/// let buf = [0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x26, 0x39, 0xf4, 0xcb];
+/// let mut view = HashingView::new(BufView::wrap(&buf), Crc32::default());
+///
+/// assert_eq!(view.read_u32(), 0x31323334);
+/// assert_eq!(view.read_u32(), 0x35363738);
+/// assert_eq!(view.read_u8(), 0x39);
+///
+/// let expected = view.inner_mut().read_u32_le();
+/// assert_eq!(view.hasher().finish(), expected as u64);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HashingView<V, H> {
+    inner: V,
+    hasher: H,
+}
+
+impl<V, H: Hasher> HashingView<V, H> {
+    pub fn new(inner: V, hasher: H) -> Self {
+        HashingView { inner, hasher }
+    }
+
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// The wrapped view, reads and writes through it are not hashed.
+    pub fn inner_mut(&mut self) -> &mut V {
+        &mut self.inner
+    }
+
+    pub fn hasher(&self) -> &H {
+        &self.hasher
+    }
+
+    pub fn hasher_mut(&mut self) -> &mut H {
+        &mut self.hasher
+    }
+
+    pub fn into_inner(self) -> (V, H) {
+        (self.inner, self.hasher)
+    }
+}
+
+impl<V: ViewRead, H: Hasher> ViewRead for HashingView<V, H> {
+    fn read_bytes(&mut self, dest: &mut [u8]) -> usize {
+        let len = self.inner.read_bytes(dest);
+        self.hasher.write(&dest[..len]);
+        len
+    }
+
+    fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    fn reader_index(&self) -> usize {
+        self.inner.reader_index()
+    }
+}
+
+impl<V: ViewWrite, H: Hasher> ViewWrite for HashingView<V, H> {
+    fn write_bytes(&mut self, src: &[u8]) {
+        self.inner.write_bytes(src);
+        self.hasher.write(src);
+    }
+
+    fn set_bytes(&mut self, index: usize, src: &[u8]) {
+        self.inner.set_bytes(index, src);
+    }
+
+    fn writer_index(&self) -> usize {
+        self.inner.writer_index()
+    }
+}
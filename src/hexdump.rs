@@ -0,0 +1,196 @@
+use crate::{BufView, BufViewMut};
+use std::fmt;
+use std::ops::Range;
+
+const BYTES_PER_LINE: usize = 16;
+
+/// A hexdump formatter for a byte buffer.
+///
+/// HexDump prints lines of 16 bytes with the offset, the hex columns and an ASCII gutter.
+/// Offsets are absolute in the buffer and lines are aligned to 16 bytes, bytes outside
+/// the dumped window are left blank. When created from a BufView or BufViewMut, a marker
+/// line under the hex columns shows the reader_index as `r` and the writer_index as `w`,
+/// or `*` when both are at the same position.
+///
+/// Example
+/// ```
+/// use buf_view::BufView;
+///
+/// let buf = b"0123456789abcdefghij";
+/// let mut buf_view = BufView::wrap(buf);
+/// buf_view.read_u16();
+///
+/// let dump = buf_view.hexdump().to_string();
+/// assert_eq!(
+///     dump,
+///     "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|\n\
+///      \x20               r\n\
+///      00000010  67 68 69 6a                                       |ghij|\n\
+///      \x20                     w\n"
+/// );
+///
+/// let dump = buf_view.hexdump().range(4..6).to_string();
+/// assert_eq!(
+///     dump,
+///     "00000000              34 35                                 |    45|\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct HexDump<'a> {
+    buf: &'a [u8],
+    range: Range<usize>,
+    reader_index: Option<usize>,
+    writer_index: Option<usize>,
+}
+
+impl<'a> HexDump<'a> {
+    /// Dump the whole `buf` without markers.
+    pub fn new(buf: &'a [u8]) -> Self {
+        HexDump {
+            buf,
+            range: 0..buf.len(),
+            reader_index: None,
+            writer_index: None,
+        }
+    }
+
+    /// Only dump the bytes of `range`, it is clamped to the buffer.
+    pub fn range(mut self, range: Range<usize>) -> Self {
+        let end = range.end.min(self.buf.len());
+        self.range = range.start.min(end)..end;
+        self
+    }
+
+    /// Only dump `radius` bytes before and after `offset`.
+    pub fn around(self, offset: usize, radius: usize) -> Self {
+        self.range(offset.saturating_sub(radius)..offset.saturating_add(radius))
+    }
+
+    /// Mark the reader_index and writer_index positions.
+    pub fn marks(mut self, reader_index: Option<usize>, writer_index: Option<usize>) -> Self {
+        self.reader_index = reader_index;
+        self.writer_index = writer_index;
+        self
+    }
+
+    fn column(pos: usize) -> usize {
+        // "00000000  " then "xx " per byte, with one more space after the 8th byte
+        10 + pos * 3 + if pos >= BYTES_PER_LINE / 2 { 1 } else { 0 }
+    }
+
+    fn fmt_marks(&self, f: &mut fmt::Formatter<'_>, line: Range<usize>) -> fmt::Result {
+        let window = self.range.start..=self.range.end;
+        let in_line =
+            |index: Option<usize>| index.filter(|i| line.contains(i) && window.contains(i));
+        let reader = in_line(self.reader_index);
+        let writer = in_line(self.writer_index);
+        if reader.is_none() && writer.is_none() {
+            return Ok(());
+        }
+
+        let mut marks = [b' '; 10 + BYTES_PER_LINE * 3 + 1 + 1 + BYTES_PER_LINE + 1];
+        if let Some(i) = reader {
+            marks[Self::column(i - line.start)] = b'r';
+        }
+        if let Some(i) = writer {
+            let c = Self::column(i - line.start);
+            marks[c] = if marks[c] == b'r' { b'*' } else { b'w' };
+        }
+        writeln!(f, "{}", std::str::from_utf8(&marks).unwrap().trim_end())
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Range { start, end } = self.range;
+        let mut line_start = start - start % BYTES_PER_LINE;
+        while line_start < end {
+            let line = line_start..line_start + BYTES_PER_LINE;
+            write!(f, "{:08x}  ", line_start)?;
+            for i in line.clone() {
+                if i >= start && i < end {
+                    write!(f, "{:02x} ", self.buf[i])?;
+                } else {
+                    f.write_str("   ")?;
+                }
+                if i - line_start == BYTES_PER_LINE / 2 - 1 {
+                    f.write_str(" ")?;
+                }
+            }
+            f.write_str(" |")?;
+            for i in line_start..line.end.min(end) {
+                let b = self.buf[i];
+                let c = if i < start {
+                    ' '
+                } else if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            f.write_str("|\n")?;
+            self.fmt_marks(f, line)?;
+            line_start += BYTES_PER_LINE;
+        }
+
+        // a mark at the very end of an aligned window has no line of its own yet
+        if end % BYTES_PER_LINE == 0
+            && (self.reader_index == Some(end) || self.writer_index == Some(end))
+        {
+            writeln!(f, "{:08x}", end)?;
+            self.fmt_marks(f, end..end + BYTES_PER_LINE)?;
+        }
+        Ok(())
+    }
+}
+
+impl BufView<'_> {
+    /// Hexdump the whole wrapped buffer, marking the reader_index and writer_index.
+    pub fn hexdump(&self) -> HexDump<'_> {
+        HexDump::new(self.buf).marks(Some(self.reader_index), Some(self.writer_index))
+    }
+}
+
+impl BufViewMut<'_> {
+    /// Hexdump the whole wrapped buffer, marking the reader_index and writer_index.
+    pub fn hexdump(&self) -> HexDump<'_> {
+        HexDump::new(self.buf).marks(Some(self.reader_index), Some(self.writer_index))
+    }
+}
+
+macro_rules! view_debug {
+    ($name:ident) => {
+        /// `{:?}` prints every field, `{:#?}` prints the indexes and a hexdump of only
+        /// the readable window.
+        impl fmt::Debug for $name<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                if !f.alternate() {
+                    return f
+                        .debug_struct(stringify!($name))
+                        .field("buf", &self.buf)
+                        .field("reader_index", &self.reader_index)
+                        .field("writer_index", &self.writer_index)
+                        .finish();
+                }
+
+                writeln!(
+                    f,
+                    "{} {{ reader_index: {}, writer_index: {}, capacity: {} }}",
+                    stringify!($name),
+                    self.reader_index,
+                    self.writer_index,
+                    self.buf.len()
+                )?;
+                write!(
+                    f,
+                    "{}",
+                    HexDump::new(self.buf).range(self.reader_index..self.writer_index)
+                )
+            }
+        }
+    };
+}
+
+view_debug!(BufView);
+view_debug!(BufViewMut);
@@ -1,6 +1,59 @@
+mod addr;
+mod array;
+mod array_iter;
+mod ascii;
+#[cfg(feature = "asn1")]
+pub mod asn1;
 mod buf_view;
 mod buf_view_mut;
+mod bulk;
+pub mod checksum;
+mod data_view;
+#[cfg(feature = "dns")]
+pub mod dns;
+mod endian;
+mod endian_types;
+mod error;
+mod fixed;
+pub mod float;
+mod hashing_view;
+mod hexdump;
 mod macros;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "net")]
+pub mod net;
+#[cfg(feature = "pcap")]
+pub mod pcap;
+mod primitive;
+#[cfg(feature = "protobuf_wire")]
+pub mod protobuf_wire;
+mod search;
+mod size_counter;
+pub mod time;
+mod tlv;
+pub mod trace;
+mod uuid;
+mod view_read;
+mod view_write;
 
+pub use crate::addr::{Eui64, MacAddr, ParseMacError};
+pub use crate::array_iter::ArrayIter;
 pub use crate::buf_view::BufView;
 pub use crate::buf_view_mut::BufViewMut;
+pub use crate::data_view::{DataView, TypedArrayView};
+pub use crate::endian::{BigEndian, Endian, Endianness, LittleEndian};
+pub use crate::endian_types::{
+    F32Be, F32Le, F64Be, F64Le, I128Be, I128Le, I16Be, I16Le, I32Be, I32Le, I64Be, I64Le, Pod,
+    U128Be, U128Le, U16Be, U16Le, U32Be, U32Le, U64Be, U64Le,
+};
+pub use crate::error::{Error, ErrorKind, Result};
+pub use crate::fixed::{Fixed, FixedInt, NibbleOrder};
+pub use crate::hashing_view::HashingView;
+pub use crate::hexdump::HexDump;
+pub use crate::primitive::Primitive;
+pub use crate::size_counter::SizeCounter;
+pub use crate::tlv::{LengthFormat, TagFormat, TlvFormat, TlvIter, TlvMark, TlvWriter};
+pub use crate::uuid::{ParseUuidError, Uuid};
+pub use crate::view_read::ViewRead;
+pub use crate::view_write::ViewWrite;
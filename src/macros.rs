@@ -32,4 +32,48 @@ macro_rules! buf_get_do {
     };
 }
 
-pub(crate) use {buf_get_do, buf_read_do};
+macro_rules! view_read_do {
+    ($this:ident, $typ: tt, be) => {{
+        let mut bytes = [0u8; std::mem::size_of::<$typ>()];
+        $this.read_bytes(&mut bytes);
+        $typ::from_be_bytes(bytes)
+    }};
+
+    ($this:ident, $typ: tt, le) => {{
+        let mut bytes = [0u8; std::mem::size_of::<$typ>()];
+        $this.read_bytes(&mut bytes);
+        $typ::from_le_bytes(bytes)
+    }};
+}
+
+macro_rules! buf_try_read_do {
+    ($this:ident, $typ: tt, be) => {
+        let end = $this.check_read(std::mem::size_of::<$typ>())?;
+        let val = $typ::from_be_bytes($this.buf[$this.reader_index..end].try_into().unwrap());
+        $this.reader_index = end;
+        return Ok(val);
+    };
+
+    ($this:ident, $typ: tt, le) => {
+        let end = $this.check_read(std::mem::size_of::<$typ>())?;
+        let val = $typ::from_le_bytes($this.buf[$this.reader_index..end].try_into().unwrap());
+        $this.reader_index = end;
+        return Ok(val);
+    };
+}
+
+macro_rules! buf_try_get_do {
+    ($this:ident, $index: expr, $typ: tt, be) => {
+        let end = $this.check_get($index, std::mem::size_of::<$typ>())?;
+        let val = $typ::from_be_bytes($this.buf[$index..end].try_into().unwrap());
+        return Ok(val);
+    };
+
+    ($this:ident, $index: expr, $typ: tt, le) => {
+        let end = $this.check_get($index, std::mem::size_of::<$typ>())?;
+        let val = $typ::from_le_bytes($this.buf[$index..end].try_into().unwrap());
+        return Ok(val);
+    };
+}
+
+pub(crate) use {buf_get_do, buf_read_do, buf_try_get_do, buf_try_read_do, view_read_do};
@@ -0,0 +1,691 @@
+//! MessagePack encoding and decoding, behind the `msgpack` feature.
+//!
+//! A [`MsgpackReader`] reads the encoding one [`Token`] at a time, an array or map
+//! token gives the number of items that follow, or reads a whole [`Value`] tree.
+//! Strings, binaries and extensions are borrowed from the wrapped buffer. Error
+//! offsets are absolute in the wrapped buffer and a failed read does not move the
+//! reader.
+//!
+//! `read_value` limits the nesting of arrays and maps to [`MAX_DEPTH`], or the depth
+//! set with `set_max_depth`, and the memory reserved for a container to what the
+//! remaining bytes can hold, so untrusted input can be decoded.
+//!
+//! A [`MsgpackWriter`] writes at the writer index of a [`BufViewMut`] in the smallest
+//! format for each value.
+//!
+//! Example
+//! ```
+//! use buf_view::msgpack::{MsgpackReader, MsgpackWriter, Token, Value};
+//! use buf_view::{BufView, BufViewMut};
+//!
+//! let mut buf = [0u8; 32];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let mut writer = MsgpackWriter::new(&mut buf_view);
+//! writer.write_map_len(2).unwrap();
+//! writer.write_str("id").unwrap();
+//! writer.write_u64(300).unwrap();
+//! writer.write_str("tags").unwrap();
+//! writer.write_array_len(1).unwrap();
+//! writer.write_i64(-1).unwrap();
+//! let len = buf_view.writer_index();
+//! assert_eq!(&buf[..6], &[0x82, 0xa2, b'i', b'd', 0xcd, 0x01]);
+//!
+//! let mut reader = MsgpackReader::new(BufView::wrap(&buf[..len]));
+//! assert_eq!(reader.read_token(), Ok(Token::Map(2)));
+//! assert_eq!(reader.read_token(), Ok(Token::Str("id")));
+//! reader.skip_value().unwrap();
+//! assert_eq!(reader.read_token(), Ok(Token::Str("tags")));
+//! assert_eq!(reader.read_value(), Ok(Value::Array(vec![Value::Int(-1)])));
+//! assert!(reader.is_empty());
+//!
+//! let value = MsgpackReader::new(BufView::wrap(&buf[..len])).read_value().unwrap();
+//! assert_eq!(value.get("id").and_then(Value::as_u64), Some(300));
+//! ```
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::time::{from_unix, to_unix};
+use crate::{BufView, BufViewMut};
+use std::time::SystemTime;
+
+/// The default maximum nesting of arrays and maps in `read_value`.
+pub const MAX_DEPTH: usize = 64;
+
+/// The extension type of timestamps.
+pub const TIMESTAMP_EXT: i8 = -1;
+
+/// A timestamp extension, seconds and nanoseconds since the Unix epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub secs: i64,
+    /// Below 1 000 000 000.
+    pub nanos: u32,
+}
+
+impl Timestamp {
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        from_unix(self.secs, self.nanos)
+    }
+
+    pub fn from_system_time(t: SystemTime) -> Option<Self> {
+        let (secs, nanos) = to_unix(t)?;
+        Some(Timestamp { secs, nanos })
+    }
+}
+
+/// An encoded item, an array or map token is followed by its items, a map has a key
+/// and a value per entry.
+///
+/// Integers are `UInt` when not negative and `Int` when negative, whatever their
+/// encoding.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Token<'a> {
+    Nil,
+    Bool(bool),
+    UInt(u64),
+    Int(i64),
+    F32(f32),
+    F64(f64),
+    Str(&'a str),
+    Bin(&'a [u8]),
+    Array(u32),
+    Map(u32),
+    /// An extension other than the timestamp.
+    Ext(i8, &'a [u8]),
+    Timestamp(Timestamp),
+}
+
+/// A decoded value, borrowing its strings, binaries and extensions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Nil,
+    Bool(bool),
+    UInt(u64),
+    Int(i64),
+    F32(f32),
+    F64(f64),
+    Str(&'a str),
+    Bin(&'a [u8]),
+    Array(Vec<Value<'a>>),
+    /// The entries in their encoded order, keys may repeat.
+    Map(Vec<(Value<'a>, Value<'a>)>),
+    Ext(i8, &'a [u8]),
+    Timestamp(Timestamp),
+}
+
+impl<'a> Value<'a> {
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Value::Nil)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::UInt(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::UInt(val) => i64::try_from(val).ok(),
+            Value::Int(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// A float of either size.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::F32(val) => Some(val as f64),
+            Value::F64(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'a str> {
+        match *self {
+            Value::Str(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match *self {
+            Value::Bin(val) => Some(val),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value<'a>]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&[(Value<'a>, Value<'a>)]> {
+        match self {
+            Value::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// The value of the first entry with the string key `key` in a map.
+    pub fn get(&self, key: &str) -> Option<&Value<'a>> {
+        self.as_map()?
+            .iter()
+            .find(|(k, _)| k.as_str() == Some(key))
+            .map(|(_, v)| v)
+    }
+}
+
+fn int(val: i64) -> Token<'static> {
+    if val < 0 {
+        Token::Int(val)
+    } else {
+        Token::UInt(val as u64)
+    }
+}
+
+/// Reads MessagePack tokens and values from a [`BufView`].
+///
+/// As an iterator it yields the tokens, a malformed one yields one error and ends the
+/// iteration.
+#[derive(Debug)]
+pub struct MsgpackReader<'a> {
+    view: BufView<'a>,
+    max_depth: usize,
+    done: bool,
+}
+
+impl<'a> MsgpackReader<'a> {
+    /// Read from the reader index to the writer index of `view`.
+    pub fn new(view: BufView<'a>) -> Self {
+        MsgpackReader {
+            view,
+            max_depth: MAX_DEPTH,
+            done: false,
+        }
+    }
+
+    /// Set the maximum nesting of arrays and maps in `read_value`.
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// The offset of the next token.
+    pub fn offset(&self) -> usize {
+        self.view.reader_index()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.view.remaining() == 0
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<&'a [u8]> {
+        let view = self.view.try_read_view(len)?;
+        Ok(&view.buf[view.reader_index..view.writer_index])
+    }
+
+    fn read_str(&mut self, len: usize) -> Result<Token<'a>> {
+        let start = self.view.reader_index();
+        let bytes = self.read_slice(len)?;
+        match std::str::from_utf8(bytes) {
+            Ok(s) => Ok(Token::Str(s)),
+            Err(_) => Err(Error::invalid(
+                "bad MessagePack string",
+                self.view.buf,
+                start,
+            )),
+        }
+    }
+
+    fn read_ext(&mut self, len: usize, start: usize) -> Result<Token<'a>> {
+        let ext_type = self.view.try_read_i8()?;
+        let data = self.read_slice(len)?;
+        if ext_type != TIMESTAMP_EXT {
+            return Ok(Token::Ext(ext_type, data));
+        }
+        let bad = || Error::invalid("bad MessagePack timestamp", self.view.buf, start);
+        let ts = match data.len() {
+            4 => Timestamp {
+                secs: u32::from_be_bytes(data.try_into().unwrap()) as i64,
+                nanos: 0,
+            },
+            8 => {
+                let bits = u64::from_be_bytes(data.try_into().unwrap());
+                Timestamp {
+                    secs: (bits & 0x3_ffff_ffff) as i64,
+                    nanos: (bits >> 34) as u32,
+                }
+            }
+            12 => Timestamp {
+                secs: i64::from_be_bytes(data[4..].try_into().unwrap()),
+                nanos: u32::from_be_bytes(data[..4].try_into().unwrap()),
+            },
+            _ => return Err(bad()),
+        };
+        if ts.nanos >= 1_000_000_000 {
+            return Err(bad());
+        }
+        Ok(Token::Timestamp(ts))
+    }
+
+    fn read_token_inner(&mut self, start: usize) -> Result<Token<'a>> {
+        let v = &mut self.view;
+        let token = match v.try_read_u8()? {
+            b @ 0x00..=0x7f => Token::UInt(b as u64),
+            b @ 0x80..=0x8f => Token::Map((b & 0x0f) as u32),
+            b @ 0x90..=0x9f => Token::Array((b & 0x0f) as u32),
+            b @ 0xa0..=0xbf => return self.read_str((b & 0x1f) as usize),
+            0xc0 => Token::Nil,
+            0xc1 => return Err(Error::invalid("reserved MessagePack type", v.buf, start)),
+            0xc2 => Token::Bool(false),
+            0xc3 => Token::Bool(true),
+            0xc4 => {
+                let len = v.try_read_u8()? as usize;
+                Token::Bin(self.read_slice(len)?)
+            }
+            0xc5 => {
+                let len = v.try_read_u16()? as usize;
+                Token::Bin(self.read_slice(len)?)
+            }
+            0xc6 => {
+                let len = v.try_read_u32()? as usize;
+                Token::Bin(self.read_slice(len)?)
+            }
+            0xc7 => {
+                let len = v.try_read_u8()? as usize;
+                return self.read_ext(len, start);
+            }
+            0xc8 => {
+                let len = v.try_read_u16()? as usize;
+                return self.read_ext(len, start);
+            }
+            0xc9 => {
+                let len = v.try_read_u32()? as usize;
+                return self.read_ext(len, start);
+            }
+            0xca => Token::F32(v.try_read_f32()?),
+            0xcb => Token::F64(v.try_read_f64()?),
+            0xcc => Token::UInt(v.try_read_u8()? as u64),
+            0xcd => Token::UInt(v.try_read_u16()? as u64),
+            0xce => Token::UInt(v.try_read_u32()? as u64),
+            0xcf => Token::UInt(v.try_read_u64()?),
+            0xd0 => int(v.try_read_i8()? as i64),
+            0xd1 => int(v.try_read_i16()? as i64),
+            0xd2 => int(v.try_read_i32()? as i64),
+            0xd3 => int(v.try_read_i64()?),
+            b @ 0xd4..=0xd8 => return self.read_ext(1 << (b - 0xd4), start),
+            0xd9 => {
+                let len = v.try_read_u8()? as usize;
+                return self.read_str(len);
+            }
+            0xda => {
+                let len = v.try_read_u16()? as usize;
+                return self.read_str(len);
+            }
+            0xdb => {
+                let len = v.try_read_u32()? as usize;
+                return self.read_str(len);
+            }
+            0xdc => Token::Array(v.try_read_u16()? as u32),
+            0xdd => Token::Array(v.try_read_u32()?),
+            0xde => Token::Map(v.try_read_u16()? as u32),
+            0xdf => Token::Map(v.try_read_u32()?),
+            b @ 0xe0..=0xff => Token::Int(b as i8 as i64),
+        };
+        Ok(token)
+    }
+
+    /// Read the next token.
+    pub fn read_token(&mut self) -> Result<Token<'a>> {
+        let start = self.view.reader_index();
+        let token = self.read_token_inner(start);
+        if token.is_err() {
+            self.view.set_reader_index(start);
+        }
+        token
+    }
+
+    /// Skip the next value, with the items of an array or map.
+    pub fn skip_value(&mut self) -> Result<()> {
+        let start = self.view.reader_index();
+        let mut pending = 1u64;
+        while pending > 0 {
+            pending -= 1;
+            match self.read_token() {
+                Ok(Token::Array(n)) => pending += n as u64,
+                Ok(Token::Map(n)) => pending += 2 * n as u64,
+                Ok(_) => {}
+                Err(e) => {
+                    self.view.set_reader_index(start);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_value_at(&mut self, depth: usize) -> Result<Value<'a>> {
+        let start = self.view.reader_index();
+        let value = match self.read_token()? {
+            Token::Nil => Value::Nil,
+            Token::Bool(val) => Value::Bool(val),
+            Token::UInt(val) => Value::UInt(val),
+            Token::Int(val) => Value::Int(val),
+            Token::F32(val) => Value::F32(val),
+            Token::F64(val) => Value::F64(val),
+            Token::Str(val) => Value::Str(val),
+            Token::Bin(val) => Value::Bin(val),
+            Token::Ext(ext_type, data) => Value::Ext(ext_type, data),
+            Token::Timestamp(ts) => Value::Timestamp(ts),
+            Token::Array(_) | Token::Map(_) if depth == self.max_depth => {
+                return Err(Error::invalid(
+                    "MessagePack nesting too deep",
+                    self.view.buf,
+                    start,
+                ));
+            }
+            Token::Array(n) => {
+                // every item takes at least one byte
+                let mut items = Vec::with_capacity((n as usize).min(self.view.remaining()));
+                for _ in 0..n {
+                    items.push(self.read_value_at(depth + 1)?);
+                }
+                Value::Array(items)
+            }
+            Token::Map(n) => {
+                let mut entries = Vec::with_capacity((n as usize).min(self.view.remaining() / 2));
+                for _ in 0..n {
+                    let key = self.read_value_at(depth + 1)?;
+                    entries.push((key, self.read_value_at(depth + 1)?));
+                }
+                Value::Map(entries)
+            }
+        };
+        Ok(value)
+    }
+
+    /// Read the next value, with the items of an array or map.
+    pub fn read_value(&mut self) -> Result<Value<'a>> {
+        let start = self.view.reader_index();
+        let value = self.read_value_at(0);
+        if value.is_err() {
+            self.view.set_reader_index(start);
+        }
+        value
+    }
+}
+
+impl<'a> Iterator for MsgpackReader<'a> {
+    type Item = Result<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.is_empty() {
+            return None;
+        }
+        let token = self.read_token();
+        self.done = token.is_err();
+        Some(token)
+    }
+}
+
+impl std::iter::FusedIterator for MsgpackReader<'_> {}
+
+/// Writes MessagePack at the writer index of a [`BufViewMut`].
+///
+/// A scalar, string, binary or extension is written whole or not at all, an array or
+/// map is written as its length then its items.
+#[derive(Debug)]
+pub struct MsgpackWriter<'v, 'b> {
+    view: &'v mut BufViewMut<'b>,
+}
+
+impl<'v, 'b> MsgpackWriter<'v, 'b> {
+    pub fn new(view: &'v mut BufViewMut<'b>) -> Self {
+        MsgpackWriter { view }
+    }
+
+    /// The view written to.
+    pub fn view(&mut self) -> &mut BufViewMut<'b> {
+        self.view
+    }
+
+    fn reserve(&self, len: usize) -> Result<()> {
+        self.view.check_get(self.view.writer_index(), len)?;
+        Ok(())
+    }
+
+    fn overflow(&self) -> Error {
+        Error::new(ErrorKind::Overflow, self.view.buf, self.view.writer_index())
+    }
+
+    /// Write the marker and length of a string, binary, array or map, the markers are
+    /// for the 8, 16 and 32 bits lengths, or None when the format has no such length.
+    fn write_len(&mut self, len: usize, extra: usize, markers: [Option<u8>; 3]) -> Result<()> {
+        match (len, markers) {
+            (0..=0xff, [Some(m), _, _]) => {
+                self.reserve(2 + extra)?;
+                self.view.write_u8(m);
+                self.view.write_u8(len as u8);
+            }
+            (0..=0xffff, [_, Some(m), _]) => {
+                self.reserve(3 + extra)?;
+                self.view.write_u8(m);
+                self.view.write_u16(len as u16);
+            }
+            (0..=0xffff_ffff, [_, _, Some(m)]) => {
+                self.reserve(5 + extra)?;
+                self.view.write_u8(m);
+                self.view.write_u32(len as u32);
+            }
+            _ => return Err(self.overflow()),
+        }
+        Ok(())
+    }
+
+    pub fn write_nil(&mut self) -> Result<()> {
+        self.view.try_write_bytes(&[0xc0])
+    }
+
+    pub fn write_bool(&mut self, val: bool) -> Result<()> {
+        self.view.try_write_bytes(&[0xc2 | val as u8])
+    }
+
+    pub fn write_u64(&mut self, val: u64) -> Result<()> {
+        match val {
+            0..=0x7f => self.view.try_write_bytes(&[val as u8]),
+            0x80..=0xff => self.view.try_write_bytes(&[0xcc, val as u8]),
+            0x100..=0xffff => {
+                self.reserve(3)?;
+                self.view.write_u8(0xcd);
+                self.view.write_u16(val as u16);
+                Ok(())
+            }
+            0x1_0000..=0xffff_ffff => {
+                self.reserve(5)?;
+                self.view.write_u8(0xce);
+                self.view.write_u32(val as u32);
+                Ok(())
+            }
+            _ => {
+                self.reserve(9)?;
+                self.view.write_u8(0xcf);
+                self.view.write_u64(val);
+                Ok(())
+            }
+        }
+    }
+
+    /// Write an integer, a positive one is written as by `write_u64`.
+    pub fn write_i64(&mut self, val: i64) -> Result<()> {
+        if val >= 0 {
+            return self.write_u64(val as u64);
+        }
+        if val >= -32 {
+            return self.view.try_write_bytes(&[val as u8]);
+        }
+        if let Ok(val) = i8::try_from(val) {
+            return self.view.try_write_bytes(&[0xd0, val as u8]);
+        }
+        if let Ok(val) = i16::try_from(val) {
+            self.reserve(3)?;
+            self.view.write_u8(0xd1);
+            self.view.write_i16(val);
+        } else if let Ok(val) = i32::try_from(val) {
+            self.reserve(5)?;
+            self.view.write_u8(0xd2);
+            self.view.write_i32(val);
+        } else {
+            self.reserve(9)?;
+            self.view.write_u8(0xd3);
+            self.view.write_i64(val);
+        }
+        Ok(())
+    }
+
+    pub fn write_f32(&mut self, val: f32) -> Result<()> {
+        self.reserve(5)?;
+        self.view.write_u8(0xca);
+        self.view.write_f32(val);
+        Ok(())
+    }
+
+    pub fn write_f64(&mut self, val: f64) -> Result<()> {
+        self.reserve(9)?;
+        self.view.write_u8(0xcb);
+        self.view.write_f64(val);
+        Ok(())
+    }
+
+    pub fn write_str(&mut self, val: &str) -> Result<()> {
+        let len = val.len();
+        if len < 32 {
+            self.reserve(1 + len)?;
+            self.view.write_u8(0xa0 | len as u8);
+        } else {
+            self.write_len(len, len, [Some(0xd9), Some(0xda), Some(0xdb)])?;
+        }
+        self.view.write_bytes(val.as_bytes());
+        Ok(())
+    }
+
+    pub fn write_bin(&mut self, val: &[u8]) -> Result<()> {
+        let len = val.len();
+        self.write_len(len, len, [Some(0xc4), Some(0xc5), Some(0xc6)])?;
+        self.view.write_bytes(val);
+        Ok(())
+    }
+
+    /// Write the length of an array, its `len` items are written next.
+    pub fn write_array_len(&mut self, len: usize) -> Result<()> {
+        if len < 16 {
+            return self.view.try_write_bytes(&[0x90 | len as u8]);
+        }
+        self.write_len(len, 0, [None, Some(0xdc), Some(0xdd)])
+    }
+
+    /// Write the length of a map, its `len` keys and values are written next.
+    pub fn write_map_len(&mut self, len: usize) -> Result<()> {
+        if len < 16 {
+            return self.view.try_write_bytes(&[0x80 | len as u8]);
+        }
+        self.write_len(len, 0, [None, Some(0xde), Some(0xdf)])
+    }
+
+    pub fn write_ext(&mut self, ext_type: i8, data: &[u8]) -> Result<()> {
+        let len = data.len();
+        let fixext = match len {
+            1 => Some(0xd4),
+            2 => Some(0xd5),
+            4 => Some(0xd6),
+            8 => Some(0xd7),
+            16 => Some(0xd8),
+            _ => None,
+        };
+        match fixext {
+            Some(m) => {
+                self.reserve(2 + len)?;
+                self.view.write_u8(m);
+            }
+            None => self.write_len(len, 1 + len, [Some(0xc7), Some(0xc8), Some(0xc9)])?,
+        }
+        self.view.write_u8(ext_type as u8);
+        self.view.write_bytes(data);
+        Ok(())
+    }
+
+    /// Write a timestamp in its 32, 64 or 96 bits format, the smallest that holds it.
+    pub fn write_timestamp(&mut self, ts: Timestamp) -> Result<()> {
+        if ts.nanos >= 1_000_000_000 {
+            return Err(self.overflow());
+        }
+        match ts.secs {
+            0..=0xffff_ffff if ts.nanos == 0 => {
+                self.write_ext(TIMESTAMP_EXT, &(ts.secs as u32).to_be_bytes())
+            }
+            0..=0x3_ffff_ffff => {
+                let bits = (ts.nanos as u64) << 34 | ts.secs as u64;
+                self.write_ext(TIMESTAMP_EXT, &bits.to_be_bytes())
+            }
+            _ => {
+                let mut data = [0u8; 12];
+                data[..4].copy_from_slice(&ts.nanos.to_be_bytes());
+                data[4..].copy_from_slice(&ts.secs.to_be_bytes());
+                self.write_ext(TIMESTAMP_EXT, &data)
+            }
+        }
+    }
+
+    /// Write a token, an array or map token is followed by its items.
+    pub fn write_token(&mut self, token: Token) -> Result<()> {
+        match token {
+            Token::Nil => self.write_nil(),
+            Token::Bool(val) => self.write_bool(val),
+            Token::UInt(val) => self.write_u64(val),
+            Token::Int(val) => self.write_i64(val),
+            Token::F32(val) => self.write_f32(val),
+            Token::F64(val) => self.write_f64(val),
+            Token::Str(val) => self.write_str(val),
+            Token::Bin(val) => self.write_bin(val),
+            Token::Array(len) => self.write_array_len(len as usize),
+            Token::Map(len) => self.write_map_len(len as usize),
+            Token::Ext(ext_type, data) => self.write_ext(ext_type, data),
+            Token::Timestamp(ts) => self.write_timestamp(ts),
+        }
+    }
+
+    /// Write a value with its items, an error leaves the items written before it.
+    pub fn write_value(&mut self, value: &Value) -> Result<()> {
+        match value {
+            Value::Nil => self.write_nil(),
+            Value::Bool(val) => self.write_bool(*val),
+            Value::UInt(val) => self.write_u64(*val),
+            Value::Int(val) => self.write_i64(*val),
+            Value::F32(val) => self.write_f32(*val),
+            Value::F64(val) => self.write_f64(*val),
+            Value::Str(val) => self.write_str(val),
+            Value::Bin(val) => self.write_bin(val),
+            Value::Array(items) => {
+                self.write_array_len(items.len())?;
+                items.iter().try_for_each(|item| self.write_value(item))
+            }
+            Value::Map(entries) => {
+                self.write_map_len(entries.len())?;
+                entries.iter().try_for_each(|(key, val)| {
+                    self.write_value(key)?;
+                    self.write_value(val)
+                })
+            }
+            Value::Ext(ext_type, data) => self.write_ext(*ext_type, data),
+            Value::Timestamp(ts) => self.write_timestamp(*ts),
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use super::{be16, check_len, ether_type, Header};
+use crate::error::{Error, Result};
+use crate::{BufViewMut, MacAddr};
+use std::net::Ipv4Addr;
+
+/// An ARP packet, with addresses of any length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpPacket<'a> {
+    bytes: &'a [u8],
+}
+
+impl ArpPacket<'_> {
+    pub const REQUEST: u16 = 1;
+    pub const REPLY: u16 = 2;
+
+    /// The hardware type, 1 for Ethernet.
+    pub fn htype(&self) -> u16 {
+        be16(self.bytes, 0)
+    }
+
+    /// The protocol type, an EtherType.
+    pub fn ptype(&self) -> u16 {
+        be16(self.bytes, 2)
+    }
+
+    pub fn hlen(&self) -> u8 {
+        self.bytes[4]
+    }
+
+    pub fn plen(&self) -> u8 {
+        self.bytes[5]
+    }
+
+    pub fn oper(&self) -> u16 {
+        be16(self.bytes, 6)
+    }
+
+    pub fn sender_hw_addr(&self) -> &[u8] {
+        let start = 8;
+        &self.bytes[start..start + self.hlen() as usize]
+    }
+
+    pub fn sender_proto_addr(&self) -> &[u8] {
+        let start = 8 + self.hlen() as usize;
+        &self.bytes[start..start + self.plen() as usize]
+    }
+
+    pub fn target_hw_addr(&self) -> &[u8] {
+        let start = 8 + self.hlen() as usize + self.plen() as usize;
+        &self.bytes[start..start + self.hlen() as usize]
+    }
+
+    pub fn target_proto_addr(&self) -> &[u8] {
+        let start = 8 + 2 * self.hlen() as usize + self.plen() as usize;
+        &self.bytes[start..start + self.plen() as usize]
+    }
+
+    /// True for Ethernet and IPv4 addresses, when the address accessors return Some.
+    pub fn is_ethernet_ipv4(&self) -> bool {
+        self.htype() == 1
+            && self.ptype() == ether_type::IPV4
+            && self.hlen() == 6
+            && self.plen() == 4
+    }
+
+    pub fn sender_mac(&self) -> Option<MacAddr> {
+        self.is_ethernet_ipv4()
+            .then(|| MacAddr::new(self.sender_hw_addr().try_into().unwrap()))
+    }
+
+    pub fn sender_ipv4(&self) -> Option<Ipv4Addr> {
+        self.is_ethernet_ipv4().then(|| {
+            <[u8; 4]>::try_from(self.sender_proto_addr())
+                .unwrap()
+                .into()
+        })
+    }
+
+    pub fn target_mac(&self) -> Option<MacAddr> {
+        self.is_ethernet_ipv4()
+            .then(|| MacAddr::new(self.target_hw_addr().try_into().unwrap()))
+    }
+
+    pub fn target_ipv4(&self) -> Option<Ipv4Addr> {
+        self.is_ethernet_ipv4().then(|| {
+            <[u8; 4]>::try_from(self.target_proto_addr())
+                .unwrap()
+                .into()
+        })
+    }
+}
+
+impl<'a> Header<'a> for ArpPacket<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let fixed = check_len(buf, start, 8)?;
+        let len = 8 + 2 * (fixed[4] as usize + fixed[5] as usize);
+        if fixed[4] == 0 || fixed[5] == 0 {
+            return Err(Error::invalid("zero ARP address length", buf, start + 4));
+        }
+        let bytes = check_len(buf, start, len)?;
+        Ok(ArpPacket { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Writes an Ethernet and IPv4 [`ArpPacket`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArpBuilder {
+    pub oper: u16,
+    pub sender_mac: MacAddr,
+    pub sender_ip: Ipv4Addr,
+    pub target_mac: MacAddr,
+    pub target_ip: Ipv4Addr,
+}
+
+impl ArpBuilder {
+    /// Write the 28 bytes packet and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + 28);
+        view.write_u16(1);
+        view.write_u16(ether_type::IPV4);
+        view.write_u8(6);
+        view.write_u8(4);
+        view.write_u16(self.oper);
+        view.write_mac(self.sender_mac);
+        view.write_ipv4(self.sender_ip);
+        view.write_mac(self.target_mac);
+        view.write_ipv4(self.target_ip);
+        start
+    }
+}
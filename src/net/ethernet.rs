@@ -0,0 +1,146 @@
+use super::{be16, check_len, Header};
+use crate::error::Result;
+use crate::{BufViewMut, MacAddr};
+
+/// An Ethernet II header, 14 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthernetHeader<'a> {
+    bytes: &'a [u8],
+}
+
+impl EthernetHeader<'_> {
+    pub const LEN: usize = 14;
+
+    pub fn dst(&self) -> MacAddr {
+        MacAddr::new(self.bytes[..6].try_into().unwrap())
+    }
+
+    pub fn src(&self) -> MacAddr {
+        MacAddr::new(self.bytes[6..12].try_into().unwrap())
+    }
+
+    /// The EtherType, `ether_type::VLAN` when an 802.1Q tag follows.
+    pub fn ether_type(&self) -> u16 {
+        be16(self.bytes, 12)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Header<'a> for EthernetHeader<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let bytes = check_len(buf, start, Self::LEN)?;
+        Ok(EthernetHeader { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// An 802.1Q VLAN tag, the 4 bytes after an Ethernet header of EtherType
+/// `ether_type::VLAN` or `ether_type::QINQ`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VlanTag<'a> {
+    bytes: &'a [u8],
+}
+
+impl VlanTag<'_> {
+    pub const LEN: usize = 4;
+
+    /// The tag control information, priority, drop eligible and VLAN id.
+    pub fn tci(&self) -> u16 {
+        be16(self.bytes, 0)
+    }
+
+    /// The priority code point.
+    pub fn pcp(&self) -> u8 {
+        (self.tci() >> 13) as u8
+    }
+
+    /// The drop eligible indicator.
+    pub fn dei(&self) -> bool {
+        self.tci() & 0x1000 != 0
+    }
+
+    pub fn vid(&self) -> u16 {
+        self.tci() & 0x0fff
+    }
+
+    /// The EtherType of the encapsulated frame.
+    pub fn ether_type(&self) -> u16 {
+        be16(self.bytes, 2)
+    }
+}
+
+impl<'a> Header<'a> for VlanTag<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let bytes = check_len(buf, start, Self::LEN)?;
+        Ok(VlanTag { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Writes an [`EthernetHeader`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthernetBuilder {
+    pub dst: MacAddr,
+    pub src: MacAddr,
+    pub ether_type: u16,
+}
+
+impl EthernetBuilder {
+    pub fn new(dst: MacAddr, src: MacAddr, ether_type: u16) -> Self {
+        EthernetBuilder {
+            dst,
+            src,
+            ether_type,
+        }
+    }
+
+    /// Write the header and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + EthernetHeader::LEN);
+        view.write_mac(self.dst);
+        view.write_mac(self.src);
+        view.write_u16(self.ether_type);
+        start
+    }
+}
+
+/// Writes a [`VlanTag`], the Ethernet header before it has EtherType
+/// `ether_type::VLAN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VlanBuilder {
+    pub pcp: u8,
+    pub dei: bool,
+    pub vid: u16,
+    pub ether_type: u16,
+}
+
+impl VlanBuilder {
+    pub fn new(vid: u16, ether_type: u16) -> Self {
+        VlanBuilder {
+            pcp: 0,
+            dei: false,
+            vid,
+            ether_type,
+        }
+    }
+
+    /// Write the tag and return its start, `pcp` must fit in 3 bits and `vid` in 12.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        assert!(self.pcp < 8 && self.vid < 0x1000);
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + VlanTag::LEN);
+        view.write_u16((self.pcp as u16) << 13 | (self.dei as u16) << 12 | self.vid);
+        view.write_u16(self.ether_type);
+        start
+    }
+}
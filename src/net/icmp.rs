@@ -0,0 +1,145 @@
+use super::{be16, be32, check_len, ip_proto, pseudo_header_v6, Header};
+use crate::checksum::{Checksum, InternetChecksum};
+use crate::error::{Error, ErrorKind, Result};
+use crate::BufViewMut;
+use std::net::Ipv6Addr;
+
+/// An ICMP or ICMPv6 header, 8 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IcmpHeader<'a> {
+    bytes: &'a [u8],
+}
+
+impl IcmpHeader<'_> {
+    pub const LEN: usize = 8;
+
+    pub const ECHO_REPLY: u8 = 0;
+    pub const DEST_UNREACHABLE: u8 = 3;
+    pub const ECHO_REQUEST: u8 = 8;
+    pub const TIME_EXCEEDED: u8 = 11;
+    pub const V6_ECHO_REQUEST: u8 = 128;
+    pub const V6_ECHO_REPLY: u8 = 129;
+
+    pub fn icmp_type(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    pub fn code(&self) -> u8 {
+        self.bytes[1]
+    }
+
+    pub fn checksum(&self) -> u16 {
+        be16(self.bytes, 2)
+    }
+
+    /// The last 4 bytes of the header, their meaning depends on the type.
+    pub fn rest(&self) -> u32 {
+        be32(self.bytes, 4)
+    }
+
+    /// The identifier of an echo request or reply.
+    pub fn identifier(&self) -> u16 {
+        be16(self.bytes, 4)
+    }
+
+    /// The sequence number of an echo request or reply.
+    pub fn sequence(&self) -> u16 {
+        be16(self.bytes, 6)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    /// Verify the ICMP checksum over this header and `payload`.
+    pub fn verify_checksum(&self, payload: &[u8]) -> bool {
+        let mut sum = InternetChecksum::new();
+        sum.update(self.bytes);
+        sum.update(payload);
+        sum.finalize() == 0
+    }
+
+    /// Verify the ICMPv6 checksum over the IPv6 pseudo header, this header and `payload`.
+    pub fn verify_checksum_v6(&self, src: Ipv6Addr, dst: Ipv6Addr, payload: &[u8]) -> bool {
+        let len = (Self::LEN + payload.len()) as u32;
+        let mut sum = pseudo_header_v6(src, dst, ip_proto::ICMPV6, len);
+        sum.update(self.bytes);
+        sum.update(payload);
+        sum.finalize() == 0
+    }
+}
+
+impl<'a> Header<'a> for IcmpHeader<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let bytes = check_len(buf, start, Self::LEN)?;
+        Ok(IcmpHeader { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Writes an [`IcmpHeader`], `finish` or `finish_v6` sets the checksum once the
+/// payload is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IcmpBuilder {
+    pub icmp_type: u8,
+    pub code: u8,
+    pub rest: u32,
+}
+
+impl IcmpBuilder {
+    pub fn new(icmp_type: u8, code: u8, rest: u32) -> Self {
+        IcmpBuilder {
+            icmp_type,
+            code,
+            rest,
+        }
+    }
+
+    /// An echo request or reply header.
+    pub fn echo(icmp_type: u8, identifier: u16, sequence: u16) -> Self {
+        IcmpBuilder::new(icmp_type, 0, (identifier as u32) << 16 | sequence as u32)
+    }
+
+    /// Write the header with a zero checksum and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + IcmpHeader::LEN);
+        view.write_u8(self.icmp_type);
+        view.write_u8(self.code);
+        view.write_u16(0);
+        view.write_u32(self.rest);
+        start
+    }
+
+    /// Set the ICMP checksum of the message from `start` to the writer index.
+    pub fn finish(view: &mut BufViewMut, start: usize) {
+        Self::set_checksum(view, start, InternetChecksum::new());
+    }
+
+    /// Set the ICMPv6 checksum over the IPv6 pseudo header of the message from
+    /// `start` to the writer index.
+    pub fn finish_v6(
+        view: &mut BufViewMut,
+        start: usize,
+        src: Ipv6Addr,
+        dst: Ipv6Addr,
+    ) -> Result<()> {
+        let len = u32::try_from(view.writer_index() - start)
+            .map_err(|_| Error::new(ErrorKind::Overflow, view.buf, start))?;
+        Self::set_checksum(
+            view,
+            start,
+            pseudo_header_v6(src, dst, ip_proto::ICMPV6, len),
+        );
+        Ok(())
+    }
+
+    fn set_checksum(view: &mut BufViewMut, start: usize, mut sum: InternetChecksum) {
+        view.set_u16(start + 2, 0);
+        sum.update(&view.buf[start..view.writer_index()]);
+        view.set_u16(start + 2, sum.finalize());
+    }
+}
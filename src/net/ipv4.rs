@@ -0,0 +1,184 @@
+use super::{be16, check_len, ipv4_at, Header, OptionIter};
+use crate::checksum::{Checksum, InternetChecksum};
+use crate::error::{Error, ErrorKind, Result};
+use crate::BufViewMut;
+use std::net::Ipv4Addr;
+
+/// An IPv4 header, options included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4Header<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Ipv4Header<'a> {
+    pub const MIN_LEN: usize = 20;
+
+    pub fn version(&self) -> u8 {
+        self.bytes[0] >> 4
+    }
+
+    /// The header length in 32-bit words.
+    pub fn ihl(&self) -> u8 {
+        self.bytes[0] & 0x0f
+    }
+
+    pub fn dscp(&self) -> u8 {
+        self.bytes[1] >> 2
+    }
+
+    pub fn ecn(&self) -> u8 {
+        self.bytes[1] & 0x03
+    }
+
+    /// The length of the header and payload.
+    pub fn total_length(&self) -> u16 {
+        be16(self.bytes, 2)
+    }
+
+    /// The payload length, `total_length` minus the header length.
+    pub fn payload_len(&self) -> usize {
+        self.total_length() as usize - self.bytes.len()
+    }
+
+    pub fn identification(&self) -> u16 {
+        be16(self.bytes, 4)
+    }
+
+    pub fn dont_fragment(&self) -> bool {
+        self.bytes[6] & 0x40 != 0
+    }
+
+    pub fn more_fragments(&self) -> bool {
+        self.bytes[6] & 0x20 != 0
+    }
+
+    /// The fragment offset in 8 byte units.
+    pub fn fragment_offset(&self) -> u16 {
+        be16(self.bytes, 6) & 0x1fff
+    }
+
+    pub fn ttl(&self) -> u8 {
+        self.bytes[8]
+    }
+
+    pub fn protocol(&self) -> u8 {
+        self.bytes[9]
+    }
+
+    pub fn checksum(&self) -> u16 {
+        be16(self.bytes, 10)
+    }
+
+    pub fn src(&self) -> Ipv4Addr {
+        ipv4_at(self.bytes, 12)
+    }
+
+    pub fn dst(&self) -> Ipv4Addr {
+        ipv4_at(self.bytes, 16)
+    }
+
+    pub fn options(&self) -> OptionIter<'a> {
+        OptionIter::new(self.bytes, Self::MIN_LEN)
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn verify_checksum(&self) -> bool {
+        InternetChecksum::checksum(self.bytes) == 0
+    }
+}
+
+impl<'a> Header<'a> for Ipv4Header<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let fixed = check_len(buf, start, Self::MIN_LEN)?;
+        if fixed[0] >> 4 != 4 {
+            return Err(Error::invalid("not an IPv4 header", buf, start));
+        }
+        let len = (fixed[0] & 0x0f) as usize * 4;
+        if len < Self::MIN_LEN {
+            return Err(Error::invalid("IHL below 5", buf, start));
+        }
+        if (be16(fixed, 2) as usize) < len {
+            return Err(Error::invalid(
+                "total length below the header length",
+                buf,
+                start + 2,
+            ));
+        }
+        let bytes = check_len(buf, start, len)?;
+        Ok(Ipv4Header { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Writes an [`Ipv4Header`], `finish` sets the total length and checksum once the
+/// payload is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv4Builder<'a> {
+    pub dscp: u8,
+    pub ecn: u8,
+    pub identification: u16,
+    pub dont_fragment: bool,
+    pub ttl: u8,
+    pub protocol: u8,
+    pub src: Ipv4Addr,
+    pub dst: Ipv4Addr,
+    /// Padded with zeros (end of list) to a multiple of 4 bytes, 40 bytes at most.
+    pub options: &'a [u8],
+}
+
+impl<'a> Ipv4Builder<'a> {
+    /// A header with a TTL of 64 and the don't fragment flag.
+    pub fn new(src: Ipv4Addr, dst: Ipv4Addr, protocol: u8) -> Self {
+        Ipv4Builder {
+            dscp: 0,
+            ecn: 0,
+            identification: 0,
+            dont_fragment: true,
+            ttl: 64,
+            protocol,
+            src,
+            dst,
+            options: &[],
+        }
+    }
+
+    /// Write the header with a zero total length and checksum, and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        assert!(self.options.len() <= 40 && self.dscp < 64 && self.ecn < 4);
+        let padded = self.options.len().div_ceil(4) * 4;
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + Ipv4Header::MIN_LEN + padded);
+        view.write_u8(0x40 | ((Ipv4Header::MIN_LEN + padded) / 4) as u8);
+        view.write_u8(self.dscp << 2 | self.ecn);
+        view.write_u16(0);
+        view.write_u16(self.identification);
+        view.write_u16(if self.dont_fragment { 0x4000 } else { 0 });
+        view.write_u8(self.ttl);
+        view.write_u8(self.protocol);
+        view.write_u16(0);
+        view.write_ipv4(self.src);
+        view.write_ipv4(self.dst);
+        view.write_bytes(self.options);
+        view.write_bytes(&[0; 3][..padded - self.options.len()]);
+        start
+    }
+
+    /// Set the total length up to the writer index and the checksum of the header
+    /// written at `start`. Fail with `Overflow` when the packet is over 65535 bytes.
+    pub fn finish(view: &mut BufViewMut, start: usize) -> Result<()> {
+        let total = u16::try_from(view.writer_index() - start)
+            .map_err(|_| Error::new(ErrorKind::Overflow, view.buf, start + 2))?;
+        view.set_u16(start + 2, total);
+        let len = (view.buf[start] & 0x0f) as usize * 4;
+        view.set_u16(start + 10, 0);
+        let sum = InternetChecksum::checksum(&view.buf[start..start + len]);
+        view.set_u16(start + 10, sum);
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+use super::{be16, be32, check_len, ipv6_at, Header};
+use crate::error::{Error, ErrorKind, Result};
+use crate::BufViewMut;
+use std::net::Ipv6Addr;
+
+/// An IPv6 fixed header, 40 bytes. Extension headers are parsed as payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6Header<'a> {
+    bytes: &'a [u8],
+}
+
+impl Ipv6Header<'_> {
+    pub const LEN: usize = 40;
+
+    pub fn version(&self) -> u8 {
+        self.bytes[0] >> 4
+    }
+
+    pub fn traffic_class(&self) -> u8 {
+        (be16(self.bytes, 0) >> 4) as u8
+    }
+
+    pub fn flow_label(&self) -> u32 {
+        be32(self.bytes, 0) & 0x000f_ffff
+    }
+
+    /// The length after the fixed header, extension headers included.
+    pub fn payload_length(&self) -> u16 {
+        be16(self.bytes, 4)
+    }
+
+    pub fn next_header(&self) -> u8 {
+        self.bytes[6]
+    }
+
+    pub fn hop_limit(&self) -> u8 {
+        self.bytes[7]
+    }
+
+    pub fn src(&self) -> Ipv6Addr {
+        ipv6_at(self.bytes, 8)
+    }
+
+    pub fn dst(&self) -> Ipv6Addr {
+        ipv6_at(self.bytes, 24)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Header<'a> for Ipv6Header<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let bytes = check_len(buf, start, Self::LEN)?;
+        if bytes[0] >> 4 != 6 {
+            return Err(Error::invalid("not an IPv6 header", buf, start));
+        }
+        Ok(Ipv6Header { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Writes an [`Ipv6Header`], `finish` sets the payload length once the payload is
+/// written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ipv6Builder {
+    pub traffic_class: u8,
+    pub flow_label: u32,
+    pub next_header: u8,
+    pub hop_limit: u8,
+    pub src: Ipv6Addr,
+    pub dst: Ipv6Addr,
+}
+
+impl Ipv6Builder {
+    /// A header with a hop limit of 64.
+    pub fn new(src: Ipv6Addr, dst: Ipv6Addr, next_header: u8) -> Self {
+        Ipv6Builder {
+            traffic_class: 0,
+            flow_label: 0,
+            next_header,
+            hop_limit: 64,
+            src,
+            dst,
+        }
+    }
+
+    /// Write the header with a zero payload length and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        assert!(self.flow_label < 1 << 20);
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + Ipv6Header::LEN);
+        view.write_u32(6 << 28 | (self.traffic_class as u32) << 20 | self.flow_label);
+        view.write_u16(0);
+        view.write_u8(self.next_header);
+        view.write_u8(self.hop_limit);
+        view.write_ipv6(self.src);
+        view.write_ipv6(self.dst);
+        start
+    }
+
+    /// Set the payload length up to the writer index of the header written at `start`.
+    /// Fail with `Overflow` when the payload is over 65535 bytes.
+    pub fn finish(view: &mut BufViewMut, start: usize) -> Result<()> {
+        let len = u16::try_from(view.writer_index() - start - Ipv6Header::LEN)
+            .map_err(|_| Error::new(ErrorKind::Overflow, view.buf, start + 4))?;
+        view.set_u16(start + 4, len);
+        Ok(())
+    }
+}
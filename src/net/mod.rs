@@ -0,0 +1,216 @@
+//! Zero-copy packet header views and builders, behind the `net` feature.
+//!
+//! A header view borrows the header bytes and decodes the fields on access. Views are
+//! parsed with [`Header::parse`] or read from a [`BufView`] with `read_header`, which
+//! validates the lengths (IHL, TCP data offset, UDP length...) and consumes the header
+//! only. Errors of `read_header` have absolute offsets in the wrapped buffer.
+//!
+//! Builders write a header at the writer index of a [`BufViewMut`] and return its
+//! start. Headers with a length or checksum covering the payload are written with
+//! these fields zeroed, the payload is written after them and `finish` back-patches
+//! the fields, so nested headers are finished innermost first.
+//!
+//! Example
+//! ```
+//! use buf_view::net::{ip_proto, Header, Ipv4Builder, Ipv4Header, UdpBuilder, UdpHeader};
+//! use buf_view::{BufView, BufViewMut};
+//! use std::net::Ipv4Addr;
+//!
+//! let (src, dst) = (Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2));
+//! let mut buf = [0u8; 64];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let ip = Ipv4Builder::new(src, dst, ip_proto::UDP).write(&mut buf_view);
+//! let udp = UdpBuilder::new(5353, 53).write(&mut buf_view);
+//! buf_view.write_bytes(b"query");
+//! UdpBuilder::finish_v4(&mut buf_view, udp, src, dst).unwrap();
+//! Ipv4Builder::finish(&mut buf_view, ip).unwrap();
+//! let len = buf_view.writer_index();
+//!
+//! let mut buf_view = BufView::wrap(&buf[..len]);
+//! let ip = buf_view.read_header::<Ipv4Header>().unwrap();
+//! assert!(ip.verify_checksum());
+//! assert_eq!((ip.src(), ip.total_length()), (src, 33));
+//! let udp = buf_view.read_header::<UdpHeader>().unwrap();
+//! assert_eq!(udp.dst_port(), 53);
+//! let payload = &buf[buf_view.reader_index()..len];
+//! assert!(udp.verify_checksum_v4(ip.src(), ip.dst(), payload));
+//! ```
+
+mod arp;
+mod ethernet;
+mod icmp;
+mod ipv4;
+mod ipv6;
+mod tcp;
+mod udp;
+
+pub use self::arp::{ArpBuilder, ArpPacket};
+pub use self::ethernet::{EthernetBuilder, EthernetHeader, VlanBuilder, VlanTag};
+pub use self::icmp::{IcmpBuilder, IcmpHeader};
+pub use self::ipv4::{Ipv4Builder, Ipv4Header};
+pub use self::ipv6::{Ipv6Builder, Ipv6Header};
+pub use self::tcp::{TcpBuilder, TcpHeader};
+pub use self::udp::{UdpBuilder, UdpHeader};
+
+use crate::checksum::{Checksum, InternetChecksum};
+use crate::error::{Error, Result};
+use crate::BufView;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// EtherType values.
+pub mod ether_type {
+    pub const IPV4: u16 = 0x0800;
+    pub const ARP: u16 = 0x0806;
+    pub const VLAN: u16 = 0x8100;
+    pub const IPV6: u16 = 0x86dd;
+    pub const QINQ: u16 = 0x88a8;
+}
+
+/// IP protocol numbers, the IPv4 protocol and IPv6 next header field.
+pub mod ip_proto {
+    pub const ICMP: u8 = 1;
+    pub const TCP: u8 = 6;
+    pub const UDP: u8 = 17;
+    pub const ICMPV6: u8 = 58;
+}
+
+/// A header view parsed from the start of a byte slice.
+pub trait Header<'a>: Sized {
+    /// Parse the header starting at `start` of `buf`, error offsets are in `buf`.
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self>;
+
+    /// The length of the header, options included.
+    fn header_len(&self) -> usize;
+
+    /// Parse the header at the start of `bytes`, the bytes after it are ignored.
+    fn parse(bytes: &'a [u8]) -> Result<Self> {
+        Self::parse_at(bytes, 0)
+    }
+}
+
+impl<'a> BufView<'a> {
+    /// Parse the header `H` at the reader index and consume it, nothing is consumed on
+    /// failure.
+    pub fn read_header<H: Header<'a>>(&mut self) -> Result<H> {
+        let header = H::parse_at(&self.buf[..self.writer_index], self.reader_index)?;
+        self.reader_index += header.header_len();
+        Ok(header)
+    }
+}
+
+/// Check `buf` holds `len` bytes at `start`, and return them.
+fn check_len(buf: &[u8], start: usize, len: usize) -> Result<&[u8]> {
+    let available = buf.len().saturating_sub(start);
+    if available < len {
+        return Err(Error::out_of_bounds(buf, start, len, available));
+    }
+    Ok(&buf[start..start + len])
+}
+
+#[inline]
+fn be16(bytes: &[u8], index: usize) -> u16 {
+    u16::from_be_bytes([bytes[index], bytes[index + 1]])
+}
+
+#[inline]
+fn be32(bytes: &[u8], index: usize) -> u32 {
+    u32::from_be_bytes(bytes[index..index + 4].try_into().unwrap())
+}
+
+fn ipv4_at(bytes: &[u8], index: usize) -> Ipv4Addr {
+    Ipv4Addr::from(be32(bytes, index))
+}
+
+fn ipv6_at(bytes: &[u8], index: usize) -> Ipv6Addr {
+    Ipv6Addr::from(<[u8; 16]>::try_from(&bytes[index..index + 16]).unwrap())
+}
+
+/// The internet checksum seeded with the IPv4 pseudo header of a `len` bytes
+/// `protocol` segment, feed it the segment to get the UDP or TCP checksum.
+pub fn pseudo_header_v4(src: Ipv4Addr, dst: Ipv4Addr, protocol: u8, len: u16) -> InternetChecksum {
+    let mut sum = InternetChecksum::new();
+    sum.update(&src.octets());
+    sum.update(&dst.octets());
+    sum.add_u16(protocol as u16);
+    sum.add_u16(len);
+    sum
+}
+
+/// The internet checksum seeded with the IPv6 pseudo header of a `len` bytes
+/// upper-layer packet, feed it the packet to get the UDP, TCP or ICMPv6 checksum.
+pub fn pseudo_header_v6(
+    src: Ipv6Addr,
+    dst: Ipv6Addr,
+    next_header: u8,
+    len: u32,
+) -> InternetChecksum {
+    let mut sum = InternetChecksum::new();
+    sum.update(&src.octets());
+    sum.update(&dst.octets());
+    sum.add_u32(len);
+    sum.add_u32(next_header as u32);
+    sum
+}
+
+/// An IPv4 or TCP option, NOP and end of list excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RawOption<'a> {
+    pub kind: u8,
+    /// The option data, after the kind and length bytes.
+    pub data: &'a [u8],
+}
+
+/// An iterator over the IPv4 or TCP options of a header.
+///
+/// It skips NOP options and stops at the end of list option. A truncated option or a
+/// bad option length yields one error, with the offset in the header, and ends the
+/// iteration.
+#[derive(Debug, Clone)]
+pub struct OptionIter<'a> {
+    header: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> OptionIter<'a> {
+    fn new(header: &'a [u8], start: usize) -> Self {
+        OptionIter { header, pos: start }
+    }
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+    type Item = Result<RawOption<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let end = self.header.len();
+        loop {
+            let kind = *self.header.get(self.pos)?;
+            match kind {
+                0 => {
+                    self.pos = end;
+                    return None;
+                }
+                1 => self.pos += 1,
+                _ => break,
+            }
+        }
+        let start = self.pos;
+        let len = match self.header.get(start + 1) {
+            Some(len) => *len as usize,
+            None => {
+                self.pos = end;
+                return Some(Err(Error::invalid("truncated option", self.header, start)));
+            }
+        };
+        if len < 2 || start + len > end {
+            self.pos = end;
+            return Some(Err(Error::invalid("bad option length", self.header, start)));
+        }
+        self.pos = start + len;
+        Some(Ok(RawOption {
+            kind: self.header[start],
+            data: &self.header[start + 2..start + len],
+        }))
+    }
+}
+
+impl std::iter::FusedIterator for OptionIter<'_> {}
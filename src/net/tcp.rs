@@ -0,0 +1,226 @@
+use super::{
+    be16, be32, check_len, ip_proto, pseudo_header_v4, pseudo_header_v6, Header, OptionIter,
+};
+use crate::checksum::{Checksum, InternetChecksum};
+use crate::error::{Error, ErrorKind, Result};
+use crate::BufViewMut;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// A TCP header, options included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpHeader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> TcpHeader<'a> {
+    pub const MIN_LEN: usize = 20;
+
+    pub const FIN: u16 = 0x001;
+    pub const SYN: u16 = 0x002;
+    pub const RST: u16 = 0x004;
+    pub const PSH: u16 = 0x008;
+    pub const ACK: u16 = 0x010;
+    pub const URG: u16 = 0x020;
+    pub const ECE: u16 = 0x040;
+    pub const CWR: u16 = 0x080;
+    pub const NS: u16 = 0x100;
+
+    pub fn src_port(&self) -> u16 {
+        be16(self.bytes, 0)
+    }
+
+    pub fn dst_port(&self) -> u16 {
+        be16(self.bytes, 2)
+    }
+
+    pub fn seq(&self) -> u32 {
+        be32(self.bytes, 4)
+    }
+
+    pub fn ack(&self) -> u32 {
+        be32(self.bytes, 8)
+    }
+
+    /// The header length in 32-bit words.
+    pub fn data_offset(&self) -> u8 {
+        self.bytes[12] >> 4
+    }
+
+    /// The 9 flag bits, see the `FIN`... `NS` constants.
+    pub fn flags(&self) -> u16 {
+        be16(self.bytes, 12) & 0x01ff
+    }
+
+    /// True when all the `flags` bits are set.
+    pub fn has_flags(&self, flags: u16) -> bool {
+        self.flags() & flags == flags
+    }
+
+    pub fn window(&self) -> u16 {
+        be16(self.bytes, 14)
+    }
+
+    pub fn checksum(&self) -> u16 {
+        be16(self.bytes, 16)
+    }
+
+    pub fn urgent_ptr(&self) -> u16 {
+        be16(self.bytes, 18)
+    }
+
+    pub fn options(&self) -> OptionIter<'a> {
+        OptionIter::new(self.bytes, Self::MIN_LEN)
+    }
+
+    /// The maximum segment size option.
+    pub fn mss(&self) -> Option<u16> {
+        self.option(2, 2).map(|data| be16(data, 0))
+    }
+
+    /// The window scale option.
+    pub fn window_scale(&self) -> Option<u8> {
+        self.option(3, 1).map(|data| data[0])
+    }
+
+    pub fn sack_permitted(&self) -> bool {
+        self.option(4, 0).is_some()
+    }
+
+    /// The timestamps option, the value and the echo reply.
+    pub fn timestamps(&self) -> Option<(u32, u32)> {
+        self.option(8, 8).map(|data| (be32(data, 0), be32(data, 4)))
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Verify the checksum over the IPv4 pseudo header, this header and `payload`.
+    pub fn verify_checksum_v4(&self, src: Ipv4Addr, dst: Ipv4Addr, payload: &[u8]) -> bool {
+        match u16::try_from(self.bytes.len() + payload.len()) {
+            Ok(len) => self.verify(pseudo_header_v4(src, dst, ip_proto::TCP, len), payload),
+            Err(_) => false,
+        }
+    }
+
+    /// Verify the checksum over the IPv6 pseudo header, this header and `payload`.
+    pub fn verify_checksum_v6(&self, src: Ipv6Addr, dst: Ipv6Addr, payload: &[u8]) -> bool {
+        let len = (self.bytes.len() + payload.len()) as u32;
+        self.verify(pseudo_header_v6(src, dst, ip_proto::TCP, len), payload)
+    }
+
+    fn verify(&self, mut sum: InternetChecksum, payload: &[u8]) -> bool {
+        sum.update(self.bytes);
+        sum.update(payload);
+        sum.finalize() == 0
+    }
+
+    /// The data of the first well-formed option `kind` with `len` data bytes.
+    fn option(&self, kind: u8, len: usize) -> Option<&'a [u8]> {
+        self.options()
+            .map_while(|option| option.ok())
+            .find(|option| option.kind == kind && option.data.len() == len)
+            .map(|option| option.data)
+    }
+}
+
+impl<'a> Header<'a> for TcpHeader<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let fixed = check_len(buf, start, Self::MIN_LEN)?;
+        let len = (fixed[12] >> 4) as usize * 4;
+        if len < Self::MIN_LEN {
+            return Err(Error::invalid("TCP data offset below 5", buf, start + 12));
+        }
+        let bytes = check_len(buf, start, len)?;
+        Ok(TcpHeader { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Writes a [`TcpHeader`], `finish_v4` or `finish_v6` sets the checksum once the
+/// payload is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TcpBuilder<'a> {
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub seq: u32,
+    pub ack: u32,
+    pub flags: u16,
+    pub window: u16,
+    pub urgent_ptr: u16,
+    /// Padded with zeros (end of list) to a multiple of 4 bytes, 40 bytes at most.
+    pub options: &'a [u8],
+}
+
+impl<'a> TcpBuilder<'a> {
+    /// A header with a window of 65535.
+    pub fn new(src_port: u16, dst_port: u16, seq: u32, flags: u16) -> Self {
+        TcpBuilder {
+            src_port,
+            dst_port,
+            seq,
+            ack: 0,
+            flags,
+            window: 0xffff,
+            urgent_ptr: 0,
+            options: &[],
+        }
+    }
+
+    /// Write the header with a zero checksum and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        assert!(self.options.len() <= 40 && self.flags < 0x200);
+        let padded = self.options.len().div_ceil(4) * 4;
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + TcpHeader::MIN_LEN + padded);
+        let data_offset = ((TcpHeader::MIN_LEN + padded) / 4) as u16;
+        view.write_u16(self.src_port);
+        view.write_u16(self.dst_port);
+        view.write_u32(self.seq);
+        view.write_u32(self.ack);
+        view.write_u16(data_offset << 12 | self.flags);
+        view.write_u16(self.window);
+        view.write_u16(0);
+        view.write_u16(self.urgent_ptr);
+        view.write_bytes(self.options);
+        view.write_bytes(&[0; 3][..padded - self.options.len()]);
+        start
+    }
+
+    /// Set the checksum over the IPv4 pseudo header of the segment from `start` to the
+    /// writer index. Fail with `Overflow` when the segment is over 65535 bytes.
+    pub fn finish_v4(
+        view: &mut BufViewMut,
+        start: usize,
+        src: Ipv4Addr,
+        dst: Ipv4Addr,
+    ) -> Result<()> {
+        let len = u16::try_from(view.writer_index() - start)
+            .map_err(|_| Error::new(ErrorKind::Overflow, view.buf, start))?;
+        Self::set_checksum(view, start, pseudo_header_v4(src, dst, ip_proto::TCP, len));
+        Ok(())
+    }
+
+    /// Set the checksum over the IPv6 pseudo header of the segment from `start` to the
+    /// writer index.
+    pub fn finish_v6(
+        view: &mut BufViewMut,
+        start: usize,
+        src: Ipv6Addr,
+        dst: Ipv6Addr,
+    ) -> Result<()> {
+        let len = u32::try_from(view.writer_index() - start)
+            .map_err(|_| Error::new(ErrorKind::Overflow, view.buf, start))?;
+        Self::set_checksum(view, start, pseudo_header_v6(src, dst, ip_proto::TCP, len));
+        Ok(())
+    }
+
+    fn set_checksum(view: &mut BufViewMut, start: usize, mut sum: InternetChecksum) {
+        view.set_u16(start + 16, 0);
+        sum.update(&view.buf[start..view.writer_index()]);
+        view.set_u16(start + 16, sum.finalize());
+    }
+}
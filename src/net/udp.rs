@@ -0,0 +1,156 @@
+use super::{be16, check_len, ip_proto, pseudo_header_v4, pseudo_header_v6, Header};
+use crate::checksum::{Checksum, InternetChecksum};
+use crate::error::{Error, ErrorKind, Result};
+use crate::BufViewMut;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// A UDP header, 8 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UdpHeader<'a> {
+    bytes: &'a [u8],
+}
+
+impl UdpHeader<'_> {
+    pub const LEN: usize = 8;
+
+    pub fn src_port(&self) -> u16 {
+        be16(self.bytes, 0)
+    }
+
+    pub fn dst_port(&self) -> u16 {
+        be16(self.bytes, 2)
+    }
+
+    /// The length of the header and payload.
+    pub fn length(&self) -> u16 {
+        be16(self.bytes, 4)
+    }
+
+    pub fn payload_len(&self) -> usize {
+        self.length() as usize - Self::LEN
+    }
+
+    pub fn checksum(&self) -> u16 {
+        be16(self.bytes, 6)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    /// Verify the checksum over the IPv4 pseudo header, this header and `payload`,
+    /// which must be `payload_len` bytes. A zero checksum means none and is valid.
+    pub fn verify_checksum_v4(&self, src: Ipv4Addr, dst: Ipv4Addr, payload: &[u8]) -> bool {
+        if self.checksum() == 0 {
+            return true;
+        }
+        let sum = pseudo_header_v4(src, dst, ip_proto::UDP, self.length());
+        self.verify(sum, payload)
+    }
+
+    /// Verify the checksum over the IPv6 pseudo header, this header and `payload`. The
+    /// checksum is mandatory over IPv6, zero is invalid.
+    pub fn verify_checksum_v6(&self, src: Ipv6Addr, dst: Ipv6Addr, payload: &[u8]) -> bool {
+        if self.checksum() == 0 {
+            return false;
+        }
+        let sum = pseudo_header_v6(src, dst, ip_proto::UDP, self.length() as u32);
+        self.verify(sum, payload)
+    }
+
+    fn verify(&self, mut sum: InternetChecksum, payload: &[u8]) -> bool {
+        if payload.len() != self.payload_len() {
+            return false;
+        }
+        sum.update(self.bytes);
+        sum.update(payload);
+        sum.finalize() == 0
+    }
+}
+
+impl<'a> Header<'a> for UdpHeader<'a> {
+    fn parse_at(buf: &'a [u8], start: usize) -> Result<Self> {
+        let bytes = check_len(buf, start, Self::LEN)?;
+        if (be16(bytes, 4) as usize) < Self::LEN {
+            return Err(Error::invalid("UDP length below 8", buf, start + 4));
+        }
+        Ok(UdpHeader { bytes })
+    }
+
+    fn header_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Writes a [`UdpHeader`], `finish_v4` or `finish_v6` sets the length and checksum
+/// once the payload is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UdpBuilder {
+    pub src_port: u16,
+    pub dst_port: u16,
+}
+
+impl UdpBuilder {
+    pub fn new(src_port: u16, dst_port: u16) -> Self {
+        UdpBuilder { src_port, dst_port }
+    }
+
+    /// Write the header with a zero length and checksum, and return its start.
+    pub fn write(&self, view: &mut BufViewMut) -> usize {
+        let start = view.writer_index();
+        assert!(view.capacity() >= start + UdpHeader::LEN);
+        view.write_u16(self.src_port);
+        view.write_u16(self.dst_port);
+        view.write_u32(0);
+        start
+    }
+
+    /// Set the length up to the writer index and the checksum over the IPv4 pseudo
+    /// header of the header written at `start`. Fail with `Overflow` when the datagram
+    /// is over 65535 bytes.
+    pub fn finish_v4(
+        view: &mut BufViewMut,
+        start: usize,
+        src: Ipv4Addr,
+        dst: Ipv4Addr,
+    ) -> Result<()> {
+        let len = Self::set_length(view, start)?;
+        Self::set_checksum(view, start, pseudo_header_v4(src, dst, ip_proto::UDP, len));
+        Ok(())
+    }
+
+    /// Set the length up to the writer index and the checksum over the IPv6 pseudo
+    /// header of the header written at `start`.
+    pub fn finish_v6(
+        view: &mut BufViewMut,
+        start: usize,
+        src: Ipv6Addr,
+        dst: Ipv6Addr,
+    ) -> Result<()> {
+        let len = Self::set_length(view, start)?;
+        Self::set_checksum(
+            view,
+            start,
+            pseudo_header_v6(src, dst, ip_proto::UDP, len as u32),
+        );
+        Ok(())
+    }
+
+    fn set_length(view: &mut BufViewMut, start: usize) -> Result<u16> {
+        let len = u16::try_from(view.writer_index() - start)
+            .map_err(|_| Error::new(ErrorKind::Overflow, view.buf, start + 4))?;
+        view.set_u16(start + 4, len);
+        Ok(len)
+    }
+
+    fn set_checksum(view: &mut BufViewMut, start: usize, mut sum: InternetChecksum) {
+        view.set_u16(start + 6, 0);
+        sum.update(&view.buf[start..view.writer_index()]);
+        // a computed zero is sent as all ones, zero means no checksum
+        let sum = match sum.finalize() {
+            0 => 0xffff,
+            sum => sum,
+        };
+        view.set_u16(start + 6, sum);
+    }
+}
@@ -0,0 +1,256 @@
+//! Classic pcap and pcapng capture files, behind the `pcap` feature.
+//!
+//! Readers take a [`BufView`] over the whole file and yield the packets as views into
+//! it, without copies. The byte order of a file is detected from its magic, classic
+//! files with the nanosecond magic have nanosecond timestamps. Errors have absolute
+//! offsets in the wrapped buffer, a truncated or malformed record yields one error and
+//! ends the iteration.
+//!
+//! Writers append to a [`BufViewMut`] in the byte order they are
+//! configured with. The pcapng format lives in [`ng`].
+//!
+//! Example
+//! ```
+//! use buf_view::pcap::{link_type, PcapHeader, PcapReader};
+//! use buf_view::{BufView, BufViewMut};
+//! use std::time::Duration;
+//!
+//! let mut buf = [0u8; 64];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let header = PcapHeader::new(link_type::ETHERNET);
+//! header.write(&mut buf_view).unwrap();
+//! header
+//!     .write_packet(&mut buf_view, Duration::new(1, 500_000), 60, b"packet")
+//!     .unwrap();
+//! let len = buf_view.writer_index();
+//!
+//! let mut reader = PcapReader::new(BufView::wrap(&buf[..len])).unwrap();
+//! assert_eq!(reader.header().link_type, link_type::ETHERNET);
+//! let mut packet = reader.next().unwrap().unwrap();
+//! assert_eq!(packet.timestamp(), Duration::new(1, 500_000));
+//! assert_eq!(packet.orig_len, 60);
+//! assert_eq!(packet.data.read_u16(), 0x7061);
+//! assert!(reader.next().is_none());
+//! ```
+
+pub mod ng;
+
+use crate::endian::Endianness;
+use crate::error::{Error, ErrorKind, Result};
+use crate::{BufView, BufViewMut};
+use std::time::Duration;
+
+/// Link-layer header types of the pcap `network` and pcapng `LinkType` fields.
+pub mod link_type {
+    pub const NULL: u32 = 0;
+    pub const ETHERNET: u32 = 1;
+    pub const RAW: u32 = 101;
+    pub const LINUX_SLL: u32 = 113;
+    pub const IEEE802_11: u32 = 105;
+    pub const IPV4: u32 = 228;
+    pub const IPV6: u32 = 229;
+    pub const LINUX_SLL2: u32 = 276;
+}
+
+const MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+
+/// The resolution of the fractional part of classic pcap timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TsResolution {
+    Micros,
+    Nanos,
+}
+
+/// The global header of a classic pcap file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcapHeader {
+    pub endianness: Endianness,
+    pub resolution: TsResolution,
+    pub version_major: u16,
+    pub version_minor: u16,
+    /// The GMT to local correction, 0 in practice.
+    pub thiszone: i32,
+    /// The accuracy of the timestamps, 0 in practice.
+    pub sigfigs: u32,
+    pub snaplen: u32,
+    pub link_type: u32,
+}
+
+impl PcapHeader {
+    /// A version 2.4 little endian header with microsecond timestamps and a snapshot
+    /// length of 262144.
+    pub fn new(link_type: u32) -> Self {
+        PcapHeader {
+            endianness: Endianness::Little,
+            resolution: TsResolution::Micros,
+            version_major: 2,
+            version_minor: 4,
+            thiszone: 0,
+            sigfigs: 0,
+            snaplen: 262_144,
+            link_type,
+        }
+    }
+
+    /// Parse the 24 bytes global header at the reader index and consume it.
+    pub fn read(view: &mut BufView) -> Result<Self> {
+        let start = view.reader_index();
+        view.check_read(24)?;
+        let magic = view.get_endian::<u32>(start, Endianness::Big);
+        let (endianness, resolution) = match (magic, magic.swap_bytes()) {
+            (MAGIC_MICROS, _) => (Endianness::Big, TsResolution::Micros),
+            (MAGIC_NANOS, _) => (Endianness::Big, TsResolution::Nanos),
+            (_, MAGIC_MICROS) => (Endianness::Little, TsResolution::Micros),
+            (_, MAGIC_NANOS) => (Endianness::Little, TsResolution::Nanos),
+            _ => {
+                return Err(Error::invalid("bad pcap magic", view.buf, start));
+            }
+        };
+        view.set_reader_index(start + 4);
+        Ok(PcapHeader {
+            endianness,
+            resolution,
+            version_major: view.read_endian(endianness),
+            version_minor: view.read_endian(endianness),
+            thiszone: view.read_endian(endianness),
+            sigfigs: view.read_endian(endianness),
+            snaplen: view.read_endian(endianness),
+            link_type: view.read_endian(endianness),
+        })
+    }
+
+    /// Write the global header at the writer index.
+    pub fn write(&self, view: &mut BufViewMut) -> Result<()> {
+        let e = self.endianness;
+        let magic = match self.resolution {
+            TsResolution::Micros => MAGIC_MICROS,
+            TsResolution::Nanos => MAGIC_NANOS,
+        };
+        view.check_get(view.writer_index(), 24)?;
+        view.write_endian(magic, e);
+        view.write_endian(self.version_major, e);
+        view.write_endian(self.version_minor, e);
+        view.write_endian(self.thiszone, e);
+        view.write_endian(self.sigfigs, e);
+        view.write_endian(self.snaplen, e);
+        view.write_endian(self.link_type, e);
+        Ok(())
+    }
+
+    /// Write a packet record at the writer index, `timestamp` is since the Unix epoch
+    /// and `orig_len` is the length of the packet on the wire.
+    ///
+    /// Fails with [`ErrorKind::Overflow`] when the timestamp seconds do not fit in 32
+    /// bits.
+    pub fn write_packet(
+        &self,
+        view: &mut BufViewMut,
+        timestamp: Duration,
+        orig_len: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        let e = self.endianness;
+        let start = view.writer_index();
+        let ts_sec = match u32::try_from(timestamp.as_secs()) {
+            Ok(secs) => secs,
+            Err(_) => return Err(Error::new(ErrorKind::Overflow, view.buf, start)),
+        };
+        let ts_frac = match self.resolution {
+            TsResolution::Micros => timestamp.subsec_micros(),
+            TsResolution::Nanos => timestamp.subsec_nanos(),
+        };
+        let incl_len = match u32::try_from(data.len()) {
+            Ok(len) => len,
+            Err(_) => return Err(Error::new(ErrorKind::Overflow, view.buf, start)),
+        };
+        view.check_get(start, 16 + data.len())?;
+        view.write_endian(ts_sec, e);
+        view.write_endian(ts_frac, e);
+        view.write_endian(incl_len, e);
+        view.write_endian(orig_len, e);
+        view.write_bytes(data);
+        Ok(())
+    }
+}
+
+/// A packet record of a classic pcap file.
+#[derive(Debug)]
+pub struct PcapPacket<'a> {
+    pub ts_sec: u32,
+    /// Microseconds or nanoseconds, see [`PcapHeader::resolution`].
+    pub ts_frac: u32,
+    /// The length of the packet on the wire, the captured data may be shorter.
+    pub orig_len: u32,
+    /// The captured data, a view with absolute indexes in the file buffer.
+    pub data: BufView<'a>,
+    resolution: TsResolution,
+}
+
+impl PcapPacket<'_> {
+    /// The timestamp since the Unix epoch.
+    pub fn timestamp(&self) -> Duration {
+        let nanos = match self.resolution {
+            TsResolution::Micros => self.ts_frac as u64 * 1000,
+            TsResolution::Nanos => self.ts_frac as u64,
+        };
+        Duration::from_secs(self.ts_sec as u64) + Duration::from_nanos(nanos)
+    }
+}
+
+/// An iterator over the packets of a classic pcap file.
+#[derive(Debug)]
+pub struct PcapReader<'a> {
+    view: BufView<'a>,
+    header: PcapHeader,
+    done: bool,
+}
+
+impl<'a> PcapReader<'a> {
+    /// Parse the global header at the reader index of `view`, the packets follow it up
+    /// to the writer index.
+    pub fn new(mut view: BufView<'a>) -> Result<Self> {
+        let header = PcapHeader::read(&mut view)?;
+        Ok(PcapReader {
+            view,
+            header,
+            done: false,
+        })
+    }
+
+    pub fn header(&self) -> &PcapHeader {
+        &self.header
+    }
+
+    fn read_packet(&mut self) -> Result<PcapPacket<'a>> {
+        let e = self.header.endianness;
+        self.view.check_read(16)?;
+        let ts_sec = self.view.read_endian(e);
+        let ts_frac = self.view.read_endian(e);
+        let incl_len: u32 = self.view.read_endian(e);
+        let orig_len = self.view.read_endian(e);
+        let data = self.view.try_read_view(incl_len as usize)?;
+        Ok(PcapPacket {
+            ts_sec,
+            ts_frac,
+            orig_len,
+            data,
+            resolution: self.header.resolution,
+        })
+    }
+}
+
+impl<'a> Iterator for PcapReader<'a> {
+    type Item = Result<PcapPacket<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.view.remaining() == 0 {
+            return None;
+        }
+        let packet = self.read_packet();
+        self.done = packet.is_err();
+        Some(packet)
+    }
+}
+
+impl std::iter::FusedIterator for PcapReader<'_> {}
@@ -0,0 +1,580 @@
+//! The pcapng format: section headers, interface descriptions, enhanced and simple
+//! packet blocks with their options.
+//!
+//! Each section header block sets the byte order of the blocks after it and resets
+//! the interface list. Options are validated when their block is read.
+//!
+//! Example
+//! ```
+//! use buf_view::pcap::link_type;
+//! use buf_view::pcap::ng::{opt, Block, PcapNgReader, PcapNgWriter};
+//! use buf_view::{BufView, BufViewMut, Endianness};
+//! use std::time::Duration;
+//!
+//! let mut buf = [0u8; 128];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let writer = PcapNgWriter::new(Endianness::Big);
+//! writer.write_section_header(&mut buf_view, &[(opt::SHB_USERAPPL, b"test")]).unwrap();
+//! writer.write_interface(&mut buf_view, link_type::ETHERNET as u16, 0, &[]).unwrap();
+//! writer
+//!     .write_enhanced_packet(&mut buf_view, 0, 1_500_000, 5, b"hello", &[])
+//!     .unwrap();
+//! let len = buf_view.writer_index();
+//!
+//! let mut reader = PcapNgReader::new(BufView::wrap(&buf[..len]));
+//! match reader.next() {
+//!     Some(Ok(Block::Section(shb))) => {
+//!         assert_eq!(shb.endianness, Endianness::Big);
+//!         let userappl = shb.options.find(opt::SHB_USERAPPL).unwrap();
+//!         assert_eq!(userappl.as_str(), Some("test"));
+//!     }
+//!     _ => unreachable!(),
+//! }
+//! assert!(matches!(reader.next(), Some(Ok(Block::Interface(_)))));
+//! match reader.next() {
+//!     Some(Ok(Block::EnhancedPacket(mut epb))) => {
+//!         assert_eq!(epb.timestamp(), Duration::from_millis(1500));
+//!         assert_eq!(epb.data.remaining(), 5);
+//!         assert_eq!(epb.data.read_u8(), b'h');
+//!     }
+//!     _ => unreachable!(),
+//! }
+//! assert!(reader.next().is_none());
+//! ```
+
+use crate::endian::Endianness;
+use crate::error::{Error, ErrorKind, Result};
+use crate::{BufView, BufViewMut};
+use std::time::Duration;
+
+/// Block types.
+pub mod block_type {
+    pub const SECTION_HEADER: u32 = 0x0a0d_0d0a;
+    pub const INTERFACE_DESCRIPTION: u32 = 1;
+    pub const SIMPLE_PACKET: u32 = 3;
+    pub const NAME_RESOLUTION: u32 = 4;
+    pub const INTERFACE_STATISTICS: u32 = 5;
+    pub const ENHANCED_PACKET: u32 = 6;
+}
+
+/// Option codes, the meaning of a code depends on the block type.
+pub mod opt {
+    pub const END_OF_OPT: u16 = 0;
+    pub const COMMENT: u16 = 1;
+    pub const SHB_HARDWARE: u16 = 2;
+    pub const SHB_OS: u16 = 3;
+    pub const SHB_USERAPPL: u16 = 4;
+    pub const IF_NAME: u16 = 2;
+    pub const IF_DESCRIPTION: u16 = 3;
+    pub const IF_TSRESOL: u16 = 9;
+    pub const EPB_FLAGS: u16 = 2;
+}
+
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b_3c4d;
+
+/// The default `if_tsresol`, microseconds.
+const DEFAULT_TSRESOL: u8 = 6;
+
+/// An option of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NgOption<'a> {
+    pub code: u16,
+    /// The value, without the padding.
+    pub value: &'a [u8],
+    endianness: Endianness,
+}
+
+impl<'a> NgOption<'a> {
+    pub fn as_u8(&self) -> Option<u8> {
+        match self.value {
+            [b] => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(&self) -> Option<u32> {
+        (self.value.len() == 4).then(|| self.endianness.decode(self.value))
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        (self.value.len() == 8).then(|| self.endianness.decode(self.value))
+    }
+
+    /// The UTF-8 value of a string option.
+    pub fn as_str(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.value).ok()
+    }
+}
+
+/// The validated options of a block, iterated up to the end of options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options<'a> {
+    bytes: &'a [u8],
+    endianness: Endianness,
+}
+
+impl<'a> Options<'a> {
+    /// Validate the options from the reader index to the writer index of `view`.
+    fn read(view: &mut BufView<'a>, endianness: Endianness) -> Result<Self> {
+        let start = view.reader_index();
+        while view.remaining() > 0 {
+            let code: u16 = view.try_read_endian(endianness)?;
+            let len: u16 = view.try_read_endian(endianness)?;
+            if code == opt::END_OF_OPT {
+                break;
+            }
+            let len = len as usize;
+            view.check_read(len.next_multiple_of(4))?;
+            view.set_reader_index(view.reader_index() + len.next_multiple_of(4));
+        }
+        let bytes = &view.buf[start..view.reader_index()];
+        view.set_reader_index(view.writer_index());
+        Ok(Options { bytes, endianness })
+    }
+
+    /// The first option with `code`.
+    pub fn find(&self, code: u16) -> Option<NgOption<'a>> {
+        self.into_iter().find(|o| o.code == code)
+    }
+}
+
+impl<'a> IntoIterator for Options<'a> {
+    type Item = NgOption<'a>;
+    type IntoIter = OptionIter<'a>;
+
+    fn into_iter(self) -> OptionIter<'a> {
+        OptionIter { options: self }
+    }
+}
+
+impl<'a> IntoIterator for &Options<'a> {
+    type Item = NgOption<'a>;
+    type IntoIter = OptionIter<'a>;
+
+    fn into_iter(self) -> OptionIter<'a> {
+        OptionIter { options: *self }
+    }
+}
+
+/// An iterator over the [`Options`] of a block.
+#[derive(Debug, Clone)]
+pub struct OptionIter<'a> {
+    options: Options<'a>,
+}
+
+impl<'a> Iterator for OptionIter<'a> {
+    type Item = NgOption<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let e = self.options.endianness;
+        let bytes = self.options.bytes;
+        if bytes.len() < 4 {
+            return None;
+        }
+        let code: u16 = e.decode(&bytes[..2]);
+        let len = e.decode::<u16>(&bytes[2..4]) as usize;
+        if code == opt::END_OF_OPT {
+            return None;
+        }
+        let value = &bytes[4..4 + len];
+        self.options.bytes = &bytes[4 + len.next_multiple_of(4)..];
+        Some(NgOption {
+            code,
+            value,
+            endianness: e,
+        })
+    }
+}
+
+/// A section header block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionHeader<'a> {
+    /// The byte order of the section, detected from the byte-order magic.
+    pub endianness: Endianness,
+    pub version_major: u16,
+    pub version_minor: u16,
+    /// The length of the section after this block, -1 when unknown.
+    pub section_len: i64,
+    pub options: Options<'a>,
+}
+
+/// An interface description block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interface<'a> {
+    pub link_type: u16,
+    /// The maximum captured length, 0 when unlimited.
+    pub snaplen: u32,
+    /// The `if_tsresol` option, 6 (microseconds) when absent.
+    pub tsresol: u8,
+    pub options: Options<'a>,
+}
+
+/// An enhanced packet block.
+#[derive(Debug)]
+pub struct EnhancedPacket<'a> {
+    pub interface_id: u32,
+    /// The timestamp in units of the interface `if_tsresol`.
+    pub timestamp_raw: u64,
+    /// The `if_tsresol` of the interface.
+    pub tsresol: u8,
+    /// The length of the packet on the wire, the captured data may be shorter.
+    pub orig_len: u32,
+    /// The captured data, a view with absolute indexes in the file buffer.
+    pub data: BufView<'a>,
+    pub options: Options<'a>,
+}
+
+impl EnhancedPacket<'_> {
+    /// The timestamp since the Unix epoch, rounded down to the nanosecond.
+    pub fn timestamp(&self) -> Duration {
+        let raw = self.timestamp_raw as u128;
+        let units = if self.tsresol & 0x80 == 0 {
+            10u128.checked_pow(self.tsresol as u32)
+        } else {
+            Some(1u128 << (self.tsresol & 0x7f))
+        };
+        match units {
+            // the raw timestamp is a u64, the products fit in a u128
+            Some(units) => {
+                let nanos = (raw % units) * 1_000_000_000 / units;
+                Duration::new((raw / units) as u64, nanos as u32)
+            }
+            None => Duration::ZERO,
+        }
+    }
+}
+
+/// A simple packet block, its packet belongs to the first interface.
+#[derive(Debug)]
+pub struct SimplePacket<'a> {
+    pub orig_len: u32,
+    /// The captured data, a view with absolute indexes in the file buffer.
+    pub data: BufView<'a>,
+}
+
+/// A pcapng block.
+#[derive(Debug)]
+pub enum Block<'a> {
+    Section(SectionHeader<'a>),
+    Interface(Interface<'a>),
+    EnhancedPacket(EnhancedPacket<'a>),
+    SimplePacket(SimplePacket<'a>),
+    /// Another block type, its body between the lengths.
+    Other {
+        block_type: u32,
+        body: BufView<'a>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct InterfaceInfo {
+    snaplen: u32,
+    tsresol: u8,
+}
+
+/// An iterator over the blocks of a pcapng file.
+#[derive(Debug)]
+pub struct PcapNgReader<'a> {
+    view: BufView<'a>,
+    endianness: Option<Endianness>,
+    interfaces: Vec<InterfaceInfo>,
+    done: bool,
+}
+
+impl<'a> PcapNgReader<'a> {
+    /// Read the blocks from the reader index to the writer index of `view`, the first
+    /// one must be a section header.
+    pub fn new(view: BufView<'a>) -> Self {
+        PcapNgReader {
+            view,
+            endianness: None,
+            interfaces: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// The byte order of the current section.
+    pub fn endianness(&self) -> Option<Endianness> {
+        self.endianness
+    }
+
+    fn interface(&self, id: u32, buf: &[u8], offset: usize) -> Result<InterfaceInfo> {
+        match self.interfaces.get(id as usize) {
+            Some(info) => Ok(*info),
+            None => Err(Error::invalid(
+                "pcapng packet for an unknown interface",
+                buf,
+                offset,
+            )),
+        }
+    }
+
+    fn read_block(&mut self) -> Result<Block<'a>> {
+        let view = &mut self.view;
+        let buf = view.buf;
+        let start = view.reader_index();
+        view.check_read(12)?;
+        if view.get_endian::<u32>(start, Endianness::Big) == block_type::SECTION_HEADER {
+            self.endianness = match view.get_endian::<u32>(start + 8, Endianness::Big) {
+                BYTE_ORDER_MAGIC => Some(Endianness::Big),
+                magic if magic.swap_bytes() == BYTE_ORDER_MAGIC => Some(Endianness::Little),
+                _ => {
+                    return Err(Error::invalid(
+                        "bad pcapng byte-order magic",
+                        buf,
+                        start + 8,
+                    ))
+                }
+            };
+            self.interfaces.clear();
+        }
+        let e = match self.endianness {
+            Some(e) => e,
+            None => {
+                return Err(Error::invalid(
+                    "pcapng block before the section header",
+                    buf,
+                    start,
+                ))
+            }
+        };
+        let block_type = view.get_endian::<u32>(start, e);
+        let total_len = view.get_endian::<u32>(start + 4, e) as usize;
+        if total_len < 12 || !total_len.is_multiple_of(4) {
+            return Err(Error::invalid("bad pcapng block length", buf, start + 4));
+        }
+        let end = view.check_read(total_len)?;
+        if view.get_endian::<u32>(end - 4, e) as usize != total_len {
+            return Err(Error::invalid("bad pcapng block length", buf, end - 4));
+        }
+        view.set_reader_index(end);
+        let mut body = BufView::wrap_with(buf, start + 8, end - 4);
+
+        let block = match block_type {
+            block_type::SECTION_HEADER => {
+                body.check_read(16)?;
+                body.set_reader_index(start + 12);
+                Block::Section(SectionHeader {
+                    endianness: e,
+                    version_major: body.read_endian(e),
+                    version_minor: body.read_endian(e),
+                    section_len: body.read_endian(e),
+                    options: Options::read(&mut body, e)?,
+                })
+            }
+            block_type::INTERFACE_DESCRIPTION => {
+                body.check_read(8)?;
+                let link_type = body.read_endian(e);
+                let _reserved: u16 = body.read_endian(e);
+                let snaplen = body.read_endian(e);
+                let options = Options::read(&mut body, e)?;
+                let tsresol = options
+                    .find(opt::IF_TSRESOL)
+                    .and_then(|o| o.value.first().copied())
+                    .unwrap_or(DEFAULT_TSRESOL);
+                self.interfaces.push(InterfaceInfo { snaplen, tsresol });
+                Block::Interface(Interface {
+                    link_type,
+                    snaplen,
+                    tsresol,
+                    options,
+                })
+            }
+            block_type::ENHANCED_PACKET => {
+                body.check_read(20)?;
+                let interface_id = body.read_endian(e);
+                let info = self.interface(interface_id, buf, start + 8)?;
+                let ts_high: u32 = body.read_endian(e);
+                let ts_low: u32 = body.read_endian(e);
+                let captured_len: u32 = body.read_endian(e);
+                let orig_len = body.read_endian(e);
+                let data = body.try_read_view(captured_len as usize)?;
+                let padding = (captured_len as usize).next_multiple_of(4) - captured_len as usize;
+                body.try_read_view(padding)?;
+                Block::EnhancedPacket(EnhancedPacket {
+                    interface_id,
+                    timestamp_raw: ((ts_high as u64) << 32) | ts_low as u64,
+                    tsresol: info.tsresol,
+                    orig_len,
+                    data,
+                    options: Options::read(&mut body, e)?,
+                })
+            }
+            block_type::SIMPLE_PACKET => {
+                let info = self.interface(0, buf, start)?;
+                let orig_len: u32 = body.try_read_endian(e)?;
+                let mut captured_len = (orig_len as usize).min(body.remaining());
+                if info.snaplen != 0 {
+                    captured_len = captured_len.min(info.snaplen as usize);
+                }
+                Block::SimplePacket(SimplePacket {
+                    orig_len,
+                    data: body.read_view(captured_len),
+                })
+            }
+            _ => Block::Other { block_type, body },
+        };
+        Ok(block)
+    }
+}
+
+impl<'a> Iterator for PcapNgReader<'a> {
+    type Item = Result<Block<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.view.remaining() == 0 {
+            return None;
+        }
+        let block = self.read_block();
+        self.done = block.is_err();
+        Some(block)
+    }
+}
+
+impl std::iter::FusedIterator for PcapNgReader<'_> {}
+
+/// Writes pcapng blocks at the writer index of a [`BufViewMut`].
+///
+/// Options are `(code, value)` pairs, the values are padded and the end of options is
+/// appended when there are any. The block lengths are back-patched, a failed write resets
+/// the writer index to the block start so later blocks can still be written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcapNgWriter {
+    pub endianness: Endianness,
+}
+
+impl PcapNgWriter {
+    pub fn new(endianness: Endianness) -> Self {
+        PcapNgWriter { endianness }
+    }
+
+    /// Write a block of `block_type` with the body written by `body`, or nothing when
+    /// it fails.
+    fn write_block<'b>(
+        &self,
+        view: &mut BufViewMut<'b>,
+        block_type: u32,
+        body: impl FnOnce(&mut BufViewMut<'b>) -> Result<()>,
+    ) -> Result<()> {
+        let start = view.writer_index();
+        let result = view
+            .try_write_endian(block_type, self.endianness)
+            .and_then(|_| view.try_write_endian(0u32, self.endianness))
+            .and_then(|_| body(view))
+            .and_then(|_| self.end_block(view, start));
+        if result.is_err() {
+            view.set_writer_index(start);
+        }
+        result
+    }
+
+    fn write_padded(&self, view: &mut BufViewMut, bytes: &[u8]) -> Result<()> {
+        view.try_write_bytes(bytes)?;
+        view.try_write_bytes(&[0; 3][..bytes.len().next_multiple_of(4) - bytes.len()])
+    }
+
+    fn write_options(&self, view: &mut BufViewMut, options: &[(u16, &[u8])]) -> Result<()> {
+        for (code, value) in options {
+            let len = match u16::try_from(value.len()) {
+                Ok(len) => len,
+                Err(_) => {
+                    return Err(Error::new(
+                        ErrorKind::Overflow,
+                        view.buf,
+                        view.writer_index(),
+                    ))
+                }
+            };
+            view.try_write_endian(*code, self.endianness)?;
+            view.try_write_endian(len, self.endianness)?;
+            self.write_padded(view, value)?;
+        }
+        if !options.is_empty() {
+            view.try_write_endian(0u32, self.endianness)?;
+        }
+        Ok(())
+    }
+
+    fn end_block(&self, view: &mut BufViewMut, start: usize) -> Result<()> {
+        let total_len = view.writer_index() + 4 - start;
+        let total_len = match u32::try_from(total_len) {
+            Ok(len) => len,
+            Err(_) => return Err(Error::new(ErrorKind::Overflow, view.buf, start + 4)),
+        };
+        view.try_write_endian(total_len, self.endianness)?;
+        view.set_endian(start + 4, total_len, self.endianness);
+        Ok(())
+    }
+
+    /// Write a version 1.0 section header of unknown length.
+    pub fn write_section_header(
+        &self,
+        view: &mut BufViewMut,
+        options: &[(u16, &[u8])],
+    ) -> Result<()> {
+        self.write_block(view, block_type::SECTION_HEADER, |view| {
+            view.try_write_endian(BYTE_ORDER_MAGIC, self.endianness)?;
+            view.try_write_endian(1u16, self.endianness)?;
+            view.try_write_endian(0u16, self.endianness)?;
+            view.try_write_endian(-1i64, self.endianness)?;
+            self.write_options(view, options)
+        })
+    }
+
+    /// Write an interface description, a `snaplen` of 0 means unlimited.
+    pub fn write_interface(
+        &self,
+        view: &mut BufViewMut,
+        link_type: u16,
+        snaplen: u32,
+        options: &[(u16, &[u8])],
+    ) -> Result<()> {
+        self.write_block(view, block_type::INTERFACE_DESCRIPTION, |view| {
+            view.try_write_endian(link_type, self.endianness)?;
+            view.try_write_endian(0u16, self.endianness)?;
+            view.try_write_endian(snaplen, self.endianness)?;
+            self.write_options(view, options)
+        })
+    }
+
+    /// Write an enhanced packet, `timestamp_raw` is in units of the interface
+    /// `if_tsresol`, microseconds by default.
+    pub fn write_enhanced_packet(
+        &self,
+        view: &mut BufViewMut,
+        interface_id: u32,
+        timestamp_raw: u64,
+        orig_len: u32,
+        data: &[u8],
+        options: &[(u16, &[u8])],
+    ) -> Result<()> {
+        let start = view.writer_index();
+        let captured_len = match u32::try_from(data.len()) {
+            Ok(len) => len,
+            Err(_) => return Err(Error::new(ErrorKind::Overflow, view.buf, start)),
+        };
+        self.write_block(view, block_type::ENHANCED_PACKET, |view| {
+            view.try_write_endian(interface_id, self.endianness)?;
+            view.try_write_endian((timestamp_raw >> 32) as u32, self.endianness)?;
+            view.try_write_endian(timestamp_raw as u32, self.endianness)?;
+            view.try_write_endian(captured_len, self.endianness)?;
+            view.try_write_endian(orig_len, self.endianness)?;
+            self.write_padded(view, data)?;
+            self.write_options(view, options)
+        })
+    }
+
+    /// Write a simple packet, `data` must already be truncated to the snapshot length of
+    /// the first interface, the writer does not truncate it.
+    pub fn write_simple_packet(
+        &self,
+        view: &mut BufViewMut,
+        orig_len: u32,
+        data: &[u8],
+    ) -> Result<()> {
+        self.write_block(view, block_type::SIMPLE_PACKET, |view| {
+            view.try_write_endian(orig_len, self.endianness)?;
+            self.write_padded(view, data)
+        })
+    }
+}
@@ -0,0 +1,52 @@
+/// A primitive number type with a fixed size byte representation.
+///
+/// Primitive is implemented for every integer and float type, it lets the generic
+/// methods decode and encode any of them in both byte orders.
+pub trait Primitive: Copy + Sized + 'static {
+    /// `std::mem::size_of::<Self>()`.
+    const SIZE: usize;
+
+    /// Decode from exactly `SIZE` big endian bytes.
+    fn from_be_slice(bytes: &[u8]) -> Self;
+
+    /// Decode from exactly `SIZE` little endian bytes.
+    fn from_le_slice(bytes: &[u8]) -> Self;
+
+    /// Encode to exactly `SIZE` big endian bytes.
+    fn to_be_slice(self, dest: &mut [u8]);
+
+    /// Encode to exactly `SIZE` little endian bytes.
+    fn to_le_slice(self, dest: &mut [u8]);
+}
+
+macro_rules! primitive_impl {
+    ($($typ:ty),*) => {
+        $(
+            impl Primitive for $typ {
+                const SIZE: usize = std::mem::size_of::<$typ>();
+
+                #[inline]
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    <$typ>::from_be_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline]
+                fn from_le_slice(bytes: &[u8]) -> Self {
+                    <$typ>::from_le_bytes(bytes.try_into().unwrap())
+                }
+
+                #[inline]
+                fn to_be_slice(self, dest: &mut [u8]) {
+                    dest.copy_from_slice(&self.to_be_bytes());
+                }
+
+                #[inline]
+                fn to_le_slice(self, dest: &mut [u8]) {
+                    dest.copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+primitive_impl!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
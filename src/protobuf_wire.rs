@@ -0,0 +1,584 @@
+//! The Protocol Buffers wire format, behind the `protobuf_wire` feature.
+//!
+//! A [`FieldIter`] reads the fields of a message without a schema, the caller picks
+//! the scalar type of each field number with the [`Field`] accessors. Embedded
+//! messages, strings and packed repeated fields are borrowed as views in the wrapped
+//! buffer. Error offsets are absolute in the wrapped buffer.
+//!
+//! A [`ProtobufWriter`] writes fields at the writer index of a [`BufViewMut`], the
+//! length of an embedded message is back-patched.
+//!
+//! Example
+//! ```
+//! use buf_view::protobuf_wire::ProtobufWriter;
+//! use buf_view::{BufView, BufViewMut};
+//!
+//! let mut buf = [0u8; 64];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! let mut writer = ProtobufWriter::new(&mut buf_view);
+//! writer.write_varint(1, 150).unwrap();
+//! let inner = writer.begin(2).unwrap();
+//! writer.write_str(1, "hi").unwrap();
+//! writer.write_packed_varints(4, &[3, 270]).unwrap();
+//! writer.end(inner).unwrap();
+//! let len = buf_view.writer_index();
+//! assert_eq!(&buf[..5], &[0x08, 0x96, 0x01, 0x12, 9]);
+//!
+//! let buf_view = BufView::wrap(&buf[..len]);
+//! let mut fields = buf_view.protobuf_fields();
+//! assert_eq!(fields.next().unwrap().unwrap().as_u64(), Ok(150));
+//! let mut inner = fields.next().unwrap().unwrap().as_message().unwrap();
+//! assert!(fields.next().is_none());
+//! let name = inner.next().unwrap().unwrap();
+//! assert_eq!((name.number, name.as_str()), (1, Ok("hi")));
+//! let packed = inner.next().unwrap().unwrap().packed_varints().unwrap();
+//! assert_eq!(packed.collect::<Result<Vec<_>, _>>(), Ok(vec![3, 270]));
+//! ```
+
+use crate::array_iter::ArrayIter;
+use crate::endian::{Endianness, LittleEndian};
+use crate::error::{Error, ErrorKind, Result};
+use crate::primitive::Primitive;
+use crate::{BufView, BufViewMut};
+
+/// The largest field number.
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// The maximum nesting of groups.
+pub const MAX_DEPTH: usize = 64;
+
+/// The encoding of a field value, the low 3 bits of its key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WireType {
+    Varint,
+    I64,
+    Len,
+    StartGroup,
+    EndGroup,
+    I32,
+}
+
+impl WireType {
+    fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0 => Some(WireType::Varint),
+            1 => Some(WireType::I64),
+            2 => Some(WireType::Len),
+            3 => Some(WireType::StartGroup),
+            4 => Some(WireType::EndGroup),
+            5 => Some(WireType::I32),
+            _ => None,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+/// The size of `val` as a varint.
+pub fn varint_len(val: u64) -> usize {
+    (64 - (val | 1).leading_zeros() as usize).div_ceil(7)
+}
+
+fn encode_varint(mut val: u64, dest: &mut [u8; 10]) -> usize {
+    let mut len = 0;
+    while val >= 0x80 {
+        dest[len] = val as u8 | 0x80;
+        val >>= 7;
+        len += 1;
+    }
+    dest[len] = val as u8;
+    len + 1
+}
+
+fn zigzag(val: i64) -> u64 {
+    ((val << 1) ^ (val >> 63)) as u64
+}
+
+fn unzigzag(val: u64) -> i64 {
+    (val >> 1) as i64 ^ -((val & 1) as i64)
+}
+
+impl<'a> BufView<'a> {
+    /// Read a base 128 varint of up to 10 bytes, a longer one is an overflow. The
+    /// reader index does not move on error.
+    pub fn try_read_varint(&mut self) -> Result<u64> {
+        let start = self.reader_index;
+        let mut val = 0u64;
+        let mut shift = 0;
+        loop {
+            let b = match self.try_read_u8() {
+                Ok(b) => b,
+                Err(e) => {
+                    self.reader_index = start;
+                    return Err(e);
+                }
+            };
+            // the 10th byte holds the last bit
+            if shift == 63 && b > 1 {
+                self.reader_index = start;
+                return Err(Error::new(ErrorKind::Overflow, self.buf, start));
+            }
+            val |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 {
+                return Ok(val);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Iterate the protobuf fields from the reader index to the writer index, the
+    /// indexes do not move.
+    pub fn protobuf_fields(&self) -> FieldIter<'a> {
+        let view = BufView::wrap_with(self.buf, self.reader_index, self.writer_index);
+        FieldIter::new(view)
+    }
+}
+
+impl BufViewMut<'_> {
+    /// Write `val` as a base 128 varint.
+    pub fn try_write_varint(&mut self, val: u64) -> Result<()> {
+        let mut dest = [0u8; 10];
+        let len = encode_varint(val, &mut dest);
+        self.try_write_bytes(&dest[..len])
+    }
+}
+
+/// A field value, borrowed from the wrapped buffer.
+#[derive(Debug)]
+pub enum Value<'a> {
+    Varint(u64),
+    I64(u64),
+    /// A string, bytes, embedded message or packed repeated field.
+    Len(BufView<'a>),
+    /// The fields between the start and end group keys.
+    Group(BufView<'a>),
+    I32(u32),
+}
+
+/// A field of a message.
+#[derive(Debug)]
+pub struct Field<'a> {
+    pub number: u32,
+    pub value: Value<'a>,
+    buf: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Field<'a> {
+    /// The offset of the field key in the wrapped buffer.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn wire_type(&self) -> WireType {
+        match self.value {
+            Value::Varint(_) => WireType::Varint,
+            Value::I64(_) => WireType::I64,
+            Value::Len(_) => WireType::Len,
+            Value::Group(_) => WireType::StartGroup,
+            Value::I32(_) => WireType::I32,
+        }
+    }
+
+    fn unexpected(&self) -> Error {
+        Error::invalid("unexpected protobuf wire type", self.buf, self.offset)
+    }
+
+    /// A `uint64`, or the bits of an `int64`, `uint32` or `int32` to truncate.
+    pub fn as_u64(&self) -> Result<u64> {
+        match self.value {
+            Value::Varint(val) => Ok(val),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// An `int64`.
+    pub fn as_i64(&self) -> Result<i64> {
+        self.as_u64().map(|val| val as i64)
+    }
+
+    /// An `int32`, negative values are sign extended to 10 bytes on the wire.
+    pub fn as_i32(&self) -> Result<i32> {
+        self.as_u64().map(|val| val as i32)
+    }
+
+    /// A zigzag encoded `sint64` or `sint32`.
+    pub fn as_sint(&self) -> Result<i64> {
+        self.as_u64().map(unzigzag)
+    }
+
+    pub fn as_bool(&self) -> Result<bool> {
+        self.as_u64().map(|val| val != 0)
+    }
+
+    /// A `fixed64`, or the bits of an `sfixed64`.
+    pub fn as_fixed64(&self) -> Result<u64> {
+        match self.value {
+            Value::I64(val) => Ok(val),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// A `fixed32`, or the bits of an `sfixed32`.
+    pub fn as_fixed32(&self) -> Result<u32> {
+        match self.value {
+            Value::I32(val) => Ok(val),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    pub fn as_f64(&self) -> Result<f64> {
+        self.as_fixed64().map(f64::from_bits)
+    }
+
+    pub fn as_f32(&self) -> Result<f32> {
+        self.as_fixed32().map(f32::from_bits)
+    }
+
+    /// The view of a length-delimited value.
+    pub fn as_view(&self) -> Result<BufView<'a>> {
+        match &self.value {
+            Value::Len(view) => Ok(BufView::wrap_with(
+                view.buf,
+                view.reader_index,
+                view.writer_index,
+            )),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    pub fn as_bytes(&self) -> Result<&'a [u8]> {
+        let view = self.as_view()?;
+        Ok(&view.buf[view.reader_index..view.writer_index])
+    }
+
+    pub fn as_str(&self) -> Result<&'a str> {
+        let view = self.as_view()?;
+        std::str::from_utf8(self.as_bytes()?)
+            .map_err(|_| Error::invalid("bad protobuf string", view.buf, view.reader_index))
+    }
+
+    /// The fields of an embedded message.
+    pub fn as_message(&self) -> Result<FieldIter<'a>> {
+        self.as_view().map(FieldIter::new)
+    }
+
+    /// The fields of a group.
+    pub fn as_group(&self) -> Result<FieldIter<'a>> {
+        match &self.value {
+            Value::Group(view) => Ok(view.protobuf_fields()),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// The values of a packed repeated varint field.
+    pub fn packed_varints(&self) -> Result<PackedVarints<'a>> {
+        Ok(PackedVarints {
+            view: self.as_view()?,
+            done: false,
+        })
+    }
+
+    /// The values of a packed repeated fixed size field, `u32`, `i32` or `f32` for
+    /// `I32` values and `u64`, `i64` or `f64` for `I64` values.
+    pub fn packed_fixed<T: Primitive>(&self) -> Result<ArrayIter<'a, T, LittleEndian>> {
+        let bytes = self.as_bytes()?;
+        if !bytes.len().is_multiple_of(T::SIZE) {
+            return Err(Error::invalid(
+                "bad protobuf packed length",
+                self.buf,
+                self.offset,
+            ));
+        }
+        Ok(ArrayIter::new(bytes))
+    }
+}
+
+/// The values of a packed repeated varint field, a malformed varint yields one error
+/// and ends the iteration.
+#[derive(Debug)]
+pub struct PackedVarints<'a> {
+    view: BufView<'a>,
+    done: bool,
+}
+
+impl Iterator for PackedVarints<'_> {
+    type Item = Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.view.remaining() == 0 {
+            return None;
+        }
+        let val = self.view.try_read_varint();
+        self.done = val.is_err();
+        Some(val)
+    }
+}
+
+impl std::iter::FusedIterator for PackedVarints<'_> {}
+
+/// An iterator over the fields of a message.
+///
+/// A truncated or malformed field, or a group not closed by a matching end group key,
+/// yields one error, with its absolute offset, and ends the iteration.
+#[derive(Debug)]
+pub struct FieldIter<'a> {
+    view: BufView<'a>,
+    done: bool,
+}
+
+impl<'a> FieldIter<'a> {
+    /// Iterate the fields from the reader index to the writer index of `view`.
+    pub fn new(view: BufView<'a>) -> Self {
+        FieldIter { view, done: false }
+    }
+
+    /// The offset of the next field.
+    pub fn offset(&self) -> usize {
+        self.view.reader_index()
+    }
+}
+
+fn read_key(view: &mut BufView) -> Result<(u32, WireType)> {
+    let start = view.reader_index();
+    let key = view.try_read_varint()?;
+    let number = key >> 3;
+    if number == 0 || number > MAX_FIELD_NUMBER as u64 {
+        return Err(Error::invalid("bad protobuf field number", view.buf, start));
+    }
+    match WireType::from_bits((key & 7) as u8) {
+        Some(wire_type) => Ok((number as u32, wire_type)),
+        None => Err(Error::invalid("bad protobuf wire type", view.buf, start)),
+    }
+}
+
+fn read_field<'a>(view: &mut BufView<'a>, depth: usize) -> Result<Field<'a>> {
+    let offset = view.reader_index();
+    let (number, wire_type) = read_key(view)?;
+    let value = match wire_type {
+        WireType::Varint => Value::Varint(view.try_read_varint()?),
+        WireType::I64 => Value::I64(view.try_read_endian(Endianness::Little)?),
+        WireType::I32 => Value::I32(view.try_read_endian(Endianness::Little)?),
+        WireType::Len => {
+            let len = view.try_read_varint()?;
+            Value::Len(view.try_read_view(usize::try_from(len).unwrap_or(usize::MAX))?)
+        }
+        WireType::StartGroup => {
+            if depth == MAX_DEPTH {
+                return Err(Error::invalid(
+                    "protobuf nesting too deep",
+                    view.buf,
+                    offset,
+                ));
+            }
+            let content_start = view.reader_index();
+            loop {
+                if view.remaining() == 0 {
+                    return Err(Error::invalid(
+                        "unterminated protobuf group",
+                        view.buf,
+                        offset,
+                    ));
+                }
+                let key_start = view.reader_index();
+                match read_key(view)? {
+                    (end, WireType::EndGroup) if end == number => {
+                        break Value::Group(BufView::wrap_with(view.buf, content_start, key_start));
+                    }
+                    (_, WireType::EndGroup) => {
+                        return Err(Error::invalid(
+                            "mismatched protobuf end group",
+                            view.buf,
+                            key_start,
+                        ));
+                    }
+                    _ => {
+                        view.set_reader_index(key_start);
+                        read_field(view, depth + 1)?;
+                    }
+                }
+            }
+        }
+        WireType::EndGroup => {
+            return Err(Error::invalid(
+                "mismatched protobuf end group",
+                view.buf,
+                offset,
+            ));
+        }
+    };
+    Ok(Field {
+        number,
+        value,
+        buf: view.buf,
+        offset,
+    })
+}
+
+impl<'a> Iterator for FieldIter<'a> {
+    type Item = Result<Field<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.view.remaining() == 0 {
+            return None;
+        }
+        let start = self.view.reader_index();
+        let field = read_field(&mut self.view, 0);
+        if field.is_err() {
+            self.view.set_reader_index(start);
+            self.done = true;
+        }
+        Some(field)
+    }
+}
+
+impl std::iter::FusedIterator for FieldIter<'_> {}
+
+/// The start of an embedded message opened by [`ProtobufWriter::begin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use = "the field must be closed with `ProtobufWriter::end`"]
+pub struct LenMark {
+    length_offset: usize,
+}
+
+/// Writes fields at the writer index of a [`BufViewMut`].
+///
+/// Length-delimited fields written with `begin` and `end` have their length
+/// back-patched, so they can nest. The length is first written in one byte, the value
+/// is moved when its final length needs more.
+#[derive(Debug)]
+pub struct ProtobufWriter<'v, 'b> {
+    view: &'v mut BufViewMut<'b>,
+}
+
+impl<'v, 'b> ProtobufWriter<'v, 'b> {
+    pub fn new(view: &'v mut BufViewMut<'b>) -> Self {
+        ProtobufWriter { view }
+    }
+
+    /// The view written to.
+    pub fn view(&mut self) -> &mut BufViewMut<'b> {
+        self.view
+    }
+
+    /// Write a field key, the value is written next.
+    pub fn write_key(&mut self, number: u32, wire_type: WireType) -> Result<()> {
+        if number == 0 || number > MAX_FIELD_NUMBER {
+            let offset = self.view.writer_index();
+            return Err(Error::invalid(
+                "bad protobuf field number",
+                self.view.buf,
+                offset,
+            ));
+        }
+        let key = (number as u64) << 3 | wire_type.bits() as u64;
+        self.view.try_write_varint(key)
+    }
+
+    /// Write a `uint64` or `uint32` field, cast an `int64` with `as u64`.
+    pub fn write_varint(&mut self, number: u32, val: u64) -> Result<()> {
+        self.write_key(number, WireType::Varint)?;
+        self.view.try_write_varint(val)
+    }
+
+    /// Write an `int32` field, a negative value takes 10 bytes.
+    pub fn write_i32(&mut self, number: u32, val: i32) -> Result<()> {
+        self.write_varint(number, val as i64 as u64)
+    }
+
+    /// Write a zigzag encoded `sint64` or `sint32` field.
+    pub fn write_sint(&mut self, number: u32, val: i64) -> Result<()> {
+        self.write_varint(number, zigzag(val))
+    }
+
+    pub fn write_bool(&mut self, number: u32, val: bool) -> Result<()> {
+        self.write_varint(number, val as u64)
+    }
+
+    /// Write a `fixed64` field, cast an `sfixed64` with `as u64`.
+    pub fn write_fixed64(&mut self, number: u32, val: u64) -> Result<()> {
+        self.write_key(number, WireType::I64)?;
+        self.view.try_write_endian(val, Endianness::Little)
+    }
+
+    /// Write a `fixed32` field, cast an `sfixed32` with `as u32`.
+    pub fn write_fixed32(&mut self, number: u32, val: u32) -> Result<()> {
+        self.write_key(number, WireType::I32)?;
+        self.view.try_write_endian(val, Endianness::Little)
+    }
+
+    pub fn write_f64(&mut self, number: u32, val: f64) -> Result<()> {
+        self.write_fixed64(number, val.to_bits())
+    }
+
+    pub fn write_f32(&mut self, number: u32, val: f32) -> Result<()> {
+        self.write_fixed32(number, val.to_bits())
+    }
+
+    pub fn write_bytes(&mut self, number: u32, val: &[u8]) -> Result<()> {
+        self.write_key(number, WireType::Len)?;
+        self.view.try_write_varint(val.len() as u64)?;
+        self.view.try_write_bytes(val)
+    }
+
+    pub fn write_str(&mut self, number: u32, val: &str) -> Result<()> {
+        self.write_bytes(number, val.as_bytes())
+    }
+
+    /// Write a packed repeated varint field.
+    pub fn write_packed_varints(&mut self, number: u32, vals: &[u64]) -> Result<()> {
+        let mark = self.begin(number)?;
+        for &val in vals {
+            self.view.try_write_varint(val)?;
+        }
+        self.end(mark)
+    }
+
+    /// Write a packed repeated fixed size field, `T` is a 4 or 8 bytes number.
+    pub fn write_packed_fixed<T: Primitive>(&mut self, number: u32, vals: &[T]) -> Result<()> {
+        let mark = self.begin(number)?;
+        for &val in vals {
+            self.view.try_write_endian(val, Endianness::Little)?;
+        }
+        self.end(mark)
+    }
+
+    /// Write the key and a placeholder length of a length-delimited field, the value is
+    /// written next and `end` back-patches the length.
+    pub fn begin(&mut self, number: u32) -> Result<LenMark> {
+        self.write_key(number, WireType::Len)?;
+        let length_offset = self.view.writer_index();
+        self.view.try_write_bytes(&[0])?;
+        Ok(LenMark { length_offset })
+    }
+
+    /// Back-patch the length of the field opened by `mark`, the value is what was
+    /// written since.
+    pub fn end(&mut self, mark: LenMark) -> Result<()> {
+        let end = self.view.writer_index();
+        let value_start = mark.length_offset + 1;
+        let mut dest = [0u8; 10];
+        let size = encode_varint((end - value_start) as u64, &mut dest);
+        if size > 1 {
+            let shift = size - 1;
+            self.view.check_get(end, shift)?;
+            self.view
+                .buf
+                .copy_within(value_start..end, value_start + shift);
+            self.view.writer_index = end + shift;
+        }
+        self.view.set_bytes(mark.length_offset, &dest[..size]);
+        Ok(())
+    }
+
+    /// Write a start group key, the group is closed with `end_group`.
+    pub fn begin_group(&mut self, number: u32) -> Result<()> {
+        self.write_key(number, WireType::StartGroup)
+    }
+
+    pub fn end_group(&mut self, number: u32) -> Result<()> {
+        self.write_key(number, WireType::EndGroup)
+    }
+}
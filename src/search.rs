@@ -0,0 +1,148 @@
+//! Word-at-a-time (SWAR) byte search, used by the find and read_until methods.
+
+use crate::BufView;
+
+const LO: u64 = 0x0101_0101_0101_0101;
+const HI: u64 = 0x8080_8080_8080_8080;
+
+/// Set the high bit of every zero byte of `v`, and only of those.
+#[inline]
+fn zero_bytes(v: u64) -> u64 {
+    !(((v & !HI).wrapping_add(!HI)) | v) & HI
+}
+
+#[inline]
+fn load(hay: &[u8], i: usize) -> u64 {
+    u64::from_le_bytes(hay[i..i + 8].try_into().unwrap())
+}
+
+pub(crate) fn find_byte(needle: u8, hay: &[u8]) -> Option<usize> {
+    let pattern = LO * needle as u64;
+    let mut i = 0;
+    while i + 8 <= hay.len() {
+        let found = zero_bytes(load(hay, i) ^ pattern);
+        if found != 0 {
+            return Some(i + (found.trailing_zeros() / 8) as usize);
+        }
+        i += 8;
+    }
+    hay[i..].iter().position(|b| *b == needle).map(|p| i + p)
+}
+
+pub(crate) fn rfind_byte(needle: u8, hay: &[u8]) -> Option<usize> {
+    let pattern = LO * needle as u64;
+    let mut end = hay.len();
+    while end >= 8 {
+        let found = zero_bytes(load(hay, end - 8) ^ pattern);
+        if found != 0 {
+            return Some(end - 1 - (found.leading_zeros() / 8) as usize);
+        }
+        end -= 8;
+    }
+    hay[..end].iter().rposition(|b| *b == needle)
+}
+
+pub(crate) fn find(needle: &[u8], hay: &[u8]) -> Option<usize> {
+    let (first, rest) = match needle.split_first() {
+        Some(split) => split,
+        None => return Some(0),
+    };
+    let mut start = 0;
+    while hay.len() - start >= needle.len() {
+        let last_start = hay.len() - needle.len();
+        let i = start + find_byte(*first, &hay[start..=last_start])?;
+        if &hay[i + 1..i + needle.len()] == rest {
+            return Some(i);
+        }
+        start = i + 1;
+    }
+    None
+}
+
+pub(crate) fn rfind(needle: &[u8], hay: &[u8]) -> Option<usize> {
+    let (last, rest) = match needle.split_last() {
+        Some(split) => split,
+        None => return Some(hay.len()),
+    };
+    let mut end = hay.len();
+    while end >= needle.len() {
+        let i = rfind_byte(*last, &hay[rest.len()..end])?;
+        if &hay[i..i + rest.len()] == rest {
+            return Some(i);
+        }
+        end = i + rest.len();
+    }
+    None
+}
+
+impl<'a> BufView<'a> {
+    /// Find the first `needle` byte in the readable window.
+    ///
+    /// The index returned is absolute in the wrapped buffer like the index of `get_*`,
+    /// so it can be passed to `set_reader_index`.
+    /// ```
+    /// use buf_view::BufView;
+    ///
+    /// let mut buf_view = BufView::wrap(b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody");
+    /// assert_eq!(buf_view.find_u8(b' '), Some(3));
+    /// assert_eq!(buf_view.find(b"\r\n\r\n"), Some(23));
+    ///
+    /// assert_eq!(buf_view.read_until(b' '), Some(&b"GET"[..]));
+    /// assert_eq!(buf_view.read_line(), Some(&b"/ HTTP/1.1"[..]));
+    /// assert_eq!(buf_view.read_line(), Some(&b"Host: a"[..]));
+    /// assert_eq!(buf_view.read_line(), Some(&b""[..]));
+    /// assert_eq!(buf_view.read_line(), None);
+    /// ```
+    pub fn find_u8(&self, needle: u8) -> Option<usize> {
+        find_byte(needle, self.readable()).map(|i| self.reader_index + i)
+    }
+
+    /// Find the last `needle` byte in the readable window, the index is absolute.
+    pub fn rfind_u8(&self, needle: u8) -> Option<usize> {
+        rfind_byte(needle, self.readable()).map(|i| self.reader_index + i)
+    }
+
+    /// Find the first occurrence of `needle` in the readable window, the index is absolute.
+    pub fn find(&self, needle: &[u8]) -> Option<usize> {
+        find(needle, self.readable()).map(|i| self.reader_index + i)
+    }
+
+    /// Find the last occurrence of `needle` in the readable window, the index is absolute.
+    pub fn rfind(&self, needle: &[u8]) -> Option<usize> {
+        rfind(needle, self.readable()).map(|i| self.reader_index + i)
+    }
+
+    /// Read the bytes before the next `delim` and consume the `delim` too.
+    ///
+    /// Return None and consume nothing when there is no `delim` in the readable window.
+    pub fn read_until(&mut self, delim: u8) -> Option<&'a [u8]> {
+        let i = self.find_u8(delim)?;
+        let val = &self.buf[self.reader_index..i];
+        self.reader_index = i + 1;
+        Some(val)
+    }
+
+    /// Read a line terminated by `\n` or `\r\n`, the terminator is consumed but not
+    /// returned.
+    ///
+    /// Return None and consume nothing when there is no complete line.
+    pub fn read_line(&mut self) -> Option<&'a [u8]> {
+        let line = self.read_until(b'\n')?;
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+
+    /// Skip the bytes matching `pred`, and return how many were skipped.
+    pub fn skip_while(&mut self, mut pred: impl FnMut(u8) -> bool) -> usize {
+        let skipped = self
+            .readable()
+            .iter()
+            .position(|b| !pred(*b))
+            .unwrap_or(self.remaining());
+        self.reader_index += skipped;
+        skipped
+    }
+
+    fn readable(&self) -> &'a [u8] {
+        &self.buf[self.reader_index..self.writer_index]
+    }
+}
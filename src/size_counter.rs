@@ -0,0 +1,66 @@
+use crate::view_write::ViewWrite;
+
+/// A dry-run writer that only counts bytes.
+///
+/// SizeCounter accepts the same write/set calls as BufViewMut through [`ViewWrite`],
+/// but never stores any data. It keeps a writer_index like BufViewMut and records the
+/// maximum extent touched by any write or set, which is the buffer size needed to run
+/// the same encoding for real.
+///
+/// Example
+/// ```
+/// use buf_view::{SizeCounter, ViewWrite};
+///
+/// let mut counter = SizeCounter::new();
+/// counter.write_u32(0);
+/// counter.write_bytes(b"hello");
+/// counter.set_u64(12, 0);
+///
+/// assert_eq!(counter.writer_index(), 9);
+/// assert_eq!(counter.size(), 20);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SizeCounter {
+    writer_index: usize,
+    size: usize,
+}
+
+impl SizeCounter {
+    pub fn new() -> Self {
+        SizeCounter::default()
+    }
+
+    /// The maximum extent touched by any write or set so far.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn writer_index(&self) -> usize {
+        self.writer_index
+    }
+
+    /// Move the writer_index without touching any byte, this does not change `size`.
+    pub fn set_writer_index(&mut self, index: usize) {
+        self.writer_index = index;
+    }
+
+    pub fn clear(&mut self) {
+        self.writer_index = 0;
+        self.size = 0;
+    }
+}
+
+impl ViewWrite for SizeCounter {
+    fn write_bytes(&mut self, src: &[u8]) {
+        self.writer_index += src.len();
+        self.size = self.size.max(self.writer_index);
+    }
+
+    fn set_bytes(&mut self, index: usize, src: &[u8]) {
+        self.size = self.size.max(index + src.len());
+    }
+
+    fn writer_index(&self) -> usize {
+        self.writer_index
+    }
+}
@@ -0,0 +1,570 @@
+//! Timestamps in the Unix, NTP, PTP, Windows FILETIME and DOS date/time formats.
+//!
+//! The raw types [`NtpTimestamp`], [`PtpTimestamp`], [`FileTime`] and [`DosDateTime`]
+//! hold the wire fields as they are and only depend on `core::time::Duration`. Their
+//! `to_system_time` and `from_system_time` conversions check the range and return None
+//! when the time is invalid or not representable.
+//!
+//! BufView and BufViewMut read and get the Unix times directly as `SystemTime` with
+//! `read_unix_time32`, `read_unix_time64`, `read_unix_millis64` and the `_le`
+//! variants, and the raw types with `read_ntp_timestamp`, `read_ptp_timestamp`,
+//! `read_filetime` and `read_dos_date_time`. BufViewMut writes and sets them with the
+//! matching `write_` and `set_` methods. NTP and PTP are big endian, FILETIME and DOS
+//! date/time little endian.
+//!
+//! Example
+//! ```
+//! use buf_view::time::{DosDateTime, FileTime};
+//! use buf_view::{BufView, BufViewMut};
+//! use std::time::{Duration, SystemTime};
+//!
+//! let t = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+//! let mut buf = [0u8; 20];
+//! let mut buf_view = BufViewMut::wrap(&mut buf);
+//! buf_view.write_unix_time32(t).unwrap();
+//! buf_view.write_filetime(FileTime::from_system_time(t).unwrap());
+//! buf_view.write_dos_date_time(DosDateTime::from_system_time(t).unwrap());
+//!
+//! let mut buf_view = BufView::wrap(&buf);
+//! assert_eq!(buf_view.read_unix_time32(), t);
+//! assert_eq!(buf_view.read_filetime().to_system_time(), Some(t));
+//! let dos = buf_view.read_dos_date_time();
+//! // 2023-11-14 22:13:20
+//! assert_eq!((dos.year(), dos.month(), dos.day()), (2023, 11, 14));
+//! assert_eq!((dos.hour(), dos.minute(), dos.second()), (22, 13, 20));
+//! assert_eq!(dos.to_system_time(), Some(t));
+//! ```
+
+use crate::error::{Error, ErrorKind, Result};
+use crate::{BufView, BufViewMut};
+use core::time::Duration;
+use std::time::SystemTime;
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+/// Seconds from 1900-01-01 to 1970-01-01.
+const NTP_UNIX_OFFSET: i64 = 2_208_988_800;
+/// Seconds from 1601-01-01 to 1970-01-01.
+const FILETIME_UNIX_OFFSET: i64 = 11_644_473_600;
+
+/// The time `secs` and `nanos` after the Unix epoch, `secs` may be negative.
+pub(crate) fn from_unix(secs: i64, nanos: u32) -> Option<SystemTime> {
+    debug_assert!(nanos < NANOS_PER_SEC);
+    if secs >= 0 {
+        SystemTime::UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        let before = Duration::new(secs.unsigned_abs(), 0).checked_sub(Duration::new(0, nanos))?;
+        SystemTime::UNIX_EPOCH.checked_sub(before)
+    }
+}
+
+/// Split `t` into whole seconds since the Unix epoch, rounded down, and nanoseconds.
+pub(crate) fn to_unix(t: SystemTime) -> Option<(i64, u32)> {
+    match t.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(d) => Some((i64::try_from(d.as_secs()).ok()?, d.subsec_nanos())),
+        Err(e) => {
+            let d = e.duration();
+            let secs = i64::try_from(d.as_secs()).ok()?;
+            match d.subsec_nanos() {
+                0 => Some((-secs, 0)),
+                nanos => Some((-secs - 1, NANOS_PER_SEC - nanos)),
+            }
+        }
+    }
+}
+
+/// An NTP 64-bit timestamp, 32.32 fixed point seconds since 1900-01-01 UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct NtpTimestamp {
+    pub seconds: u32,
+    pub fraction: u32,
+}
+
+impl NtpTimestamp {
+    /// The time since the start of the NTP era the timestamp is in.
+    pub fn to_duration(&self) -> Duration {
+        let nanos = ((self.fraction as u64 * NANOS_PER_SEC as u64 + (1 << 31)) >> 32) as u32;
+        Duration::new(self.seconds as u64, nanos.min(NANOS_PER_SEC - 1))
+    }
+
+    /// The timestamp `d` after the start of an NTP era, None when `d` is 2^32 seconds
+    /// or more.
+    pub fn from_duration(d: Duration) -> Option<Self> {
+        let seconds = u32::try_from(d.as_secs()).ok()?;
+        let fraction = ((d.subsec_nanos() as u64) << 32) + NANOS_PER_SEC as u64 / 2;
+        Some(NtpTimestamp {
+            seconds,
+            fraction: (fraction / NANOS_PER_SEC as u64) as u32,
+        })
+    }
+
+    /// The time, with the era rule of RFC 4330: seconds with the high bit clear are in
+    /// era 1 starting 2036-02-07, so the range is 1968-01-20 to 2104-02-26.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let d = self.to_duration();
+        let mut secs = d.as_secs() as i64 - NTP_UNIX_OFFSET;
+        if self.seconds & 0x8000_0000 == 0 {
+            secs += 1 << 32;
+        }
+        from_unix(secs, d.subsec_nanos())
+    }
+
+    /// The timestamp of `t`, None outside the range of `to_system_time`.
+    pub fn from_system_time(t: SystemTime) -> Option<Self> {
+        let (secs, nanos) = to_unix(t)?;
+        let ntp_secs = secs.checked_add(NTP_UNIX_OFFSET)?;
+        if !(0x8000_0000..0x1_8000_0000).contains(&ntp_secs) {
+            return None;
+        }
+        let d = Duration::new(ntp_secs as u64 & 0xffff_ffff, nanos);
+        NtpTimestamp::from_duration(d)
+    }
+}
+
+/// A PTP (IEEE 1588) timestamp, 48-bit seconds and 32-bit nanoseconds since the PTP
+/// epoch 1970-01-01.
+///
+/// PTP counts TAI seconds, no UTC offset is applied by the conversions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PtpTimestamp {
+    /// Must be less than 2^48 to be written.
+    pub seconds: u64,
+    pub nanoseconds: u32,
+}
+
+impl PtpTimestamp {
+    /// The time since the PTP epoch, None when `nanoseconds` is a second or more.
+    pub fn to_duration(&self) -> Option<Duration> {
+        if self.nanoseconds >= NANOS_PER_SEC {
+            return None;
+        }
+        Some(Duration::new(self.seconds, self.nanoseconds))
+    }
+
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        SystemTime::UNIX_EPOCH.checked_add(self.to_duration()?)
+    }
+
+    /// The timestamp of `t`, None before the epoch or after 2^48 seconds.
+    pub fn from_system_time(t: SystemTime) -> Option<Self> {
+        let d = t.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+        if d.as_secs() >= 1 << 48 {
+            return None;
+        }
+        Some(PtpTimestamp {
+            seconds: d.as_secs(),
+            nanoseconds: d.subsec_nanos(),
+        })
+    }
+}
+
+/// A Windows FILETIME, 100 nanosecond intervals since 1601-01-01 UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct FileTime(pub u64);
+
+impl FileTime {
+    /// The time since 1601-01-01.
+    pub fn to_duration(&self) -> Duration {
+        Duration::new(self.0 / 10_000_000, (self.0 % 10_000_000) as u32 * 100)
+    }
+
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let d = self.to_duration();
+        from_unix(d.as_secs() as i64 - FILETIME_UNIX_OFFSET, d.subsec_nanos())
+    }
+
+    /// The FILETIME of `t` rounded down to 100 ns, None before 1601.
+    pub fn from_system_time(t: SystemTime) -> Option<Self> {
+        let (secs, nanos) = to_unix(t)?;
+        let secs = u64::try_from(secs.checked_add(FILETIME_UNIX_OFFSET)?).ok()?;
+        let ticks = secs
+            .checked_mul(10_000_000)?
+            .checked_add(nanos as u64 / 100)?;
+        Some(FileTime(ticks))
+    }
+}
+
+/// A packed MS-DOS date and time as used by FAT and ZIP, with a 2 second resolution.
+///
+/// The date packs `year - 1980` in bits 9-15, the month in bits 5-8 and the day in bits
+/// 0-4, the time packs the hour in bits 11-15, the minute in bits 5-10 and the second
+/// divided by 2 in bits 0-4. DOS times have no time zone, the conversions use UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DosDateTime {
+    pub date: u16,
+    pub time: u16,
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The proleptic Gregorian (year, month, day) of `days` since 1970-01-01.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+pub(crate) fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DosDateTime {
+    pub fn year(&self) -> u32 {
+        1980 + (self.date >> 9) as u32
+    }
+
+    pub fn month(&self) -> u32 {
+        (self.date >> 5) as u32 & 0x0f
+    }
+
+    pub fn day(&self) -> u32 {
+        self.date as u32 & 0x1f
+    }
+
+    pub fn hour(&self) -> u32 {
+        (self.time >> 11) as u32
+    }
+
+    pub fn minute(&self) -> u32 {
+        (self.time >> 5) as u32 & 0x3f
+    }
+
+    pub fn second(&self) -> u32 {
+        (self.time as u32 & 0x1f) * 2
+    }
+
+    /// The time in UTC, None when a field is out of range, e.g. a zero month or day.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        let (year, month, day) = (self.year(), self.month(), self.day());
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+        if self.hour() > 23 || self.minute() > 59 || self.second() > 59 {
+            return None;
+        }
+        let days = days_from_civil(year as i64, month, day);
+        let secs = self.hour() * 3600 + self.minute() * 60 + self.second();
+        from_unix(days * 86_400 + secs as i64, 0)
+    }
+
+    /// The date and time of `t` in UTC rounded down to 2 seconds, None outside the
+    /// years 1980 to 2107.
+    pub fn from_system_time(t: SystemTime) -> Option<Self> {
+        let (secs, _) = to_unix(t)?;
+        let (year, month, day) = civil_from_days(secs.div_euclid(86_400));
+        if !(1980..=2107).contains(&year) {
+            return None;
+        }
+        let secs = secs.rem_euclid(86_400) as u32;
+        let (hour, minute, second) = (secs / 3600, secs / 60 % 60, secs % 60);
+        Some(DosDateTime {
+            date: ((year - 1980) as u16) << 9 | (month as u16) << 5 | day as u16,
+            time: (hour as u16) << 11 | (minute as u16) << 5 | (second / 2) as u16,
+        })
+    }
+}
+
+fn unix_millis(millis: i64) -> Option<SystemTime> {
+    from_unix(
+        millis.div_euclid(1000),
+        millis.rem_euclid(1000) as u32 * 1_000_000,
+    )
+}
+
+fn to_unix_millis(t: SystemTime) -> Option<i64> {
+    let (secs, nanos) = to_unix(t)?;
+    secs.checked_mul(1000)?
+        .checked_add((nanos / 1_000_000) as i64)
+}
+
+fn ntp_from_bytes(bytes: [u8; 8]) -> NtpTimestamp {
+    NtpTimestamp {
+        seconds: u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+        fraction: u32::from_be_bytes(bytes[4..].try_into().unwrap()),
+    }
+}
+
+fn ptp_from_bytes(bytes: [u8; 10]) -> PtpTimestamp {
+    let mut seconds = [0u8; 8];
+    seconds[2..].copy_from_slice(&bytes[..6]);
+    PtpTimestamp {
+        seconds: u64::from_be_bytes(seconds),
+        nanoseconds: u32::from_be_bytes(bytes[6..].try_into().unwrap()),
+    }
+}
+
+fn dos_from_bytes(bytes: [u8; 4]) -> DosDateTime {
+    DosDateTime {
+        time: u16::from_le_bytes([bytes[0], bytes[1]]),
+        date: u16::from_le_bytes([bytes[2], bytes[3]]),
+    }
+}
+
+macro_rules! time_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            /// Read unsigned 32-bit seconds since the Unix epoch, which covers 1970 to 2106.
+            pub fn read_unix_time32(&mut self) -> SystemTime {
+                let secs = u32::from_be_bytes(self.read_array());
+                SystemTime::UNIX_EPOCH + Duration::from_secs(secs as u64)
+            }
+
+            pub fn read_unix_time32_le(&mut self) -> SystemTime {
+                let secs = u32::from_le_bytes(self.read_array());
+                SystemTime::UNIX_EPOCH + Duration::from_secs(secs as u64)
+            }
+
+            /// Read signed 64-bit seconds since the Unix epoch, fail with `Overflow` when
+            /// the time is out of the `SystemTime` range, nothing is consumed on failure.
+            pub fn read_unix_time64(&mut self) -> Result<SystemTime> {
+                self.check_read(8)?;
+                let val = self.get_unix_time64(self.reader_index)?;
+                self.reader_index += 8;
+                Ok(val)
+            }
+
+            pub fn read_unix_time64_le(&mut self) -> Result<SystemTime> {
+                self.check_read(8)?;
+                let val = self.get_unix_time64_le(self.reader_index)?;
+                self.reader_index += 8;
+                Ok(val)
+            }
+
+            /// Read signed 64-bit milliseconds since the Unix epoch.
+            pub fn read_unix_millis64(&mut self) -> Result<SystemTime> {
+                self.check_read(8)?;
+                let val = self.get_unix_millis64(self.reader_index)?;
+                self.reader_index += 8;
+                Ok(val)
+            }
+
+            pub fn read_unix_millis64_le(&mut self) -> Result<SystemTime> {
+                self.check_read(8)?;
+                let val = self.get_unix_millis64_le(self.reader_index)?;
+                self.reader_index += 8;
+                Ok(val)
+            }
+
+            pub fn read_ntp_timestamp(&mut self) -> NtpTimestamp {
+                ntp_from_bytes(self.read_array())
+            }
+
+            pub fn read_ptp_timestamp(&mut self) -> PtpTimestamp {
+                ptp_from_bytes(self.read_array())
+            }
+
+            pub fn read_filetime(&mut self) -> FileTime {
+                FileTime(u64::from_le_bytes(self.read_array()))
+            }
+
+            /// Read a DOS time then a DOS date, the order of ZIP and FAT entries.
+            pub fn read_dos_date_time(&mut self) -> DosDateTime {
+                dos_from_bytes(self.read_array())
+            }
+
+            pub fn get_unix_time64(&self, index: usize) -> Result<SystemTime> {
+                self.check_get(index, 8)?;
+                let secs = i64::from_be_bytes(self.get_array(index));
+                from_unix(secs, 0).ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))
+            }
+
+            pub fn get_unix_time64_le(&self, index: usize) -> Result<SystemTime> {
+                self.check_get(index, 8)?;
+                let secs = i64::from_le_bytes(self.get_array(index));
+                from_unix(secs, 0).ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))
+            }
+
+            pub fn get_unix_millis64(&self, index: usize) -> Result<SystemTime> {
+                self.check_get(index, 8)?;
+                let millis = i64::from_be_bytes(self.get_array(index));
+                unix_millis(millis).ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))
+            }
+
+            pub fn get_unix_millis64_le(&self, index: usize) -> Result<SystemTime> {
+                self.check_get(index, 8)?;
+                let millis = i64::from_le_bytes(self.get_array(index));
+                unix_millis(millis).ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))
+            }
+
+            pub fn get_ntp_timestamp(&self, index: usize) -> NtpTimestamp {
+                ntp_from_bytes(self.get_array(index))
+            }
+
+            pub fn get_ptp_timestamp(&self, index: usize) -> PtpTimestamp {
+                ptp_from_bytes(self.get_array(index))
+            }
+
+            pub fn get_filetime(&self, index: usize) -> FileTime {
+                FileTime(u64::from_le_bytes(self.get_array(index)))
+            }
+
+            pub fn get_dos_date_time(&self, index: usize) -> DosDateTime {
+                dos_from_bytes(self.get_array(index))
+            }
+        }
+    };
+}
+
+time_read_methods!(BufView);
+time_read_methods!(BufViewMut);
+
+impl BufViewMut<'_> {
+    /// Write `t` as unsigned 32-bit seconds since the Unix epoch, rounded down. Fail
+    /// with `Overflow` before 1970 or after 2106, nothing is written on failure.
+    pub fn write_unix_time32(&mut self, t: SystemTime) -> Result<()> {
+        self.set_unix_time32(self.writer_index, t)?;
+        self.writer_index += 4;
+        Ok(())
+    }
+
+    pub fn write_unix_time32_le(&mut self, t: SystemTime) -> Result<()> {
+        self.set_unix_time32_le(self.writer_index, t)?;
+        self.writer_index += 4;
+        Ok(())
+    }
+
+    /// Write `t` as signed 64-bit seconds since the Unix epoch, rounded down.
+    pub fn write_unix_time64(&mut self, t: SystemTime) -> Result<()> {
+        self.set_unix_time64(self.writer_index, t)?;
+        self.writer_index += 8;
+        Ok(())
+    }
+
+    pub fn write_unix_time64_le(&mut self, t: SystemTime) -> Result<()> {
+        self.set_unix_time64_le(self.writer_index, t)?;
+        self.writer_index += 8;
+        Ok(())
+    }
+
+    /// Write `t` as signed 64-bit milliseconds since the Unix epoch, rounded down.
+    pub fn write_unix_millis64(&mut self, t: SystemTime) -> Result<()> {
+        self.set_unix_millis64(self.writer_index, t)?;
+        self.writer_index += 8;
+        Ok(())
+    }
+
+    pub fn write_unix_millis64_le(&mut self, t: SystemTime) -> Result<()> {
+        self.set_unix_millis64_le(self.writer_index, t)?;
+        self.writer_index += 8;
+        Ok(())
+    }
+
+    pub fn write_ntp_timestamp(&mut self, val: NtpTimestamp) {
+        self.set_ntp_timestamp(self.writer_index, val);
+        self.writer_index += 8;
+    }
+
+    /// Write a PTP timestamp, `val.seconds` must fit in 48 bits.
+    pub fn write_ptp_timestamp(&mut self, val: PtpTimestamp) {
+        self.set_ptp_timestamp(self.writer_index, val);
+        self.writer_index += 10;
+    }
+
+    pub fn write_filetime(&mut self, val: FileTime) {
+        self.write_array(&val.0.to_le_bytes());
+    }
+
+    /// Write a DOS time then a DOS date, the order of ZIP and FAT entries.
+    pub fn write_dos_date_time(&mut self, val: DosDateTime) {
+        self.set_dos_date_time(self.writer_index, val);
+        self.writer_index += 4;
+    }
+
+    pub fn set_unix_time32(&mut self, index: usize, t: SystemTime) -> Result<()> {
+        let secs = self.unix_secs::<u32>(index, t)?;
+        self.check_get(index, 4)?;
+        self.set_array(index, &secs.to_be_bytes());
+        Ok(())
+    }
+
+    pub fn set_unix_time32_le(&mut self, index: usize, t: SystemTime) -> Result<()> {
+        let secs = self.unix_secs::<u32>(index, t)?;
+        self.check_get(index, 4)?;
+        self.set_array(index, &secs.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn set_unix_time64(&mut self, index: usize, t: SystemTime) -> Result<()> {
+        let secs = self.unix_secs::<i64>(index, t)?;
+        self.check_get(index, 8)?;
+        self.set_array(index, &secs.to_be_bytes());
+        Ok(())
+    }
+
+    pub fn set_unix_time64_le(&mut self, index: usize, t: SystemTime) -> Result<()> {
+        let secs = self.unix_secs::<i64>(index, t)?;
+        self.check_get(index, 8)?;
+        self.set_array(index, &secs.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn set_unix_millis64(&mut self, index: usize, t: SystemTime) -> Result<()> {
+        let millis =
+            to_unix_millis(t).ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))?;
+        self.check_get(index, 8)?;
+        self.set_array(index, &millis.to_be_bytes());
+        Ok(())
+    }
+
+    pub fn set_unix_millis64_le(&mut self, index: usize, t: SystemTime) -> Result<()> {
+        let millis =
+            to_unix_millis(t).ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))?;
+        self.check_get(index, 8)?;
+        self.set_array(index, &millis.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn set_ntp_timestamp(&mut self, index: usize, val: NtpTimestamp) {
+        let mut bytes = [0u8; 8];
+        bytes[..4].copy_from_slice(&val.seconds.to_be_bytes());
+        bytes[4..].copy_from_slice(&val.fraction.to_be_bytes());
+        self.set_array(index, &bytes);
+    }
+
+    pub fn set_ptp_timestamp(&mut self, index: usize, val: PtpTimestamp) {
+        assert!(val.seconds < 1 << 48);
+        let mut bytes = [0u8; 10];
+        bytes[..6].copy_from_slice(&val.seconds.to_be_bytes()[2..]);
+        bytes[6..].copy_from_slice(&val.nanoseconds.to_be_bytes());
+        self.set_array(index, &bytes);
+    }
+
+    pub fn set_filetime(&mut self, index: usize, val: FileTime) {
+        self.set_array(index, &val.0.to_le_bytes());
+    }
+
+    pub fn set_dos_date_time(&mut self, index: usize, val: DosDateTime) {
+        let mut bytes = [0u8; 4];
+        bytes[..2].copy_from_slice(&val.time.to_le_bytes());
+        bytes[2..].copy_from_slice(&val.date.to_le_bytes());
+        self.set_array(index, &bytes);
+    }
+
+    fn unix_secs<T: TryFrom<i64>>(&self, index: usize, t: SystemTime) -> Result<T> {
+        to_unix(t)
+            .and_then(|(secs, _)| T::try_from(secs).ok())
+            .ok_or_else(|| Error::new(ErrorKind::Overflow, self.buf, index))
+    }
+}
@@ -0,0 +1,366 @@
+//! Tag-length-value sequences with configurable field encodings.
+
+use crate::endian::Endianness;
+use crate::error::{Error, ErrorKind, Result};
+use crate::{BufView, BufViewMut};
+
+/// The encoding of the tag field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TagFormat {
+    U8,
+    U16,
+    /// An ASN.1 BER identifier of up to 4 bytes, the tag value is its bytes read as a
+    /// big endian number, e.g. `0x9f02` for the EMV amount.
+    Ber,
+}
+
+/// The encoding of the length field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthFormat {
+    U8,
+    U16,
+    U32,
+    /// The ASN.1 BER definite form, short below 128 and up to 4 length bytes after.
+    Ber,
+}
+
+/// The layout of a TLV sequence.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, BufViewMut, LengthFormat, TagFormat, TlvFormat, TlvWriter};
+///
+/// // RADIUS attributes, the length counts the 2 bytes header
+/// let mut format = TlvFormat::new(TagFormat::U8, LengthFormat::U8);
+/// format.length_includes_header = true;
+///
+/// let mut buf = [0u8; 16];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// let mut writer = TlvWriter::new(&mut buf_view, format);
+/// writer.write(1, b"alice").unwrap();
+/// writer.write(5, &[0, 0, 0, 7]).unwrap();
+/// assert_eq!(&buf[..3], &[1, 7, b'a']);
+///
+/// let buf_view = BufView::wrap(&buf[..13]);
+/// let mut iter = buf_view.tlv_iter(format);
+/// let (tag, mut value) = iter.next().unwrap().unwrap();
+/// assert_eq!((tag, value.remaining(), value.read_u8()), (1, 5, b'a'));
+/// let (tag, mut value) = iter.next().unwrap().unwrap();
+/// assert_eq!((tag, value.read_u32()), (5, 7));
+/// assert!(iter.next().is_none());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TlvFormat {
+    pub tag: TagFormat,
+    pub length: LengthFormat,
+    /// The byte order of the `U16` and `U32` fields.
+    pub endianness: Endianness,
+    /// The length counts the tag and length fields too.
+    pub length_includes_header: bool,
+}
+
+impl TlvFormat {
+    /// A big endian format whose length counts the value only.
+    pub fn new(tag: TagFormat, length: LengthFormat) -> Self {
+        TlvFormat {
+            tag,
+            length,
+            endianness: Endianness::Big,
+            length_includes_header: false,
+        }
+    }
+
+    /// The ASN.1 BER / EMV format.
+    pub fn ber() -> Self {
+        TlvFormat::new(TagFormat::Ber, LengthFormat::Ber)
+    }
+}
+
+fn ber_length_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0xff => 2,
+        0x100..=0xffff => 3,
+        0x1_0000..=0xff_ffff => 4,
+        _ => 5,
+    }
+}
+
+/// An iterator over the `(tag, value)` items of a TLV sequence.
+///
+/// The values are views with absolute indexes in the wrapped buffer. A truncated or
+/// malformed item yields one error, with its absolute offset, and ends the iteration.
+#[derive(Debug)]
+pub struct TlvIter<'a> {
+    view: BufView<'a>,
+    format: TlvFormat,
+    done: bool,
+}
+
+impl<'a> TlvIter<'a> {
+    /// Iterate the items from the reader index to the writer index of `view`.
+    pub fn new(view: BufView<'a>, format: TlvFormat) -> Self {
+        TlvIter {
+            view,
+            format,
+            done: false,
+        }
+    }
+
+    /// The offset of the next item.
+    pub fn offset(&self) -> usize {
+        self.view.reader_index()
+    }
+
+    fn read_tag(&mut self) -> Result<u32> {
+        let e = self.format.endianness;
+        let tag = match self.format.tag {
+            TagFormat::U8 => self.view.try_read_u8()? as u32,
+            TagFormat::U16 => self.view.try_read_endian::<u16>(e)? as u32,
+            TagFormat::Ber => {
+                let start = self.view.reader_index();
+                let first = self.view.try_read_u8()?;
+                let mut tag = first as u32;
+                if first & 0x1f == 0x1f {
+                    loop {
+                        if tag > 0xff_ffff {
+                            return Err(Error::invalid("BER tag too long", self.view.buf, start));
+                        }
+                        let b = self.view.try_read_u8()?;
+                        tag = (tag << 8) | b as u32;
+                        if b & 0x80 == 0 {
+                            break;
+                        }
+                    }
+                }
+                tag
+            }
+        };
+        Ok(tag)
+    }
+
+    fn read_length(&mut self) -> Result<usize> {
+        let e = self.format.endianness;
+        let len = match self.format.length {
+            LengthFormat::U8 => self.view.try_read_u8()? as usize,
+            LengthFormat::U16 => self.view.try_read_endian::<u16>(e)? as usize,
+            LengthFormat::U32 => self.view.try_read_endian::<u32>(e)? as usize,
+            LengthFormat::Ber => {
+                let start = self.view.reader_index();
+                let first = self.view.try_read_u8()?;
+                if first < 0x80 {
+                    first as usize
+                } else {
+                    let n = (first & 0x7f) as usize;
+                    if n == 0 {
+                        return Err(Error::invalid(
+                            "indefinite BER length",
+                            self.view.buf,
+                            start,
+                        ));
+                    }
+                    if n > 4 {
+                        return Err(Error::new(ErrorKind::Overflow, self.view.buf, start));
+                    }
+                    let mut len = 0;
+                    for _ in 0..n {
+                        len = (len << 8) | self.view.try_read_u8()? as usize;
+                    }
+                    len
+                }
+            }
+        };
+        Ok(len)
+    }
+
+    fn read_item(&mut self) -> Result<(u32, BufView<'a>)> {
+        let start = self.view.reader_index();
+        let tag = self.read_tag()?;
+        let mut len = self.read_length()?;
+        if self.format.length_includes_header {
+            let header_len = self.view.reader_index() - start;
+            len = match len.checked_sub(header_len) {
+                Some(len) => len,
+                None => {
+                    return Err(Error::invalid(
+                        "TLV length shorter than its header",
+                        self.view.buf,
+                        start,
+                    ));
+                }
+            };
+        }
+        Ok((tag, self.view.try_read_view(len)?))
+    }
+}
+
+impl<'a> Iterator for TlvIter<'a> {
+    type Item = Result<(u32, BufView<'a>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.view.remaining() == 0 {
+            return None;
+        }
+        let start = self.view.reader_index();
+        let item = self.read_item();
+        if item.is_err() {
+            self.view.set_reader_index(start);
+            self.done = true;
+        }
+        Some(item)
+    }
+}
+
+impl std::iter::FusedIterator for TlvIter<'_> {}
+
+impl<'a> BufView<'a> {
+    /// Iterate the TLV items from the reader index to the writer index, the indexes do
+    /// not move.
+    pub fn tlv_iter(&self, format: TlvFormat) -> TlvIter<'a> {
+        let view = BufView::wrap_with(self.buf, self.reader_index, self.writer_index);
+        TlvIter::new(view, format)
+    }
+}
+
+/// The start of an item opened by [`TlvWriter::begin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use = "the item must be closed with `TlvWriter::end`"]
+pub struct TlvMark {
+    start: usize,
+    length_offset: usize,
+    value_start: usize,
+}
+
+/// Writes TLV items at the writer index of a [`BufViewMut`].
+///
+/// Items written with `begin` and `end` have their length back-patched, so they can
+/// nest. A BER length is first written in one byte, the value is moved when its final
+/// length needs more.
+#[derive(Debug)]
+pub struct TlvWriter<'v, 'b> {
+    view: &'v mut BufViewMut<'b>,
+    format: TlvFormat,
+}
+
+impl<'v, 'b> TlvWriter<'v, 'b> {
+    pub fn new(view: &'v mut BufViewMut<'b>, format: TlvFormat) -> Self {
+        TlvWriter { view, format }
+    }
+
+    /// The view written to, to write values between `begin` and `end`.
+    pub fn view(&mut self) -> &mut BufViewMut<'b> {
+        self.view
+    }
+
+    fn overflow(&self, offset: usize) -> Error {
+        Error::new(ErrorKind::Overflow, self.view.buf, offset)
+    }
+
+    fn write_tag(&mut self, tag: u32) -> Result<()> {
+        let start = self.view.writer_index();
+        let e = self.format.endianness;
+        match self.format.tag {
+            TagFormat::U8 => match u8::try_from(tag) {
+                Ok(tag) => self.view.try_write_bytes(&[tag]),
+                Err(_) => Err(self.overflow(start)),
+            },
+            TagFormat::U16 => match u16::try_from(tag) {
+                Ok(tag) => self.view.try_write_endian(tag, e),
+                Err(_) => Err(self.overflow(start)),
+            },
+            TagFormat::Ber => {
+                let skip = (tag.leading_zeros() / 8).min(3) as usize;
+                self.view.try_write_bytes(&tag.to_be_bytes()[skip..])
+            }
+        }
+    }
+
+    /// The size of the length field holding `len`.
+    fn length_size(&self, len: usize) -> usize {
+        match self.format.length {
+            LengthFormat::U8 => 1,
+            LengthFormat::U16 => 2,
+            LengthFormat::U32 => 4,
+            LengthFormat::Ber => ber_length_len(len),
+        }
+    }
+
+    /// Encode `len` into `dest`, return the used size.
+    fn encode_length(&self, len: usize, dest: &mut [u8; 5], offset: usize) -> Result<usize> {
+        let e = self.format.endianness;
+        let size = self.length_size(len);
+        match self.format.length {
+            LengthFormat::U8 => {
+                dest[0] = u8::try_from(len).map_err(|_| self.overflow(offset))?;
+            }
+            LengthFormat::U16 => {
+                let len = u16::try_from(len).map_err(|_| self.overflow(offset))?;
+                e.encode(len, &mut dest[..2]);
+            }
+            LengthFormat::U32 => {
+                let len = u32::try_from(len).map_err(|_| self.overflow(offset))?;
+                e.encode(len, &mut dest[..4]);
+            }
+            LengthFormat::Ber if size == 1 => dest[0] = len as u8,
+            LengthFormat::Ber => {
+                let len = u32::try_from(len).map_err(|_| self.overflow(offset))?;
+                dest[0] = 0x80 | (size - 1) as u8;
+                dest[1..size].copy_from_slice(&len.to_be_bytes()[5 - size..]);
+            }
+        }
+        Ok(size)
+    }
+
+    /// Write an item with a known value.
+    pub fn write(&mut self, tag: u32, value: &[u8]) -> Result<()> {
+        let mark = self.begin(tag)?;
+        self.view.try_write_bytes(value)?;
+        self.end(mark)
+    }
+
+    /// Write the tag and a placeholder length, the value is written next and `end`
+    /// back-patches the length.
+    pub fn begin(&mut self, tag: u32) -> Result<TlvMark> {
+        let start = self.view.writer_index();
+        self.write_tag(tag)?;
+        let length_offset = self.view.writer_index();
+        let placeholder = self.length_size(0);
+        self.view.try_write_bytes(&[0; 4][..placeholder])?;
+        Ok(TlvMark {
+            start,
+            length_offset,
+            value_start: self.view.writer_index(),
+        })
+    }
+
+    /// Back-patch the length of the item opened by `mark`, the value is what was written
+    /// since.
+    pub fn end(&mut self, mark: TlvMark) -> Result<()> {
+        let end = self.view.writer_index();
+        let value_len = end - mark.value_start;
+        let placeholder = mark.value_start - mark.length_offset;
+        let len = if self.format.length_includes_header {
+            // a longer BER length makes the header longer, settle the size first
+            let tag_len = mark.length_offset - mark.start;
+            let mut size = self.length_size(value_len);
+            while self.length_size(tag_len + size + value_len) != size {
+                size = self.length_size(tag_len + size + value_len);
+            }
+            tag_len + size + value_len
+        } else {
+            value_len
+        };
+        let mut dest = [0u8; 5];
+        let size = self.encode_length(len, &mut dest, mark.length_offset)?;
+        if size != placeholder {
+            let shift = size - placeholder;
+            self.view.check_get(end, shift)?;
+            self.view
+                .buf
+                .copy_within(mark.value_start..end, mark.value_start + shift);
+            self.view.writer_index = end + shift;
+        }
+        self.view.set_bytes(mark.length_offset, &dest[..size]);
+        Ok(())
+    }
+}
@@ -0,0 +1,323 @@
+use crate::hexdump::HexDump;
+use crate::BufView;
+use std::fmt;
+use std::ops::Range;
+
+/// The value recorded for a traced read.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceValue {
+    None,
+    Unsigned(u128),
+    Signed(i128),
+    Float(f64),
+    Bool(bool),
+    Bytes(Vec<u8>),
+    Text(String),
+}
+
+macro_rules! trace_value_from {
+    ($variant:ident, $as:ty, $($typ:ty),*) => {
+        $(
+            impl From<$typ> for TraceValue {
+                fn from(val: $typ) -> Self {
+                    TraceValue::$variant(val as $as)
+                }
+            }
+        )*
+    };
+}
+
+trace_value_from!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+trace_value_from!(Signed, i128, i8, i16, i32, i64, i128, isize);
+trace_value_from!(Float, f64, f32, f64);
+
+impl From<()> for TraceValue {
+    fn from(_: ()) -> Self {
+        TraceValue::None
+    }
+}
+
+impl From<bool> for TraceValue {
+    fn from(val: bool) -> Self {
+        TraceValue::Bool(val)
+    }
+}
+
+impl From<&[u8]> for TraceValue {
+    fn from(val: &[u8]) -> Self {
+        TraceValue::Bytes(val.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for TraceValue {
+    fn from(val: [u8; N]) -> Self {
+        TraceValue::Bytes(val.to_vec())
+    }
+}
+
+impl From<&str> for TraceValue {
+    fn from(val: &str) -> Self {
+        TraceValue::Text(val.to_string())
+    }
+}
+
+impl From<String> for TraceValue {
+    fn from(val: String) -> Self {
+        TraceValue::Text(val)
+    }
+}
+
+impl<T: Into<TraceValue>> From<Option<T>> for TraceValue {
+    fn from(val: Option<T>) -> Self {
+        val.map_or(TraceValue::None, Into::into)
+    }
+}
+
+impl fmt::Display for TraceValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceValue::None => Ok(()),
+            TraceValue::Unsigned(val) => write!(f, "{} (0x{:x})", val, val),
+            TraceValue::Signed(val) => write!(f, "{}", val),
+            TraceValue::Float(val) => write!(f, "{}", val),
+            TraceValue::Bool(val) => write!(f, "{}", val),
+            TraceValue::Bytes(val) => {
+                for b in val {
+                    write!(f, "{:02x}", b)?;
+                }
+                Ok(())
+            }
+            TraceValue::Text(val) => write!(f, "{:?}", val),
+        }
+    }
+}
+
+/// One traced read: the field path, the byte range it was read from and the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceRecord {
+    pub path: String,
+    pub range: Range<usize>,
+    pub value: TraceValue,
+}
+
+/// The records of a [`TracedView`], in read order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace {
+    records: Vec<TraceRecord>,
+}
+
+impl Trace {
+    pub fn records(&self) -> &[TraceRecord] {
+        &self.records
+    }
+
+    /// The last record read from the byte at `index`, if any.
+    pub fn find(&self, index: usize) -> Option<&TraceRecord> {
+        self.records.iter().rev().find(|r| r.range.contains(&index))
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Render the records as a hexdump of `buf`, each record is a header line with its
+    /// path, range and value, followed by the dump of its bytes.
+    pub fn annotate<'b>(&'b self, buf: &'b [u8]) -> TraceDump<'b> {
+        TraceDump { trace: self, buf }
+    }
+
+    /// Export the records as a JSON array of `{"path", "start", "end", "value"}` objects.
+    /// Bytes values are hex strings and non finite floats are null.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, record) in self.records.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"path\":");
+            json_string(&mut json, &record.path);
+            json.push_str(&format!(
+                ",\"start\":{},\"end\":{},\"value\":",
+                record.range.start, record.range.end
+            ));
+            match &record.value {
+                TraceValue::None => json.push_str("null"),
+                TraceValue::Unsigned(val) => json.push_str(&val.to_string()),
+                TraceValue::Signed(val) => json.push_str(&val.to_string()),
+                TraceValue::Float(val) if val.is_finite() => json.push_str(&format!("{:?}", val)),
+                TraceValue::Float(_) => json.push_str("null"),
+                TraceValue::Bool(val) => json.push_str(&val.to_string()),
+                TraceValue::Bytes(_) => json_string(&mut json, &record.value.to_string()),
+                TraceValue::Text(val) => json_string(&mut json, val),
+            }
+            json.push('}');
+        }
+        json.push(']');
+        json
+    }
+}
+
+fn json_string(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/// The annotated hexdump of a [`Trace`], see [`Trace::annotate`].
+#[derive(Debug, Clone)]
+pub struct TraceDump<'b> {
+    trace: &'b Trace,
+    buf: &'b [u8],
+}
+
+impl fmt::Display for TraceDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for record in &self.trace.records {
+            write!(
+                f,
+                "{} [0x{:x}..0x{:x}]",
+                record.path, record.range.start, record.range.end
+            )?;
+            if record.value != TraceValue::None {
+                write!(f, " = {}", record.value)?;
+            }
+            writeln!(f)?;
+            write!(f, "{}", HexDump::new(self.buf).range(record.range.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+/// A BufView recording which field was read from which byte range.
+///
+/// Tracing is opt-in, create a TracedView with [`BufView::traced`](crate::BufView::traced).
+/// Every read made through `field` pushes a [`TraceRecord`] with the field path, the byte
+/// range consumed by the read and the value read. `scope` and `index` nest the path, so
+/// the records get paths like `ip.options[2].len`. Reads made directly on `view_mut` are
+/// not traced.
+///
+/// Example
+/// ```
+/// use buf_view::BufView;
+///
+/// let buf = [0x45, 0x00, 0x00, 0x1c, 0x01, 0x04, 0x02, 0x02];
+/// let mut view = BufView::wrap(&buf).traced();
+///
+/// view.scope("ip", |v| {
+///     v.field("ver_ihl", |v| v.read_u8());
+///     v.field("tos", |v| v.read_u8());
+///     v.field("len", |v| v.read_u16());
+///     for i in 0..2 {
+///         v.scope("options", |v| {
+///             v.index(i, |v| {
+///                 v.field("kind", |v| v.read_u8());
+///                 v.field("len", |v| v.read_u8());
+///             })
+///         });
+///     }
+/// });
+///
+/// let (_, trace) = view.into_parts();
+/// let record = trace.find(7).unwrap();
+/// assert_eq!(record.path, "ip.options[1].len");
+/// assert_eq!(record.range, 7..8);
+/// assert!(trace
+///     .to_json()
+///     .starts_with(r#"[{"path":"ip.ver_ihl","start":0,"end":1,"value":69},"#));
+/// ```
+#[derive(Debug)]
+pub struct TracedView<'a> {
+    view: BufView<'a>,
+    trace: Trace,
+    path: String,
+}
+
+impl<'a> TracedView<'a> {
+    pub fn new(view: BufView<'a>) -> Self {
+        TracedView {
+            view,
+            trace: Trace::default(),
+            path: String::new(),
+        }
+    }
+
+    /// Run `read` on the view, and record the bytes it consumed and the value it
+    /// returned as the field `name` of the current scope.
+    pub fn field<T, F>(&mut self, name: &str, read: F) -> T
+    where
+        T: Clone + Into<TraceValue>,
+        F: FnOnce(&mut BufView<'a>) -> T,
+    {
+        let start = self.view.reader_index;
+        let val = read(&mut self.view);
+        let end = self.view.reader_index;
+        let mark = self.push(name);
+        self.trace.records.push(TraceRecord {
+            path: self.path.clone(),
+            range: start.min(end)..end.max(start),
+            value: val.clone().into(),
+        });
+        self.path.truncate(mark);
+        val
+    }
+
+    /// Nest the fields recorded by `f` under `name`.
+    pub fn scope<T>(&mut self, name: &str, f: impl FnOnce(&mut Self) -> T) -> T {
+        let mark = self.push(name);
+        let val = f(self);
+        self.path.truncate(mark);
+        val
+    }
+
+    /// Nest the fields recorded by `f` under the element `index` of the current scope.
+    pub fn index<T>(&mut self, index: usize, f: impl FnOnce(&mut Self) -> T) -> T {
+        let mark = self.path.len();
+        self.path.push_str(&format!("[{}]", index));
+        let val = f(self);
+        self.path.truncate(mark);
+        val
+    }
+
+    pub fn view(&self) -> &BufView<'a> {
+        &self.view
+    }
+
+    /// The traced view, reads made through it are not recorded.
+    pub fn view_mut(&mut self) -> &mut BufView<'a> {
+        &mut self.view
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    pub fn into_parts(self) -> (BufView<'a>, Trace) {
+        (self.view, self.trace)
+    }
+
+    fn push(&mut self, name: &str) -> usize {
+        let mark = self.path.len();
+        if !self.path.is_empty() {
+            self.path.push('.');
+        }
+        self.path.push_str(name);
+        mark
+    }
+}
+
+impl<'a> BufView<'a> {
+    /// Turn on tracing, see [`TracedView`].
+    pub fn traced(self) -> TracedView<'a> {
+        TracedView::new(self)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{BufView, BufViewMut};
+use std::fmt;
+use std::str::FromStr;
+
+/// A UUID, stored in the RFC 4122 big endian byte order.
+///
+/// Displayed hyphenated in lowercase, parsed hyphenated in either case, optionally in
+/// braces like `{...}` registry GUIDs.
+///
+/// Example
+/// ```
+/// use buf_view::{BufView, BufViewMut, Uuid};
+///
+/// // the GPT EFI system partition type
+/// let esp: Uuid = "C12A7328-F81F-11D2-BA4B-00A0C93EC93B".parse().unwrap();
+/// let mut buf = [0u8; 32];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// buf_view.write_guid_mixed_endian(esp);
+/// buf_view.write_uuid(esp);
+/// assert_eq!(&buf[..4], &[0x28, 0x73, 0x2a, 0xc1]);
+/// assert_eq!(&buf[16..20], &[0xc1, 0x2a, 0x73, 0x28]);
+///
+/// let mut buf_view = BufView::wrap(&buf);
+/// assert_eq!(buf_view.read_guid_mixed_endian(), esp);
+/// assert_eq!(buf_view.read_uuid().to_string(), "c12a7328-f81f-11d2-ba4b-00a0c93ec93b");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    pub const NIL: Uuid = Uuid([0; 16]);
+
+    /// The UUID of `bytes` in the RFC 4122 order.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Uuid(bytes)
+    }
+
+    /// The UUID of `bytes` in the Microsoft GUID order, the first three fields little
+    /// endian.
+    pub const fn from_bytes_mixed_endian(bytes: [u8; 16]) -> Self {
+        Uuid(swap_fields(bytes))
+    }
+
+    pub const fn from_u128(val: u128) -> Self {
+        Uuid(val.to_be_bytes())
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// The bytes in the Microsoft GUID order.
+    pub const fn to_bytes_mixed_endian(&self) -> [u8; 16] {
+        swap_fields(self.0)
+    }
+
+    pub const fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    pub fn is_nil(&self) -> bool {
+        *self == Uuid::NIL
+    }
+
+    /// The version number, the high nibble of byte 6.
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+}
+
+/// Swap the byte order of the 32, 16 and 16-bit leading fields.
+const fn swap_fields(b: [u8; 16]) -> [u8; 16] {
+    [
+        b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13],
+        b[14], b[15],
+    ]
+}
+
+impl From<[u8; 16]> for Uuid {
+    fn from(bytes: [u8; 16]) -> Self {
+        Uuid(bytes)
+    }
+}
+
+impl From<Uuid> for [u8; 16] {
+    fn from(uuid: Uuid) -> Self {
+        uuid.0
+    }
+}
+
+/// The error parsing a [`Uuid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseUuidError(());
+
+impl fmt::Display for ParseUuidError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid UUID syntax")
+    }
+}
+
+impl std::error::Error for ParseUuidError {}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, b) in self.0.iter().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                f.write_str("-")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = ParseUuidError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = match s.strip_prefix('{') {
+            Some(inner) => inner.strip_suffix('}').ok_or(ParseUuidError(()))?,
+            None => s,
+        };
+        let s = s.as_bytes();
+        if s.len() != 36 {
+            return Err(ParseUuidError(()));
+        }
+        let mut bytes = [0u8; 16];
+        let mut pos = 0;
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            if matches!(i, 4 | 6 | 8 | 10) {
+                if s[pos] != b'-' {
+                    return Err(ParseUuidError(()));
+                }
+                pos += 1;
+            }
+            let hi = (s[pos] as char).to_digit(16).ok_or(ParseUuidError(()))?;
+            let lo = (s[pos + 1] as char)
+                .to_digit(16)
+                .ok_or(ParseUuidError(()))?;
+            *byte = (hi << 4 | lo) as u8;
+            pos += 2;
+        }
+        Ok(Uuid(bytes))
+    }
+}
+
+macro_rules! uuid_read_methods {
+    ($name:ident) => {
+        impl $name<'_> {
+            /// Read a UUID in the RFC 4122 big endian order.
+            pub fn read_uuid(&mut self) -> Uuid {
+                Uuid(self.read_array())
+            }
+
+            /// Read a Microsoft GUID, the first three fields little endian.
+            pub fn read_guid_mixed_endian(&mut self) -> Uuid {
+                Uuid::from_bytes_mixed_endian(self.read_array())
+            }
+
+            pub fn get_uuid(&self, index: usize) -> Uuid {
+                Uuid(self.get_array(index))
+            }
+
+            pub fn get_guid_mixed_endian(&self, index: usize) -> Uuid {
+                Uuid::from_bytes_mixed_endian(self.get_array(index))
+            }
+        }
+    };
+}
+
+uuid_read_methods!(BufView);
+uuid_read_methods!(BufViewMut);
+
+impl BufViewMut<'_> {
+    pub fn write_uuid(&mut self, val: Uuid) {
+        self.write_array(&val.0);
+    }
+
+    pub fn write_guid_mixed_endian(&mut self, val: Uuid) {
+        self.write_array(&val.to_bytes_mixed_endian());
+    }
+
+    pub fn set_uuid(&mut self, index: usize, val: Uuid) {
+        self.set_array(index, &val.0);
+    }
+
+    pub fn set_guid_mixed_endian(&mut self, index: usize, val: Uuid) {
+        self.set_array(index, &val.to_bytes_mixed_endian());
+    }
+}
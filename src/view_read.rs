@@ -0,0 +1,105 @@
+use crate::macros::view_read_do;
+
+/// The sequential read surface shared by the readable views.
+///
+/// ViewRead is implemented by [`BufView`](crate::BufView), [`BufViewMut`](crate::BufViewMut)
+/// and by wrappers such as [`HashingView`](crate::HashingView), so a decoder written
+/// against it runs on any of them. Only `read_bytes`, `remaining` and `reader_index` need
+/// to be implemented, every primitive method is built on `read_bytes`.
+pub trait ViewRead {
+    fn read_bytes(&mut self, dest: &mut [u8]) -> usize;
+
+    fn remaining(&self) -> usize;
+
+    fn reader_index(&self) -> usize;
+
+    fn read_u8(&mut self) -> u8 {
+        let mut bytes = [0u8; 1];
+        self.read_bytes(&mut bytes);
+        bytes[0]
+    }
+
+    fn read_i8(&mut self) -> i8 {
+        self.read_u8() as i8
+    }
+
+    fn read_u16(&mut self) -> u16 {
+        view_read_do!(self, u16, be)
+    }
+
+    fn read_u16_le(&mut self) -> u16 {
+        view_read_do!(self, u16, le)
+    }
+
+    fn read_i16(&mut self) -> i16 {
+        view_read_do!(self, i16, be)
+    }
+
+    fn read_i16_le(&mut self) -> i16 {
+        view_read_do!(self, i16, le)
+    }
+
+    fn read_u32(&mut self) -> u32 {
+        view_read_do!(self, u32, be)
+    }
+
+    fn read_u32_le(&mut self) -> u32 {
+        view_read_do!(self, u32, le)
+    }
+
+    fn read_i32(&mut self) -> i32 {
+        view_read_do!(self, i32, be)
+    }
+
+    fn read_i32_le(&mut self) -> i32 {
+        view_read_do!(self, i32, le)
+    }
+
+    fn read_u64(&mut self) -> u64 {
+        view_read_do!(self, u64, be)
+    }
+
+    fn read_u64_le(&mut self) -> u64 {
+        view_read_do!(self, u64, le)
+    }
+
+    fn read_i64(&mut self) -> i64 {
+        view_read_do!(self, i64, be)
+    }
+
+    fn read_i64_le(&mut self) -> i64 {
+        view_read_do!(self, i64, le)
+    }
+
+    fn read_u128(&mut self) -> u128 {
+        view_read_do!(self, u128, be)
+    }
+
+    fn read_u128_le(&mut self) -> u128 {
+        view_read_do!(self, u128, le)
+    }
+
+    fn read_i128(&mut self) -> i128 {
+        view_read_do!(self, i128, be)
+    }
+
+    fn read_i128_le(&mut self) -> i128 {
+        view_read_do!(self, i128, le)
+    }
+
+    fn read_f32(&mut self) -> f32 {
+        view_read_do!(self, f32, be)
+    }
+
+    fn read_f32_le(&mut self) -> f32 {
+        view_read_do!(self, f32, le)
+    }
+
+    fn read_f64(&mut self) -> f64 {
+        view_read_do!(self, f64, be)
+    }
+
+    fn read_f64_le(&mut self) -> f64 {
+        view_read_do!(self, f64, le)
+    }
+}
@@ -0,0 +1,213 @@
+/// The write/set surface shared by the writable views.
+///
+/// ViewWrite is implemented by [`BufViewMut`](crate::BufViewMut), which stores the data,
+/// and by [`SizeCounter`](crate::SizeCounter), which only counts it. An encoder written
+/// against `ViewWrite` can run once on a SizeCounter to learn the output size, then run
+/// again on a BufViewMut wrapping an exactly sized buffer.
+///
+/// Only `write_bytes`, `set_bytes` and `writer_index` need to be implemented, every
+/// primitive method is built on top of them.
+///
+/// Example
+/// ```
+/// use buf_view::{BufViewMut, SizeCounter, ViewWrite};
+///
+/// fn encode<W: ViewWrite>(w: &mut W, name: &str) {
+///     let start = w.writer_index();
+///     w.write_u16(0);
+///     w.write_bytes(name.as_bytes());
+///     w.set_u16(start, name.len() as u16);
+/// }
+///
+/// let mut counter = SizeCounter::new();
+/// encode(&mut counter, "buf-view");
+///
+/// let mut buf = vec![0u8; counter.size()];
+/// let mut buf_view = BufViewMut::wrap(&mut buf);
+/// encode(&mut buf_view, "buf-view");
+/// assert_eq!(buf_view.writer_index(), 10);
+/// assert_eq!(buf_view.read_u16(), 8);
+/// ```
+pub trait ViewWrite {
+    fn write_bytes(&mut self, src: &[u8]);
+
+    fn set_bytes(&mut self, index: usize, src: &[u8]);
+
+    fn writer_index(&self) -> usize;
+
+    fn write_u8(&mut self, val: u8) {
+        self.write_bytes(&[val]);
+    }
+
+    fn write_i8(&mut self, val: i8) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_u16(&mut self, val: u16) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_u16_le(&mut self, val: u16) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_i16(&mut self, val: i16) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_i16_le(&mut self, val: i16) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, val: u32) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_u32_le(&mut self, val: u32) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_i32(&mut self, val: i32) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_i32_le(&mut self, val: i32) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, val: u64) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_u64_le(&mut self, val: u64) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_i64(&mut self, val: i64) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_i64_le(&mut self, val: i64) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, val: u128) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_u128_le(&mut self, val: u128) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_i128(&mut self, val: i128) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_i128_le(&mut self, val: i128) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_f32(&mut self, val: f32) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_f32_le(&mut self, val: f32) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn write_f64(&mut self, val: f64) {
+        self.write_bytes(&val.to_be_bytes());
+    }
+
+    fn write_f64_le(&mut self, val: f64) {
+        self.write_bytes(&val.to_le_bytes());
+    }
+
+    fn set_u8(&mut self, index: usize, val: u8) {
+        self.set_bytes(index, &[val]);
+    }
+
+    fn set_i8(&mut self, index: usize, val: i8) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_u16(&mut self, index: usize, val: u16) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_u16_le(&mut self, index: usize, val: u16) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_i16(&mut self, index: usize, val: i16) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_i16_le(&mut self, index: usize, val: i16) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_u32(&mut self, index: usize, val: u32) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_u32_le(&mut self, index: usize, val: u32) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_i32(&mut self, index: usize, val: i32) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_i32_le(&mut self, index: usize, val: i32) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_u64(&mut self, index: usize, val: u64) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_u64_le(&mut self, index: usize, val: u64) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_i64(&mut self, index: usize, val: i64) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_i64_le(&mut self, index: usize, val: i64) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_u128(&mut self, index: usize, val: u128) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_u128_le(&mut self, index: usize, val: u128) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_i128(&mut self, index: usize, val: i128) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_i128_le(&mut self, index: usize, val: i128) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_f32(&mut self, index: usize, val: f32) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_f32_le(&mut self, index: usize, val: f32) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+
+    fn set_f64(&mut self, index: usize, val: f64) {
+        self.set_bytes(index, &val.to_be_bytes());
+    }
+
+    fn set_f64_le(&mut self, index: usize, val: f64) {
+        self.set_bytes(index, &val.to_le_bytes());
+    }
+}
@@ -0,0 +1,78 @@
+use buf_view::{BufView, BufViewMut, Eui64, MacAddr};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+#[test]
+fn test_addr() {
+    let v4 = SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 8080);
+    let v6 = SocketAddrV6::new("2001:db8::1".parse().unwrap(), 443, 0, 0);
+    let mac = MacAddr::new([0x02, 0x00, 0x5e, 0x10, 0x00, 0x01]);
+
+    let mut buf = [0u8; 64];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_ipv4(Ipv4Addr::LOCALHOST);
+    buf_view.write_ipv6(Ipv6Addr::LOCALHOST);
+    buf_view.write_socket_addr_v4(v4);
+    buf_view.write_socket_addr_v6(v6);
+    buf_view.write_mac(mac);
+    buf_view.write_eui64(Eui64::from_mac(mac));
+    assert_eq!(buf_view.writer_index(), 58);
+    buf_view.set_ipv4(58, Ipv4Addr::BROADCAST);
+    assert_eq!(&buf[20..26], &[10, 0, 0, 1, 0x1f, 0x90]);
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.read_ipv4(), Ipv4Addr::LOCALHOST);
+    assert_eq!(buf_view.read_ipv6(), Ipv6Addr::LOCALHOST);
+    assert_eq!(buf_view.read_socket_addr_v4(), v4);
+    assert_eq!(buf_view.read_socket_addr_v6(), v6);
+    assert_eq!(buf_view.read_mac(), mac);
+    assert_eq!(
+        buf_view.read_eui64().octets(),
+        [0x02, 0x00, 0x5e, 0xff, 0xfe, 0x10, 0x00, 0x01]
+    );
+    assert_eq!(buf_view.get_socket_addr_v4(20), v4);
+    assert_eq!(buf_view.get_socket_addr_v6(26), v6);
+    assert_eq!(buf_view.get_mac(44), mac);
+    assert_eq!(buf_view.get_ipv4(58), Ipv4Addr::BROADCAST);
+
+    let mut buf = [0u8; 18];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.set_socket_addr_v6(0, v6);
+    buf_view.set_mac(0, MacAddr::BROADCAST);
+    assert_eq!(
+        buf_view.get_ipv6(0).segments()[..4],
+        [0xffff, 0xffff, 0xffff, 0]
+    );
+    assert_eq!(buf_view.get_socket_addr_v6(0).port(), 443);
+}
+
+#[test]
+fn test_mac_addr() {
+    let mac: MacAddr = "00:1B:63:84:45:e6".parse().unwrap();
+    assert_eq!(mac.octets(), [0x00, 0x1b, 0x63, 0x84, 0x45, 0xe6]);
+    assert_eq!(mac.to_string(), "00:1b:63:84:45:e6");
+    assert_eq!("00-1b-63-84-45-e6".parse(), Ok(mac));
+    assert!(!mac.is_multicast() && !mac.is_local());
+    assert!(MacAddr::BROADCAST.is_broadcast() && MacAddr::BROADCAST.is_multicast());
+    assert!("01:00:5e:00:00:fb"
+        .parse::<MacAddr>()
+        .unwrap()
+        .is_multicast());
+
+    for bad in [
+        "",
+        "00:1b:63:84:45",
+        "00:1b:63:84:45:e6:00",
+        "00:1b:63:84:45:e",
+        "00:1b:63:84:45:+e",
+        "00:1b-63:84:45:e6",
+        "001b.6384.45e6",
+    ] {
+        assert!(bad.parse::<MacAddr>().is_err(), "{}", bad);
+    }
+
+    let eui: Eui64 = "00-1b-63-ff-fe-84-45-e6".parse().unwrap();
+    assert_eq!(eui, Eui64::from_mac(mac));
+    assert_eq!(eui.to_string(), "00:1b:63:ff:fe:84:45:e6");
+    assert_eq!(eui.to_interface_id()[0], 0x02);
+    assert!("00:1b:63:84:45:e6".parse::<Eui64>().is_err());
+}
@@ -0,0 +1,27 @@
+use buf_view::{BufView, BufViewMut};
+
+#[test]
+fn test_array() {
+    let mut buf = [0u8; 32];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_array(b"RIFF");
+    buf_view.write_u32_le(24);
+    buf_view.write_array(&[0xaa; 16]);
+    buf_view.set_array(8, &[1, 2]);
+    assert_eq!(buf_view.writer_index(), 24);
+    assert_eq!(buf_view.peek_array::<4>(), *b"RIFF");
+    assert_eq!(buf_view.read_array::<4>(), *b"RIFF");
+    assert_eq!(buf_view.get_array::<3>(8), [1, 2, 0xaa]);
+    assert_eq!(buf_view.read_array::<0>(), []);
+    assert_eq!(buf_view.reader_index(), 4);
+
+    let mut buf_view = BufView::wrap(&buf[..24]);
+    assert_eq!(buf_view.read_array_ref::<4>(), b"RIFF");
+    assert_eq!(u32::from_le_bytes(buf_view.peek_array()), 24);
+    assert_eq!(buf_view.read_u32_le(), 24);
+    let hash: &[u8; 16] = buf_view.read_array_ref();
+    assert_eq!(hash[..3], [1, 2, 0xaa]);
+    assert_eq!(hash[15], 0xaa);
+    assert_eq!(buf_view.remaining(), 0);
+    assert_eq!(buf_view.get_array::<2>(22), [0xaa, 0xaa]);
+}
@@ -0,0 +1,43 @@
+use buf_view::{BigEndian, BufView, BufViewMut, LittleEndian};
+
+#[test]
+fn test_array_iter() {
+    let mut buf = [0u8; 64];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_u8(7);
+    for i in 0..10u32 {
+        buf_view.write_u32_le(i * 1000);
+    }
+    buf_view.write_f64(-1.5);
+    buf_view.write_f64(2.5);
+
+    let mut buf_view = BufView::wrap(&buf[..57]);
+    buf_view.read_u8();
+    let mut iter = buf_view.read_array_iter::<u32, LittleEndian>(10);
+    assert_eq!(buf_view.reader_index(), 41);
+    assert_eq!(iter.len(), 10);
+    assert_eq!(iter.clone().sum::<u32>(), 45000);
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.nth(2), Some(3000));
+    assert_eq!(iter.next_back(), Some(9000));
+    assert_eq!(iter.nth_back(1), Some(7000));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.clone().rev().collect::<Vec<_>>(), [6000, 5000, 4000]);
+    assert_eq!(iter.clone().last(), Some(6000));
+    assert_eq!(iter.nth(3), None);
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+
+    let floats = buf_view.read_array_iter::<f64, BigEndian>(2);
+    assert_eq!(floats.collect::<Vec<_>>(), [-1.5, 2.5]);
+    assert_eq!(buf_view.remaining(), 0);
+    assert_eq!(buf_view.read_array_iter::<i128, BigEndian>(0).count(), 0);
+
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.set_writer_index(9);
+    buf_view.read_u8();
+    let halves = buf_view.read_array_iter::<i16, BigEndian>(4);
+    assert_eq!(halves.as_bytes(), &[0, 0, 0, 0, 0xe8, 3, 0, 0]);
+    assert_eq!(halves.collect::<Vec<_>>(), [0, 0, -6141, 0]);
+    assert_eq!(buf_view.remaining(), 0);
+}
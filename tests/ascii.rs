@@ -0,0 +1,107 @@
+use buf_view::{BufView, BufViewMut, ErrorKind};
+
+#[test]
+fn test_read_ascii() {
+    let mut buf_view = BufView::wrap(b"Content-Length: 18446744073709551615\r\n");
+    buf_view.read_until(b':');
+    buf_view.skip_while(|b| b == b' ');
+    assert_eq!(buf_view.read_ascii_u64(10), Ok(u64::MAX));
+    assert_eq!(buf_view.read_line(), Some(&b""[..]));
+
+    let mut buf_view = BufView::wrap(b"18446744073709551616");
+    let err = buf_view.read_ascii_u64(10).unwrap_err();
+    assert_eq!(*err.kind(), ErrorKind::Overflow);
+    assert_eq!(buf_view.reader_index(), 0);
+
+    let mut buf_view = BufView::wrap(b"x");
+    let err = buf_view.read_ascii_u64(16).unwrap_err();
+    assert_eq!(*err.kind(), ErrorKind::Invalid("expected digits"));
+
+    let mut buf_view = BufView::wrap(b"DeadBeefg");
+    assert_eq!(buf_view.read_ascii_u64(16), Ok(0xdeadbeef));
+    assert_eq!(buf_view.remaining(), 1);
+
+    let mut buf_view = BufView::wrap(b"-9223372036854775808 +12 -9223372036854775809 -");
+    assert_eq!(buf_view.read_ascii_i64(10), Ok(i64::MIN));
+    buf_view.read_u8();
+    assert_eq!(buf_view.read_ascii_i64(10), Ok(12));
+    buf_view.read_u8();
+    assert_eq!(
+        *buf_view.read_ascii_i64(10).unwrap_err().kind(),
+        ErrorKind::Overflow
+    );
+    buf_view.skip_while(|b| b != b' ');
+    buf_view.read_u8();
+    assert!(buf_view.read_ascii_i64(10).is_err());
+    assert_eq!(buf_view.remaining(), 1);
+
+    for (text, val, rest) in [
+        ("3.25,", 3.25, 1),
+        ("-0.5e-2", -0.005, 0),
+        (".5e", 0.5, 1),
+        ("7.e+2x", 700.0, 1),
+        ("1e", 1.0, 1),
+        ("0.1", 0.1, 0),
+        ("4035.1234,N", 4035.1234, 2),
+    ] {
+        let mut buf_view = BufView::wrap(text.as_bytes());
+        assert_eq!(buf_view.parse_ascii_f64(), Ok(val), "{}", text);
+        assert_eq!(buf_view.remaining(), rest, "{}", text);
+    }
+    for text in ["", "-", ".", "-.e5", "e5"] {
+        let mut buf_view = BufView::wrap(text.as_bytes());
+        assert!(buf_view.parse_ascii_f64().is_err(), "{}", text);
+    }
+    let mut buf_view = BufView::wrap(b"1e400");
+    assert_eq!(
+        *buf_view.parse_ascii_f64().unwrap_err().kind(),
+        ErrorKind::Overflow
+    );
+}
+
+#[test]
+fn test_write_ascii() {
+    let mut buf = [0u8; 64];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    for val in [0, 7, 10, 99, 100, 12345, u64::MAX] {
+        buf_view.write_ascii_u64(val);
+        buf_view.write_u8(b',');
+    }
+    assert_eq!(
+        buf_view.as_slice(),
+        b"0,7,10,99,100,12345,18446744073709551615,"
+    );
+
+    let mut buf = [0u8; 64];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_ascii_i64(i64::MIN);
+    buf_view.write_ascii_i64(0);
+    buf_view.write_ascii_hex(0);
+    buf_view.write_ascii_hex(u64::MAX);
+    assert_eq!(
+        buf_view.as_slice(),
+        b"-922337203685477580800ffffffffffffffff"
+    );
+
+    // a tar size field is 11 octal digits and a NUL
+    let mut buf = [0u8; 12];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_ascii_padded(1024, 8, 11).unwrap();
+    buf_view.write_u8(0);
+    assert_eq!(&buf, b"00000002000\0");
+
+    let mut buf = [b' '; 8];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    assert_eq!(
+        *buf_view
+            .write_ascii_padded(0o1000, 8, 3)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::Overflow
+    );
+    assert!(buf_view.set_ascii_padded(6, 1, 10, 3).is_err());
+    buf_view.set_ascii_padded(1, 0xff, 2, 8).unwrap_err();
+    buf_view.set_ascii_padded(0, 0xff, 2, 8).unwrap();
+    assert_eq!(buf_view.writer_index(), 0);
+    assert_eq!(&buf, b"11111111");
+}
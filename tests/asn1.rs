@@ -0,0 +1,176 @@
+#![cfg(feature = "asn1")]
+
+use buf_view::asn1::{Asn1Reader, Asn1Writer, Class, Mode, Tag};
+use buf_view::{BufView, BufViewMut, ErrorKind};
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn test_der_round_trip() {
+    let t = UNIX_EPOCH + Duration::new(1_700_000_000, 120_000_000);
+    let mut buf = [0u8; 512];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut writer = Asn1Writer::new(&mut buf_view);
+    let seq = writer.begin(Tag::SEQUENCE).unwrap();
+    let version = writer.begin(Tag::context(0, true)).unwrap();
+    writer.write_i64(2).unwrap();
+    writer.end(version).unwrap();
+    writer.write_u64(u64::MAX).unwrap();
+    writer.write_unsigned(&[0, 0, 0x80, 1]).unwrap();
+    writer.write_bool(true).unwrap();
+    writer.write_null().unwrap();
+    writer.write_bit_string(3, &[0xa5, 0xff]).unwrap();
+    writer.write_octet_string(&[0xcc; 130]).unwrap();
+    writer.write_utf8_string("héllo").unwrap();
+    writer.write_utc_time(t).unwrap();
+    writer.write_generalized_time(t).unwrap();
+    writer.write(Tag::context(40, false), b"x").unwrap();
+    writer.end(seq).unwrap();
+    assert!(writer.write_oid(&[3, 1]).is_err());
+    let len = buf_view.writer_index();
+    // the 130 bytes string makes the sequence length take 2 bytes, its content moved
+    assert_eq!(&buf[..5], &[0x30, 0x81, (len - 3) as u8, 0xa0, 3]);
+
+    let mut reader = Asn1Reader::new(BufView::wrap(&buf[..len]), Mode::Der);
+    let mut seq = reader.read_sequence().unwrap();
+    assert!(reader.next().is_none());
+    let mut version = seq
+        .read_optional(Tag::context(0, true))
+        .unwrap()
+        .unwrap()
+        .children()
+        .unwrap();
+    assert_eq!(version.read_element().unwrap().as_i64(), Ok(2));
+    assert_eq!(seq.read_optional(Tag::context(1, true)), Ok(None));
+    let max = seq.read_expect(Tag::INTEGER).unwrap();
+    assert_eq!(max.content().len(), 9);
+    assert_eq!(max.as_u64(), Ok(u64::MAX));
+    assert_eq!(max.as_i64().unwrap_err().kind(), &ErrorKind::Overflow);
+    let int = seq.read_element().unwrap();
+    assert_eq!(int.as_integer_bytes(), Ok(&[0, 0x80, 1][..]));
+    assert_eq!(seq.read_element().unwrap().as_bool(), Ok(true));
+    assert_eq!(seq.read_element().unwrap().as_null(), Ok(()));
+    let bits = seq.read_element().unwrap().as_bit_string().unwrap();
+    assert_eq!((bits.bytes, bits.bit_len()), (&[0xa5, 0xf8][..], 13));
+    assert_eq!(
+        (bits.bit(0), bits.bit(1), bits.bit(13)),
+        (Some(true), Some(false), None)
+    );
+    assert_eq!(
+        seq.read_element().unwrap().as_octet_string().unwrap().len(),
+        130
+    );
+    assert_eq!(
+        seq.read_expect(Tag::UTF8_STRING).unwrap().as_str(),
+        Ok("héllo")
+    );
+    let utc = seq.read_expect(Tag::UTC_TIME).unwrap();
+    assert_eq!(utc.content(), b"231114221320Z");
+    assert_eq!(
+        utc.as_utc_time(),
+        Ok(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    );
+    let gen = seq.read_expect(Tag::GENERALIZED_TIME).unwrap();
+    assert_eq!(gen.content(), b"20231114221320.12Z");
+    assert_eq!(gen.as_generalized_time(), Ok(t));
+    let tagged = seq.read_element().unwrap();
+    assert_eq!(
+        (tagged.tag.class, tagged.tag.number),
+        (Class::ContextSpecific, 40)
+    );
+    assert_eq!(tagged.as_bytes(), &[0x9f, 40, 1, b'x']);
+    assert!(seq.is_empty());
+
+    let mut buf = [0u8; 16];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    Asn1Writer::new(&mut buf_view)
+        .write_oid(&[2, 999, 3])
+        .unwrap();
+    let oid = BufView::wrap(&buf[..6]);
+    let oid = Asn1Reader::new(oid, Mode::Der).read_element().unwrap();
+    assert_eq!(oid.content(), &[0x88, 0x37, 0x03]);
+    assert_eq!(oid.as_oid().unwrap().to_string(), "2.999.3");
+}
+
+#[test]
+fn test_ber_and_der_checks() {
+    // an indefinite length sequence holding an indefinite length [1]
+    let ber = [
+        0x30, 0x80, 0xa1, 0x80, 0x01, 0x01, 0x05, 0, 0, 0x02, 0x01, 0x7f, 0, 0,
+    ];
+    let mut reader = Asn1Reader::new(BufView::wrap(&ber), Mode::Ber);
+    let seq = reader.read_element().unwrap();
+    assert_eq!((seq.content().len(), seq.as_bytes().len()), (10, 14));
+    let children: Vec<_> = seq.children().unwrap().collect::<Result<_, _>>().unwrap();
+    assert_eq!(children.len(), 2);
+    let inner = children[0].children().unwrap().next().unwrap().unwrap();
+    assert_eq!(inner.as_bool(), Ok(true));
+    assert_eq!(children[1].as_i64(), Ok(127));
+
+    let check = |bytes: &[u8], mode| {
+        let mut reader = Asn1Reader::new(BufView::wrap(bytes), mode);
+        let element = reader.read_element()?;
+        match element.tag {
+            Tag::BOOLEAN => element.as_bool().map(|_| ()),
+            Tag::INTEGER => element.as_i64().map(|_| ()),
+            Tag::BIT_STRING => element.as_bit_string().map(|_| ()),
+            Tag::UTC_TIME => element.as_utc_time().map(|_| ()),
+            Tag::OID => element.as_oid().map(|_| ()),
+            _ => Ok(()),
+        }
+    };
+    let kind = |bytes: &[u8], mode| check(bytes, mode).unwrap_err().kind().clone();
+    assert_eq!(
+        kind(&ber, Mode::Der),
+        ErrorKind::Invalid("indefinite length in DER")
+    );
+    assert_eq!(
+        kind(&[0x04, 0x81, 0x01, 0], Mode::Der),
+        ErrorKind::Invalid("non-minimal DER length")
+    );
+    assert!(check(&[0x04, 0x81, 0x01, 0], Mode::Ber).is_ok());
+    assert_eq!(
+        kind(&[0x01, 0x01, 0x01], Mode::Der),
+        ErrorKind::Invalid("non-DER ASN.1 BOOLEAN")
+    );
+    assert_eq!(check(&[0x01, 0x01, 0x01], Mode::Ber), Ok(()));
+    assert_eq!(
+        kind(&[0x02, 0x02, 0, 1], Mode::Ber),
+        ErrorKind::Invalid("non-minimal ASN.1 INTEGER")
+    );
+    assert_eq!(
+        kind(&[0x03, 0x02, 1, 1], Mode::Der),
+        ErrorKind::Invalid("non-DER ASN.1 BIT STRING padding")
+    );
+    assert_eq!(
+        kind(&[0x06, 0x02, 0x80, 1], Mode::Ber),
+        ErrorKind::Invalid("non-minimal ASN.1 OID")
+    );
+    assert_eq!(
+        kind(&[0x1f, 0x05, 0], Mode::Ber),
+        ErrorKind::Invalid("non-minimal ASN.1 tag")
+    );
+    // UTCTime without seconds, and with an offset
+    let utc = b"\x17\x0b2311142213Z";
+    assert_eq!(
+        kind(utc, Mode::Der),
+        ErrorKind::Invalid("bad ASN.1 UTCTime")
+    );
+    assert_eq!(check(utc, Mode::Ber), Ok(()));
+    let offset = b"\x17\x0f2311142213-0130";
+    let mut reader = Asn1Reader::new(BufView::wrap(offset), Mode::Ber);
+    let t = reader.read_element().unwrap().as_utc_time().unwrap();
+    assert_eq!(
+        t,
+        UNIX_EPOCH + Duration::from_secs(1_700_000_000 - 20 + 5400)
+    );
+
+    let err = check(&[0x30, 0x03, 0x02, 0x01], Mode::Der).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+    let mut nested = vec![0x30, 0x80];
+    for _ in 0..64 {
+        nested.extend_from_slice(&[0x30, 0x80]);
+    }
+    let err = check(&nested, Mode::Ber).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("ASN.1 nesting too deep"));
+    assert_eq!(err.offset(), 128);
+}
@@ -0,0 +1,65 @@
+use buf_view::{BufView, BufViewMut};
+
+#[test]
+fn test_bulk() {
+    let mut buf = [0u8; 64];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_u16_slice(&[0x0102, 0x0304]);
+    buf_view.write_u32_slice_le(&[0x0506_0708, 0x090a_0b0c]);
+    buf_view.write_i64_slice(&[-2]);
+    buf_view.write_f32_slice_le(&[1.5, -0.25]);
+    buf_view.write_u128_slice(&[]);
+    buf_view.write_f64_slice(&[0.1]);
+    assert_eq!(buf_view.writer_index(), 36);
+    assert_eq!(&buf[..12], &[1, 2, 3, 4, 8, 7, 6, 5, 0x0c, 0x0b, 0x0a, 9]);
+
+    let mut buf_view = BufView::wrap(&buf[..36]);
+    let mut halves = [0u16; 2];
+    buf_view.read_u16_into(&mut halves);
+    assert_eq!(halves, [0x0102, 0x0304]);
+    let mut words = [0u32; 2];
+    buf_view.read_u32_into_le(&mut words);
+    assert_eq!(words, [0x0506_0708, 0x090a_0b0c]);
+    let mut longs = [0i64; 1];
+    buf_view.read_i64_into(&mut longs);
+    assert_eq!(longs, [-2]);
+    let mut floats = [0f32; 2];
+    buf_view.read_f32_into_le(&mut floats);
+    assert_eq!(floats, [1.5, -0.25]);
+    buf_view.read_u128_into(&mut []);
+    let mut doubles = [0f64; 1];
+    buf_view.read_f64_into(&mut doubles);
+    assert_eq!(doubles, [0.1]);
+    assert_eq!(buf_view.remaining(), 0);
+
+    // bulk and single reads agree on a large array
+    let vals: Vec<u32> = (0..1000u32).map(|i| i.wrapping_mul(0x9e37_79b9)).collect();
+    let mut buf = vec![0u8; 4000];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_u32_slice_le(&vals);
+    let mut buf_view = BufView::wrap(&buf);
+    let mut read = vec![0u32; 1000];
+    buf_view.read_u32_into_le(&mut read);
+    assert_eq!(read, vals);
+    let mut buf_view = BufView::wrap(&buf);
+    assert!(vals.iter().all(|v| buf_view.read_u32_le() == *v));
+
+    // converting in place is the same as reading with the other byte order
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.swap_bytes_in_place_u32(0..4000);
+    let mut buf_view = BufView::wrap(&buf);
+    buf_view.read_u32_into(&mut read);
+    assert_eq!(read, vals);
+}
+
+#[test]
+fn test_swap_bytes_in_place() {
+    let mut buf: Vec<u8> = (0..32).collect();
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.swap_bytes_in_place_u16(0..4);
+    buf_view.swap_bytes_in_place_u64(4..12);
+    buf_view.swap_bytes_in_place_u128(16..32);
+    assert_eq!(&buf[..12], &[1, 0, 3, 2, 11, 10, 9, 8, 7, 6, 5, 4]);
+    assert_eq!(buf[16], 31);
+    assert_eq!(buf[31], 16);
+}
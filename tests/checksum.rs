@@ -0,0 +1,70 @@
+use buf_view::checksum::{
+    Adler32, Checksum, Crc16Arc, Crc16CcittFalse, Crc16Modbus, Crc16X25, Crc32, Crc32c,
+    InternetChecksum,
+};
+use buf_view::{BufView, BufViewMut};
+
+const CHECK: &[u8] = b"123456789";
+
+#[test]
+fn test_checksum() {
+    assert_eq!(Crc32::checksum(CHECK), 0xcbf43926);
+    assert_eq!(Crc32c::checksum(CHECK), 0xe3069283);
+    assert_eq!(Crc16Arc::checksum(CHECK), 0xbb3d);
+    assert_eq!(Crc16Modbus::checksum(CHECK), 0x4b37);
+    assert_eq!(Crc16X25::checksum(CHECK), 0x906e);
+    assert_eq!(Crc16CcittFalse::checksum(CHECK), 0x29b1);
+    assert_eq!(Adler32::checksum(CHECK), 0x091e01de);
+    assert_eq!(Adler32::checksum(b"Wikipedia"), 0x11e60398);
+    assert_eq!(Crc32::checksum(b""), 0);
+
+    // incremental updates of every split must match the one shot result
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 31 + 7) as u8).collect();
+    for split in [0, 1, 3, 7, 8, 9, 500, 999, 1000] {
+        let mut crc32 = Crc32::new();
+        let mut crc32c = Crc32c::new();
+        let mut adler = Adler32::new();
+        let mut inet = InternetChecksum::new();
+        for part in [&data[..split], &data[split..]] {
+            crc32.update(part);
+            crc32c.update(part);
+            adler.update(part);
+            inet.update(part);
+        }
+        assert_eq!(crc32.finalize(), Crc32::checksum(&data));
+        assert_eq!(crc32c.finalize(), Crc32c::checksum(&data));
+        assert_eq!(adler.finalize(), Adler32::checksum(&data));
+        assert_eq!(inet.finalize(), InternetChecksum::checksum(&data));
+    }
+}
+
+#[test]
+fn test_internet_checksum() {
+    // the IPv4 header example from wikipedia
+    let mut header = [
+        0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0x00, 0x00, 0xc0, 0xa8, 0x00,
+        0x01, 0xc0, 0xa8, 0x00, 0xc7,
+    ];
+    assert_eq!(InternetChecksum::checksum(&header), 0xb861);
+
+    let mut buf_view = BufViewMut::wrap(&mut header);
+    buf_view.set_u16(10, 0xb861);
+    buf_view.set_writer_index(20);
+    assert_eq!(buf_view.checksum::<InternetChecksum>(), 0);
+
+    // odd length pads a zero byte
+    assert_eq!(InternetChecksum::checksum(&[0x01, 0x02, 0x03]), !0x0402u16);
+    let mut inet = InternetChecksum::new();
+    inet.add_u32(0xc0a80001);
+    inet.add_u16(17);
+    assert_eq!(inet.finalize(), !(0xc0a8u16 + 0x0001 + 17));
+
+    let buf = [
+        0xff, 0xff, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39,
+    ];
+    let mut buf_view = BufView::wrap(&buf);
+    buf_view.read_u16();
+    assert_eq!(buf_view.checksum::<Crc32>(), 0xcbf43926);
+    assert_eq!(buf_view.checksum_range::<Crc32>(2..11), 0xcbf43926);
+    assert_eq!(buf_view.checksum_range::<InternetChecksum>(0..2), 0);
+}
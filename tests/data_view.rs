@@ -0,0 +1,79 @@
+use buf_view::{BigEndian, BufViewMut, DataView, ErrorKind, LittleEndian, TypedArrayView};
+
+#[test]
+fn test_data_view() {
+    let mut buf = [0u8; 25];
+    let mut view = DataView::new(&mut buf[..]);
+    assert_eq!(view.byte_length(), 25);
+    view.set_int8(0, -1).unwrap();
+    view.set_uint16(1, 0x0102, false).unwrap();
+    view.set_int32(3, -2, true).unwrap();
+    view.set_float64(7, 0.5, false).unwrap();
+    view.set_big_int64(15, i64::MIN, true).unwrap();
+    view.set_float16(23, -2.0, false).unwrap();
+
+    let err = view.set_uint32(22, 1, false).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::OutOfBounds {
+            needed: 4,
+            available: 3
+        }
+    );
+    assert!(view.set_uint8(25, 1).is_err());
+    assert!(view.get_uint8(usize::MAX).is_err());
+
+    assert_eq!(view.get_uint8(0), Ok(0xff));
+    assert_eq!(view.get_int16(1, false), Ok(0x0102));
+    assert_eq!(view.get_uint16(1, true), Ok(0x0201));
+    assert_eq!(view.get_int32(3, true), Ok(-2));
+    assert_eq!(view.get_uint32(3, false), Ok(0xfeff_ffff));
+    assert_eq!(view.get_float64(7, false), Ok(0.5));
+    assert_eq!(view.get_big_int64(15, true), Ok(i64::MIN));
+    assert_eq!(view.get_big_uint64(15, false), Ok(0x80));
+    assert_eq!(view.get_float16(23, false), Ok(-2.0));
+    assert_eq!(
+        view.get_float32(21, false),
+        Ok(f32::from_bits(0x00_80_c0_00))
+    );
+    assert_eq!(view.get_int8(24), Ok(0));
+
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut view = buf_view.data_view();
+    view.set_float32(0, 1.0, true).unwrap();
+    assert_eq!(buf_view.get_f32_le(0), 1.0);
+}
+
+#[test]
+fn test_typed_array_view() {
+    let mut buf = [0u8; 16];
+    let err = TypedArrayView::<u32, LittleEndian>::new(&mut buf, 2, None).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::Invalid(_)));
+    assert!(TypedArrayView::<u32, LittleEndian>::new(&mut buf[..14], 4, None).is_err());
+    assert!(TypedArrayView::<u32, LittleEndian>::new(&mut buf, 8, Some(3)).is_err());
+    assert!(TypedArrayView::<u32, LittleEndian>::new(&mut buf, 20, Some(0)).is_err());
+    assert!(TypedArrayView::<u32, LittleEndian>::new(&mut buf, 16, None)
+        .unwrap()
+        .is_empty());
+
+    let mut arr = TypedArrayView::<i16, BigEndian>::new(&mut buf, 4, None).unwrap();
+    assert_eq!((arr.len(), arr.byte_length()), (6, 12));
+    arr.fill(-1);
+    arr.set(1, 0x0102);
+    arr.set(6, 5);
+    arr.set(usize::MAX, 5);
+    assert_eq!(arr.get(1), Some(0x0102));
+    assert_eq!(arr.get(6), None);
+    assert_eq!(arr.get(usize::MAX), None);
+    assert_eq!(arr.to_vec(), [-1, 0x0102, -1, -1, -1, -1]);
+    assert_eq!(arr.iter().rev().nth(4), Some(0x0102));
+    assert_eq!(&buf[..8], &[0, 0, 0, 0, 0xff, 0xff, 1, 2]);
+
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut floats = buf_view
+        .typed_array::<f32, LittleEndian>(0, Some(1))
+        .unwrap();
+    floats.set(0, 2.0);
+    assert_eq!(floats.get(0), Some(2.0));
+    assert_eq!(buf_view.get_f32_le(0), 2.0);
+}
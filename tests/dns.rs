@@ -0,0 +1,204 @@
+#![cfg(feature = "dns")]
+
+use buf_view::dns::{class, rtype, Encoder, Message, RData, Section};
+use buf_view::{BufView, BufViewMut, ErrorKind};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_encode_decode() {
+    let mut buf = [0u8; 512];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_u16(0xffff);
+    let mut enc = Encoder::new(&mut buf_view, 7, 0x8400).unwrap();
+    enc.add_question("example.com.", rtype::ANY, class::IN)
+        .unwrap();
+    enc.add_name_record(
+        Section::Answer,
+        "www.Example.com",
+        rtype::CNAME,
+        60,
+        "web.example.com",
+    )
+    .unwrap();
+    enc.add_mx(Section::Answer, "example.com", 60, 10, "mail.example.com")
+        .unwrap();
+    enc.add_txt(Section::Answer, "example.com", 60, &[b"v=spf1", b""])
+        .unwrap();
+    enc.add_name_record(
+        Section::Authority,
+        "example.com",
+        rtype::NS,
+        60,
+        "ns.example.org",
+    )
+    .unwrap();
+    enc.add_aaaa(
+        Section::Additional,
+        "ns.example.org",
+        60,
+        Ipv6Addr::LOCALHOST,
+    )
+    .unwrap();
+    let err = enc
+        .add_a(Section::Answer, "a.example.com", 60, Ipv4Addr::LOCALHOST)
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("DNS sections out of order"));
+    let len = enc.finish().unwrap();
+
+    // "www" then a pointer to the question name at offset 12
+    assert_eq!(&buf[31..37], &[3, b'w', b'w', b'w', 0xc0, 12]);
+
+    let mut buf_view = BufView::wrap(&buf[..len + 2]);
+    buf_view.set_reader_index(2);
+    let msg = buf_view.read_dns_message().unwrap();
+    assert_eq!(buf_view.reader_index(), len + 2);
+    let header = msg.header();
+    assert_eq!(
+        (header.id, header.qr(), header.aa(), header.rcode()),
+        (7, true, true, 0)
+    );
+    assert_eq!(
+        (
+            header.qd_count,
+            header.an_count,
+            header.ns_count,
+            header.ar_count
+        ),
+        (1, 3, 1, 1)
+    );
+
+    let question = msg.questions().next().unwrap();
+    assert_eq!(
+        (question.qtype, question.name.to_string()),
+        (rtype::ANY, "example.com".to_string())
+    );
+
+    let answers: Vec<_> = msg.answers().collect();
+    assert_eq!(answers.len(), 3);
+    // the suffix is compressed against the question name
+    assert_eq!(answers[0].name.to_string(), "www.example.com");
+    assert_eq!(answers[0].name, answers[0].name);
+    match answers[0].data().unwrap() {
+        RData::Cname(name) => assert!(name.eq_str("web.example.com")),
+        data => panic!("{:?}", data),
+    }
+    match answers[1].data().unwrap() {
+        RData::Mx {
+            preference,
+            exchange,
+        } => {
+            assert_eq!(
+                (preference, exchange.to_string()),
+                (10, "mail.example.com".to_string())
+            );
+        }
+        data => panic!("{:?}", data),
+    }
+    match answers[2].data().unwrap() {
+        RData::Txt(strings) => {
+            assert_eq!(strings.collect::<Vec<_>>(), [&b"v=spf1"[..], b""]);
+        }
+        data => panic!("{:?}", data),
+    }
+    let ns = msg.authorities().next().unwrap();
+    assert_eq!(
+        ns.data().unwrap(),
+        RData::Ns(msg.additionals().next().unwrap().name)
+    );
+    let aaaa = msg.additionals().next().unwrap();
+    assert_eq!(aaaa.data(), Ok(RData::Aaaa(Ipv6Addr::LOCALHOST)));
+}
+
+#[test]
+fn test_bad_names() {
+    let header = [0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    let parse = |name: &[u8]| {
+        let mut msg = header.to_vec();
+        msg.extend_from_slice(name);
+        msg.extend_from_slice(&[0, 1, 0, 1]);
+        Message::parse(&msg).map(|m| m.wire_len())
+    };
+
+    assert_eq!(parse(&[1, b'a', 0]), Ok(19));
+    // a pointer to itself, and a pointer forward
+    let err = parse(&[1, b'a', 0xc0, 12]).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::Invalid("DNS compression pointer loop")
+    );
+    assert_eq!(err.offset(), 14);
+    assert!(parse(&[0xc0, 14, 0]).is_err());
+    let err = parse(&[0x80, 0]).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("bad DNS label type"));
+    let err = parse(&[5, b'a']).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+
+    let mut long = Vec::new();
+    for _ in 0..4 {
+        long.push(63);
+        long.extend_from_slice(&[b'x'; 63]);
+    }
+    long.push(0);
+    let err = parse(&long).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("DNS name too long"));
+
+    // a chain of pointers, each pointing to the previous name
+    let mut msg = header.to_vec();
+    msg.extend_from_slice(&[0, 0, 1, 0, 1]);
+    let mut prev = 12;
+    for count in 2..=34 {
+        msg[5] = count;
+        let start = msg.len();
+        msg.extend_from_slice(&[0xc0, prev as u8, 0, 1, 0, 1]);
+        prev = start;
+        if count < 34 {
+            assert_eq!(
+                Message::parse(&msg).unwrap().questions().count(),
+                count as usize
+            );
+        }
+    }
+    let err = Message::parse(&msg).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::Invalid("too many DNS compression pointers")
+    );
+
+    let mut buf = [0u8; 40];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut enc = Encoder::new(&mut buf_view, 1, 0).unwrap();
+    let label = "x".repeat(64);
+    assert!(enc.add_question(&label, rtype::A, class::IN).is_err());
+    assert!(enc.add_question("a..b", rtype::A, class::IN).is_err());
+    let err = enc
+        .add_question("a-rather-long-name.example.com", rtype::A, class::IN)
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+}
+
+#[test]
+fn test_encoder_fills_buffer() {
+    let mut buf = [0u8; 40];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut enc = Encoder::new(&mut buf_view, 1, 0).unwrap();
+    let long = "abcdefghij.example.com";
+    enc.add_question("ab.cd", rtype::A, class::IN).unwrap();
+    // the first label of the long name fits, a failed add drops it and its suffixes
+    let err = enc.add_question(long, rtype::A, class::IN).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+    assert_eq!(enc.view().writer_index(), 23);
+    enc.add_question("q.cd", rtype::A, class::IN).unwrap();
+    assert!(enc.add_question(long, rtype::A, class::IN).is_err());
+    // a failed record does not move to the answer section
+    assert!(enc
+        .add_a(Section::Answer, "ab.cd", 60, Ipv4Addr::LOCALHOST)
+        .is_err());
+    enc.add_question("cd", rtype::A, class::IN).unwrap();
+    assert_eq!(enc.finish(), Ok(37));
+
+    let msg = Message::parse(&buf[..37]).unwrap();
+    assert_eq!((msg.wire_len(), msg.header().qd_count), (37, 3));
+    let names: Vec<_> = msg.questions().map(|q| q.name.to_string()).collect();
+    assert_eq!(names, ["ab.cd", "q.cd", "cd"]);
+    assert_eq!(msg.answers().count(), 0);
+}
@@ -0,0 +1,56 @@
+use buf_view::{BufView, BufViewMut, F64Le, I16Le, Pod, U16Be, U32Be, U32Le, U64Be};
+use std::mem::{align_of, size_of};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+struct Record {
+    tag: [u8; 4],
+    id: U32Le,
+    value: F64Le,
+    delta: I16Le,
+    flags: u8,
+}
+
+unsafe impl Pod for Record {}
+
+#[test]
+fn test_endian_types() {
+    assert_eq!(size_of::<Record>(), 19);
+    assert_eq!(align_of::<Record>(), 1);
+    assert_eq!(align_of::<U64Be>(), 1);
+
+    let mut val = U32Be::new(0x0102_0304);
+    assert_eq!(val.to_bytes(), [1, 2, 3, 4]);
+    val.set(7);
+    assert_eq!(u32::from(val), 7);
+    assert_eq!(U16Be::from_bytes([0xab, 0xcd]).get(), 0xabcd);
+    assert_eq!(format!("{:?}", I16Le::new(-3)), "-3");
+
+    let mut buf = [0u8; 24];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_u8(0xee);
+    buf_view.write_bytes(b"REC1");
+    buf_view.write_u32_le(42);
+    buf_view.write_f64_le(-0.75);
+    buf_view.write_i16_le(-300);
+    buf_view.write_u8(0x80);
+
+    let record = buf_view.ref_mut_at::<Record>(1);
+    record.id.set(record.id.get() + 1);
+    record.flags |= 1;
+    assert_eq!(buf_view.ref_at::<Record>(1).id.get(), 43);
+    assert_eq!(buf_view.writer_index(), 20);
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.read_u8(), 0xee);
+    let record = buf_view.read_ref::<Record>();
+    assert_eq!(buf_view.reader_index(), 20);
+    assert_eq!(&record.tag, b"REC1");
+    assert_eq!(record.id.get(), 43);
+    assert_eq!(record.value.get(), -0.75);
+    assert_eq!(record.delta.get(), -300);
+    assert_eq!(record.flags, 0x81);
+    let tail = buf_view.read_ref::<[U16Be; 2]>();
+    assert_eq!(tail, &[U16Be::new(0), U16Be::new(0)]);
+    assert_eq!(buf_view.ref_at::<U32Le>(5).get(), 43);
+}
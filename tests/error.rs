@@ -0,0 +1,88 @@
+use buf_view::{BufView, BufViewMut, ErrorKind};
+
+#[test]
+fn test_try_read() {
+    let buf = [0x01, 0x02, 0x03, 0x04, 0x05];
+    let mut buf_view = BufView::wrap(&buf);
+
+    assert_eq!(buf_view.try_read_u16(), Ok(0x0102));
+    assert_eq!(buf_view.try_read_u16_le(), Ok(0x0403));
+    let err = buf_view.try_read_u32().unwrap_err();
+    assert_eq!(
+        *err.kind(),
+        ErrorKind::OutOfBounds {
+            needed: 4,
+            available: 1
+        }
+    );
+    assert_eq!(err.offset(), 4);
+    assert_eq!(err.to_string(), "need 4 bytes at 0x4, have 1");
+    // a failed read does not advance the reader_index
+    assert_eq!(buf_view.reader_index(), 4);
+    assert_eq!(buf_view.try_read_i8(), Ok(5));
+    assert!(buf_view.try_read_u8().is_err());
+
+    assert_eq!(buf_view.try_get_u32(1), Ok(0x02030405));
+    assert_eq!(buf_view.try_get_i16_le(0), Ok(0x0201));
+    assert!(buf_view.try_get_u16(4).is_err());
+    assert!(buf_view.try_get_u8(usize::MAX).is_err());
+    let err = buf_view.try_get_u64(2).unwrap_err();
+    assert_eq!(err.to_string(), "need 8 bytes at 0x2, have 3");
+
+    let mut dest = [0u8; 3];
+    assert!(buf_view.try_get_bytes(3, &mut dest).is_err());
+    assert_eq!(buf_view.try_get_bytes(2, &mut dest), Ok(()));
+    assert_eq!(dest, [3, 4, 5]);
+
+    let mut buf = [0u8; 4];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    assert_eq!(buf_view.try_write_bytes(&[1, 2, 3]), Ok(()));
+    assert!(buf_view.try_write_bytes(&[4, 5]).is_err());
+    assert_eq!(buf_view.try_read_u16(), Ok(0x0102));
+    assert!(buf_view.try_read_u16().is_err());
+    assert!(buf_view.try_set_bytes(3, &[4, 5]).is_err());
+    assert_eq!(buf_view.try_set_bytes(2, &[4, 5]), Ok(()));
+    assert_eq!(buf_view.try_get_u16(2), Ok(0x0405));
+}
+
+#[test]
+fn test_error_context() {
+    let mut buf = vec![0u8; 0x1f3];
+    buf[0x10] = 4;
+    let mut frame = BufView::wrap(&buf);
+    frame.set_reader_index(0x10);
+
+    let err = frame
+        .context("frame", |v| {
+            let count = v.try_read_u8()?;
+            let len = 0x1f3 - v.reader_index();
+            let mut headers = v.try_read_view(len)?;
+            headers.context_index("headers", 0, |v| v.try_read_view(0x1d6).map(|_| ()))?;
+            for i in 1..count as usize {
+                headers.context_index("headers", i, |v| {
+                    v.context("name", |v| v.try_read_u8())?;
+                    v.context("value", |v| v.try_read_u32())
+                })?;
+            }
+            Ok(())
+        })
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "frame.headers[3].value: need 4 bytes at 0x1f2, have 1"
+    );
+    assert_eq!(err.path(), "frame.headers[3].value");
+    assert_eq!(err.offset(), 0x1f2);
+    let (start, around) = err.around();
+    assert_eq!(start, 0x1ea);
+    assert_eq!(around.len(), 9);
+    assert_eq!(
+        format!("{:#}", err),
+        "frame.headers[3].value: need 4 bytes at 0x1f2, have 1\n  \
+         0x1ea: 00 00 00 00 00 00 00 00>00"
+    );
+
+    let err = buf_view::Error::new(ErrorKind::Invalid("bad magic"), &buf, 0).context("header");
+    assert_eq!(err.to_string(), "header: bad magic at 0x0");
+}
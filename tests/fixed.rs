@@ -0,0 +1,116 @@
+use buf_view::{BufView, BufViewMut, ErrorKind, Fixed, NibbleOrder};
+
+#[test]
+fn test_fixed() {
+    let mut buf = [0u8; 32];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_fixed::<i16, 15>(0.25).unwrap();
+    buf_view.write_fixed::<i16, 15>(-1.0).unwrap();
+    buf_view.write_fixed_le::<i32, 31>(-0.5).unwrap();
+    buf_view.write_fixed::<i32, 16>(-2.75).unwrap();
+    buf_view.write_fixed::<u8, 4>(15.9375).unwrap();
+    buf_view.write_fixed_le::<u16, 8>(1.0 / 3.0).unwrap();
+    assert_eq!(buf_view.writer_index(), 15);
+
+    // out of range and not finite values are rejected, nothing is written
+    let err = buf_view.write_fixed::<i16, 15>(1.0).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Overflow);
+    assert!(buf_view.write_fixed::<u8, 4>(-0.1).is_err());
+    assert!(buf_view.write_fixed::<i32, 16>(f64::NAN).is_err());
+    assert!(buf_view.write_fixed::<i64, 0>(f64::INFINITY).is_err());
+    assert_eq!(buf_view.writer_index(), 15);
+    // rounding to the nearest raw value keeps 0.99998 in range
+    buf_view.set_fixed::<i16, 15>(15, 0.99998).unwrap();
+    buf_view.set_fixed_le::<i16, 8>(17, -0.5).unwrap();
+    assert!(buf_view.set_fixed::<i16, 8>(31, 0.5).is_err());
+    // a short buffer is an error, not a panic
+    let mut short = [0u8; 1];
+    let mut short_view = BufViewMut::wrap(&mut short);
+    let err = short_view.write_fixed::<i16, 15>(0.5).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+    assert!(short_view.write_fixed_le::<i16, 15>(0.5).is_err());
+    assert_eq!(short_view.writer_index(), 0);
+
+    assert_eq!(&buf[..4], &[0x20, 0x00, 0x80, 0x00]);
+    assert_eq!(&buf[4..8], &[0x00, 0x00, 0x00, 0xc0]);
+    assert_eq!(&buf[8..12], &[0xff, 0xfd, 0x40, 0x00]);
+    assert_eq!(&buf[12..17], &[0xff, 0x55, 0x00, 0x7f, 0xff]);
+    assert_eq!(&buf[17..19], &[0x80, 0xff]);
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.read_fixed::<i16, 15>(), 0.25);
+    assert_eq!(buf_view.read_fixed::<i16, 15>(), -1.0);
+    assert_eq!(buf_view.read_fixed_le::<i32, 31>(), -0.5);
+    let q = buf_view.read_fixed_raw::<i32, 16>();
+    assert_eq!(q, Fixed::from_raw(-0x2_c000));
+    assert_eq!((q.numerator(), q.denominator()), (-0x2_c000, 0x1_0000));
+    assert_eq!(q.to_f64(), -2.75);
+    assert_eq!(buf_view.read_fixed::<u8, 4>(), 15.9375);
+    let third = buf_view.read_fixed_raw_le::<u16, 8>();
+    assert_eq!(third.raw(), 0x55);
+    assert_eq!(buf_view.get_fixed::<i16, 15>(15), 32767.0 / 32768.0);
+    assert_eq!(buf_view.get_fixed_le::<i16, 8>(17), -0.5);
+
+    assert_eq!(Fixed::<i8, 7>::from_f64(-1.0), Some(Fixed::from_raw(-128)));
+    assert_eq!(Fixed::<i8, 7>::from_f64(1.0), None);
+    assert_eq!(Fixed::<i64, 62>::from_f64(1.5).unwrap().to_f64(), 1.5);
+}
+
+#[test]
+fn test_bcd() {
+    let buf = [0x12, 0x34, 0x56, 0x78, 0x90, 0x21, 0x43, 0xf5, 0x1a];
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(
+        buf_view.get_bcd(0, 10, NibbleOrder::HighFirst),
+        Ok(1234567890)
+    );
+    assert_eq!(buf_view.get_bcd(0, 3, NibbleOrder::HighFirst), Ok(234));
+    assert_eq!(buf_view.get_bcd(5, 5, NibbleOrder::LowFirst), Ok(12345));
+    assert_eq!(buf_view.get_bcd(0, 0, NibbleOrder::HighFirst), Ok(0));
+
+    let err = buf_view.get_bcd(8, 2, NibbleOrder::HighFirst).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("invalid BCD digit"));
+    assert_eq!(err.offset(), 8);
+    // the padding nibble is not checked
+    assert_eq!(buf_view.get_bcd(7, 1, NibbleOrder::HighFirst), Ok(5));
+    assert_eq!(buf_view.get_bcd(7, 1, NibbleOrder::LowFirst), Ok(5));
+    assert!(buf_view.get_bcd(8, 4, NibbleOrder::HighFirst).is_err());
+
+    assert_eq!(buf_view.read_bcd(4, NibbleOrder::HighFirst), Ok(1234));
+    assert!(buf_view.read_bcd(14, NibbleOrder::HighFirst).is_err());
+    assert_eq!(buf_view.reader_index(), 2);
+    assert_eq!(buf_view.read_bcd(6, NibbleOrder::LowFirst), Ok(658709));
+
+    let nines = [0x99u8; 10];
+    let mut buf_view = BufView::wrap(&nines);
+    let err = buf_view.read_bcd(20, NibbleOrder::HighFirst).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Overflow);
+    assert_eq!(buf_view.reader_index(), 0);
+    assert_eq!(
+        buf_view.read_bcd(19, NibbleOrder::HighFirst),
+        Ok(9_999_999_999_999_999_999)
+    );
+
+    let mut buf = [0xffu8; 12];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view
+        .write_bcd(1234567890, 10, NibbleOrder::HighFirst)
+        .unwrap();
+    buf_view.write_bcd(123, 3, NibbleOrder::HighFirst).unwrap();
+    buf_view.write_bcd(12345, 5, NibbleOrder::LowFirst).unwrap();
+    let err = buf_view
+        .write_bcd(1000, 3, NibbleOrder::HighFirst)
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Overflow);
+    assert!(buf_view.write_bcd(1, 6, NibbleOrder::HighFirst).is_err());
+    assert_eq!(buf_view.writer_index(), 10);
+    buf_view.set_bcd(10, 7, 4, NibbleOrder::LowFirst).unwrap();
+    assert_eq!(
+        buf,
+        [0x12, 0x34, 0x56, 0x78, 0x90, 0x01, 0x23, 0x21, 0x43, 0x05, 0x00, 0x70]
+    );
+    assert_eq!(
+        BufView::wrap(&buf).get_bcd(0, 10, NibbleOrder::HighFirst),
+        Ok(1234567890)
+    );
+}
@@ -0,0 +1,154 @@
+use buf_view::float::*;
+use buf_view::{BufView, BufViewMut};
+
+#[test]
+fn test_f16() {
+    assert_eq!(f16_to_f32(0x3c00), 1.0);
+    assert_eq!(f16_to_f32(0xc000), -2.0);
+    assert_eq!(f16_to_f32(0x7bff), 65504.0);
+    assert_eq!(f16_to_f32(0x0400), 2f32.powi(-14));
+    assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+    assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+    assert!(f16_to_f32(0x7c01).is_nan());
+    assert_eq!(f16_to_f32(0x8000).to_bits(), (-0.0f32).to_bits());
+
+    // every half value survives the round trip through f32
+    for half in 0..=u16::MAX {
+        let val = f16_to_f32(half);
+        if val.is_nan() {
+            assert!(f16_to_f32(f32_to_f16(val)).is_nan());
+        } else {
+            assert_eq!(f32_to_f16(val), half);
+        }
+    }
+
+    assert_eq!(f32_to_f16(65520.0), 0x7c00);
+    assert_eq!(f32_to_f16(65519.0), 0x7bff);
+    assert_eq!(f32_to_f16(1e10), 0x7c00);
+    assert_eq!(f32_to_f16(2f32.powi(-25)), 0x0000);
+    assert_eq!(f32_to_f16(1.5 * 2f32.powi(-25)), 0x0001);
+    assert_eq!(f32_to_f16(3.0 * 2f32.powi(-25)), 0x0002);
+    assert_eq!(f32_to_f16(-1e-30), 0x8000);
+    // 1 + 2^-11 is the tie between 1 and the next half, ties to even
+    assert_eq!(f32_to_f16(1.0 + 2f32.powi(-11)), 0x3c00);
+    assert_eq!(f32_to_f16(1.0 + 3.0 * 2f32.powi(-11)), 0x3c02);
+    assert_eq!(f32_to_f16(f32::NAN) & 0x7e00, 0x7e00);
+
+    assert_eq!(f32_to_bf16(1.0), 0x3f80);
+    assert_eq!(f32_to_bf16(1.0 + 2f32.powi(-8)), 0x3f80);
+    assert_eq!(f32_to_bf16(1.0 + 3.0 * 2f32.powi(-8)), 0x3f82);
+    assert_eq!(f32_to_bf16(f32::MAX), 0x7f80);
+    assert_eq!(bf16_to_f32(0xc040), -3.0);
+    assert!(bf16_to_f32(f32_to_bf16(f32::from_bits(0x7f80_0001))).is_nan());
+}
+
+#[test]
+fn test_f80() {
+    assert_eq!(f64_to_f80(44100.0), (0x400e, 0xac44_0000_0000_0000));
+    assert_eq!(f64_to_f80(-1.0), (0xbfff, 0x8000_0000_0000_0000));
+    assert_eq!(f64_to_f80(f64::INFINITY), (0x7fff, 0x8000_0000_0000_0000));
+    assert_eq!(f80_to_f64(0x3fff, 0x8000_0000_0000_0400), 1.0);
+    assert_eq!(
+        f80_to_f64(0x3fff, 0x8000_0000_0000_0c00),
+        1.0 + 2f64.powi(-51)
+    );
+    assert_eq!(f80_to_f64(0x3fff, 0xffff_ffff_ffff_ffff), 2.0);
+    assert_eq!(
+        f80_to_f64(0x3fff + 1024, 0x8000_0000_0000_0000),
+        f64::INFINITY
+    );
+    assert_eq!(f80_to_f64(0x3fff - 1074, 0x8000_0000_0000_0000), 5e-324);
+    assert_eq!(f80_to_f64(0x3fff - 1075, 0x8000_0000_0000_0000), 0.0);
+    assert_eq!(f80_to_f64(0x3fff - 1075, 0x8000_0000_0000_0001), 5e-324);
+    assert_eq!(f80_to_f64(0, 0), 0.0);
+    assert_eq!(f80_to_f64(0x8000, 0).to_bits(), (-0.0f64).to_bits());
+    assert!(f80_to_f64(0x7fff, 0xc000_0000_0000_0000).is_nan());
+
+    for val in [
+        0.1,
+        -123456.789,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        5e-324,
+        1.5e-310,
+        f64::NEG_INFINITY,
+    ] {
+        let (sign_exp, mantissa) = f64_to_f80(val);
+        assert_eq!(f80_to_f64(sign_exp, mantissa), val);
+    }
+}
+
+#[test]
+fn test_ibm() {
+    assert_eq!(f64_to_ibm32(-118.625), 0xc276_a000);
+    assert_eq!(f64_to_ibm32(1.0), 0x4110_0000);
+    assert_eq!(f64_to_ibm32(0.0), 0);
+    assert_eq!(ibm32_to_f64(0xc276_a000), -118.625);
+    assert_eq!(ibm32_to_f64(0x4110_0000), 1.0);
+    assert_eq!(
+        ibm32_to_f64(0x7fff_ffff),
+        (1.0 - 2f64.powi(-24)) * 2f64.powi(252)
+    );
+    assert_eq!(f64_to_ibm64(0.1), 0x4019_9999_9999_999a);
+    assert_eq!(ibm64_to_f64(0x4019_9999_9999_999a), 0.1);
+
+    assert_eq!(f64_to_ibm32(1e80), 0x7fff_ffff);
+    assert_eq!(f64_to_ibm32(f64::NEG_INFINITY), 0xffff_ffff);
+    assert_eq!(f64_to_ibm32(f64::NAN), 0x7fff_ffff);
+    assert_eq!(f64_to_ibm32(1e-300), 0);
+    // the smallest normalized single is 16^-65, below it the fraction is unnormalized
+    assert_eq!(f64_to_ibm32(16f64.powi(-65)), 0x0010_0000);
+    assert_eq!(f64_to_ibm32(16f64.powi(-66)), 0x0001_0000);
+    // 1.0 has 3 leading zero bits in its hex fraction, 1 + 2^-21 is a tie
+    assert_eq!(f64_to_ibm32(1.0 + 2f64.powi(-21)), 0x4110_0000);
+    assert_eq!(f64_to_ibm32(1.0 + 3.0 * 2f64.powi(-21)), 0x4110_0002);
+    assert_eq!(f64_to_ibm32(1.0 + 2f64.powi(-22)), 0x4110_0000);
+    assert_eq!(f64_to_ibm32(0.9999999999), 0x4110_0000);
+
+    for val in [0.1, -3.75e-20, 123456789.0, 7.2e75, -1.0 / 3.0] {
+        assert_eq!(ibm64_to_f64(f64_to_ibm64(val)), val);
+        let single = ibm32_to_f64(f64_to_ibm32(val));
+        assert!(((single - val) / val).abs() < 2f64.powi(-20));
+    }
+}
+
+#[test]
+fn test_float_view() {
+    let mut buf = [0u8; 48];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_f16_le(-0.5);
+    buf_view.write_bf16(3.0);
+    buf_view.write_f80_le(-44100.0);
+    buf_view.write_ibm_f32_le(1.0);
+    buf_view.write_ibm_f64_le(0.1);
+    buf_view.set_f16(40, 2.0);
+    buf_view.set_bf16_le(42, 2.0);
+    buf_view.set_f80(30, 1.0);
+    buf_view.set_ibm_f32(44, -118.625);
+    assert_eq!(buf_view.writer_index(), 26);
+    assert_eq!(buf_view.read_f16_le(), -0.5);
+    assert_eq!(buf_view.read_bf16(), 3.0);
+    assert_eq!(buf_view.read_f80_le(), -44100.0);
+    assert_eq!(buf_view.read_ibm_f32_le(), 1.0);
+    assert_eq!(buf_view.read_ibm_f64_le(), 0.1);
+    assert_eq!(buf_view.get_f80_le(4), -44100.0);
+    assert_eq!(buf_view.get_f80(30), 1.0);
+    buf_view.set_f80_le(30, 7.0);
+    buf_view.set_ibm_f64(18, 2.5);
+    buf_view.set_ibm_f64_le(18, 2.5);
+    buf_view.set_ibm_f32_le(44, 0.5);
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.get_f16(40), 2.0);
+    assert_eq!(buf_view.get_f16_le(0), -0.5);
+    assert_eq!(buf_view.get_bf16(2), 3.0);
+    assert_eq!(buf_view.get_bf16_le(42), 2.0);
+    assert_eq!(buf_view.get_f80_le(30), 7.0);
+    assert_eq!(buf_view.get_ibm_f32_le(44), 0.5);
+    assert_eq!(buf_view.get_ibm_f32(14), ibm32_to_f64(0x0000_1041));
+    assert_eq!(buf_view.get_ibm_f64_le(18), 2.5);
+    assert_eq!(
+        buf_view.get_ibm_f64(18),
+        ibm64_to_f64(f64_to_ibm64(2.5).swap_bytes())
+    );
+}
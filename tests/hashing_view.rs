@@ -0,0 +1,62 @@
+use buf_view::checksum::{Adler32, Checksum, Crc32, Crc32c};
+use buf_view::{BufView, BufViewMut, HashingView, SizeCounter, ViewRead, ViewWrite};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+
+#[test]
+fn test_hashing_view() {
+    let mut buf = [0u8; 32];
+    let mut writer = HashingView::new(BufViewMut::wrap(&mut buf), Crc32c::new());
+    writer.write_u16(21);
+    writer.write_u32_le(0x01020304);
+    writer.write_f64(2.5);
+    writer.write_bytes(b"payload");
+    let crc = writer.hasher().finalize();
+    // set is random access and not hashed
+    writer.set_u16(0, 21);
+    assert_eq!(writer.hasher().finalize(), crc);
+    writer.inner_mut().write_u32(crc);
+    let (writer, _) = writer.into_inner();
+    assert_eq!(writer.writer_index(), 25);
+    assert_eq!(crc, Crc32c::checksum(&buf[..21]));
+
+    let mut reader = HashingView::new(BufView::wrap_with(&buf, 0, 25), Crc32c::new());
+    assert_eq!(reader.read_u16(), 21);
+    assert_eq!(reader.read_u32_le(), 0x01020304);
+    assert_eq!(reader.read_f64(), 2.5);
+    let mut payload = [0u8; 7];
+    reader.read_bytes(&mut payload);
+    assert_eq!(&payload, b"payload");
+    assert_eq!(reader.reader_index(), 21);
+    assert_eq!(reader.remaining(), 4);
+    let trailer = reader.inner_mut().read_u32();
+    assert_eq!(reader.hasher().finish(), trailer as u64);
+
+    // any std hasher works, too
+    let data = [1u8, 2, 3, 4, 5];
+    let mut reader = HashingView::new(BufView::wrap(&data), DefaultHasher::new());
+    reader.read_u8();
+    reader.read_u32();
+    let mut hasher = DefaultHasher::new();
+    hasher.write(&data);
+    assert_eq!(reader.hasher().finish(), hasher.finish());
+
+    let mut counter = HashingView::new(SizeCounter::new(), Adler32::new());
+    counter.write_bytes(b"Wikipedia");
+    assert_eq!(counter.inner().size(), 9);
+    assert_eq!(counter.hasher().finish(), 0x11e60398);
+}
+
+#[test]
+fn test_hashing_view_verify_trailer() {
+    let mut buf = [0u8; 13];
+    buf[..9].copy_from_slice(b"123456789");
+    buf[9..].copy_from_slice(&Crc32::checksum(b"123456789").to_le_bytes());
+
+    let mut reader = HashingView::new(BufView::wrap(&buf), Crc32::new());
+    while reader.remaining() > 4 {
+        reader.read_u8();
+    }
+    let trailer = reader.inner_mut().read_u32_le();
+    assert_eq!(reader.hasher().finish(), trailer as u64);
+}
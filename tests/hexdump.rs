@@ -0,0 +1,58 @@
+use buf_view::{BufView, BufViewMut, HexDump};
+
+#[test]
+fn test_hexdump() {
+    let buf: Vec<u8> = (0..40u8).map(|i| i + 0x3c).collect();
+    let mut buf_view = BufView::wrap_with(&buf, 0, 32);
+    buf_view.read_u8();
+
+    assert_eq!(
+        buf_view.hexdump().to_string(),
+        "00000000  3c 3d 3e 3f 40 41 42 43  44 45 46 47 48 49 4a 4b  |<=>?@ABCDEFGHIJK|\n\
+         \x20            r\n\
+         00000010  4c 4d 4e 4f 50 51 52 53  54 55 56 57 58 59 5a 5b  |LMNOPQRSTUVWXYZ[|\n\
+         00000020  5c 5d 5e 5f 60 61 62 63                           |\\]^_`abc|\n\
+         \x20         w\n"
+    );
+
+    assert_eq!(
+        buf_view.hexdump().around(0x13, 2).to_string(),
+        "00000010     4d 4e 4f 50                                    | MNOP|\n"
+    );
+
+    let mut buf = [0u8, 1, 0x7f, b'a', b' ', 0xff];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    assert_eq!(
+        buf_view.hexdump().to_string(),
+        "00000000  00 01 7f 61 20 ff                                 |...a .|\n\
+         \x20         *\n"
+    );
+    buf_view.set_writer_index(4);
+    buf_view.set_reader_index(1);
+    assert_eq!(
+        format!("{:#?}", buf_view),
+        "BufViewMut { reader_index: 1, writer_index: 4, capacity: 6 }\n\
+         00000000     01 7f 61                                       | ..a|\n"
+    );
+    assert_eq!(
+        format!("{:?}", buf_view),
+        "BufViewMut { buf: [0, 1, 127, 97, 32, 255], reader_index: 1, writer_index: 4 }"
+    );
+
+    // the mark at the end of an aligned buffer gets a line of its own
+    let buf = [0u8; 16];
+    let buf_view = BufView::wrap(&buf);
+    assert_eq!(
+        buf_view.hexdump().to_string(),
+        "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n\
+         \x20         r\n\
+         00000010\n\
+         \x20         w\n"
+    );
+
+    assert_eq!(HexDump::new(&[]).to_string(), "");
+    assert_eq!(
+        HexDump::new(b"abc").range(1..10).to_string(),
+        "00000000     62 63                                          | bc|\n"
+    );
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "msgpack")]
+
+use buf_view::msgpack::{MsgpackReader, MsgpackWriter, Timestamp, Token, Value};
+use buf_view::{BufView, BufViewMut, ErrorKind};
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn test_msgpack_round_trip() {
+    let long = "x".repeat(300);
+    let mut buf = [0u8; 1024];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut writer = MsgpackWriter::new(&mut buf_view);
+    writer.write_array_len(20).unwrap();
+    writer.write_nil().unwrap();
+    writer.write_bool(false).unwrap();
+    for val in [127, 128, 0x1_0000, u64::MAX] {
+        writer.write_u64(val).unwrap();
+    }
+    for val in [-32, -33, -129, i64::MIN] {
+        writer.write_i64(val).unwrap();
+    }
+    writer.write_f32(0.5).unwrap();
+    writer.write_f64(-0.5).unwrap();
+    writer.write_str("héllo").unwrap();
+    writer.write_str(&long).unwrap();
+    writer.write_bin(&[1, 2, 3]).unwrap();
+    writer.write_ext(5, &[9; 4]).unwrap();
+    writer.write_ext(6, &[9; 3]).unwrap();
+    let ts = Timestamp::from_system_time(UNIX_EPOCH + Duration::new(1 << 33, 5)).unwrap();
+    writer.write_timestamp(ts).unwrap();
+    writer
+        .write_timestamp(Timestamp { secs: -1, nanos: 0 })
+        .unwrap();
+    writer.write_map_len(16).unwrap();
+    for i in 0..16 {
+        writer.write_u64(i).unwrap();
+        writer.write_nil().unwrap();
+    }
+    let len = buf_view.writer_index();
+    assert_eq!(
+        &buf[..9],
+        &[0xdc, 0, 20, 0xc0, 0xc2, 0x7f, 0xcc, 0x80, 0xce]
+    );
+    assert!(MsgpackWriter::new(&mut BufViewMut::wrap(&mut [0; 8]))
+        .write_u64(u64::MAX)
+        .is_err());
+
+    let tokens: Vec<_> = MsgpackReader::new(BufView::wrap(&buf[..len]))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(tokens.len(), 21 + 32);
+    assert_eq!(
+        &tokens[..11],
+        &[
+            Token::Array(20),
+            Token::Nil,
+            Token::Bool(false),
+            Token::UInt(127),
+            Token::UInt(128),
+            Token::UInt(0x1_0000),
+            Token::UInt(u64::MAX),
+            Token::Int(-32),
+            Token::Int(-33),
+            Token::Int(-129),
+            Token::Int(i64::MIN),
+        ]
+    );
+    assert_eq!(
+        tokens[11..14],
+        [Token::F32(0.5), Token::F64(-0.5), Token::Str("héllo")]
+    );
+    assert_eq!(tokens[14], Token::Str(&long));
+    assert_eq!(
+        tokens[15..18],
+        [
+            Token::Bin(&[1, 2, 3]),
+            Token::Ext(5, &[9; 4]),
+            Token::Ext(6, &[9; 3])
+        ]
+    );
+    assert_eq!(tokens[18], Token::Timestamp(ts));
+    assert_eq!(
+        ts.to_system_time(),
+        Some(UNIX_EPOCH + Duration::new(1 << 33, 5))
+    );
+    assert_eq!(
+        tokens[19],
+        Token::Timestamp(Timestamp { secs: -1, nanos: 0 })
+    );
+    assert_eq!(tokens[20], Token::Map(16));
+
+    let value = MsgpackReader::new(BufView::wrap(&buf[..len]))
+        .read_value()
+        .unwrap();
+    let items = value.as_array().unwrap();
+    assert_eq!((items[0].is_nil(), items[1].as_bool()), (true, Some(false)));
+    assert_eq!(
+        (items[5].as_u64(), items[5].as_i64()),
+        (Some(u64::MAX), None)
+    );
+    assert_eq!((items[6].as_u64(), items[6].as_i64()), (None, Some(-32)));
+    assert_eq!(items[10].as_f64(), Some(0.5));
+    assert_eq!(items[12], Value::Str("héllo"));
+    assert_eq!(items[14].as_bytes(), Some(&[1, 2, 3][..]));
+    assert_eq!(items[19].as_map().unwrap().len(), 16);
+
+    // the writer picks the same formats
+    let mut copy = [0u8; 1024];
+    let mut copy_view = BufViewMut::wrap(&mut copy);
+    MsgpackWriter::new(&mut copy_view)
+        .write_value(&value)
+        .unwrap();
+    assert_eq!(copy_view.writer_index(), len);
+    assert_eq!(copy[..len], buf[..len]);
+}
+
+#[test]
+fn test_msgpack_errors() {
+    let err = |bytes: &[u8]| {
+        let mut reader = MsgpackReader::new(BufView::wrap(bytes));
+        let err = reader.read_value().unwrap_err();
+        assert_eq!(reader.offset(), 0);
+        (err.kind().clone(), err.offset())
+    };
+    let invalid = |msg| ErrorKind::Invalid(msg);
+    assert_eq!(
+        err(&[0x92, 1, 0xc1]),
+        (invalid("reserved MessagePack type"), 2)
+    );
+    assert_eq!(
+        err(&[0x91, 0xa2, 0xff, 0xfe]),
+        (invalid("bad MessagePack string"), 2)
+    );
+    assert_eq!(
+        err(&[0xd5, 0xff, 0, 0]),
+        (invalid("bad MessagePack timestamp"), 0)
+    );
+    let nanos = [0xd7, 0xff, 0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0];
+    assert_eq!(err(&nanos), (invalid("bad MessagePack timestamp"), 0));
+    assert_eq!(
+        err(&[0xda, 0, 3, b'a']),
+        (
+            ErrorKind::OutOfBounds {
+                needed: 3,
+                available: 1
+            },
+            3
+        )
+    );
+    // a claimed length of 4 billion items does not reserve their memory
+    let huge = [0xdd, 0xff, 0xff, 0xff, 0xff, 0xc0];
+    assert!(matches!(err(&huge).0, ErrorKind::OutOfBounds { .. }));
+    let mut reader = MsgpackReader::new(BufView::wrap(&huge));
+    assert!(reader.skip_value().is_err());
+    assert_eq!(reader.offset(), 0);
+
+    let nested = [0x91, 0x91, 0x81, 0x91, 0xc0, 0xc0];
+    let mut reader = MsgpackReader::new(BufView::wrap(&nested));
+    reader.set_max_depth(3);
+    let err = reader.read_value().unwrap_err();
+    assert_eq!(
+        (err.kind(), err.offset()),
+        (&invalid("MessagePack nesting too deep"), 3)
+    );
+    reader.set_max_depth(4);
+    assert!(reader.read_value().is_ok());
+    // skipping does not recurse, the depth is not limited
+    let mut reader = MsgpackReader::new(BufView::wrap(&nested));
+    reader.set_max_depth(0);
+    reader.skip_value().unwrap();
+    assert!(reader.is_empty());
+
+    let mut tokens = MsgpackReader::new(BufView::wrap(&[0x01, 0xc1, 0x02]));
+    assert_eq!(tokens.next(), Some(Ok(Token::UInt(1))));
+    assert!(tokens.next().unwrap().is_err());
+    assert_eq!((tokens.next(), tokens.offset()), (None, 1));
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "net")]
+
+use buf_view::net::*;
+use buf_view::{BufView, BufViewMut, ErrorKind, MacAddr};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[test]
+fn test_net_build_parse() {
+    let mac_a = MacAddr::new([2, 0, 0, 0, 0, 1]);
+    let mac_b = MacAddr::new([2, 0, 0, 0, 0, 2]);
+    let (src, dst) = (
+        Ipv4Addr::new(192, 168, 1, 10),
+        Ipv4Addr::new(93, 184, 216, 34),
+    );
+    // MSS 1460, SACK permitted, timestamps
+    let tcp_options = [2, 4, 0x05, 0xb4, 4, 2, 8, 10, 0, 0, 0, 1, 0, 0, 0, 0];
+
+    let mut buf = [0u8; 128];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    EthernetBuilder::new(mac_b, mac_a, ether_type::VLAN).write(&mut buf_view);
+    VlanBuilder {
+        pcp: 5,
+        dei: false,
+        vid: 100,
+        ether_type: ether_type::IPV4,
+    }
+    .write(&mut buf_view);
+    let mut ip = Ipv4Builder::new(src, dst, ip_proto::TCP);
+    // router alert, padded with an end of list byte
+    ip.options = &[0x94, 4, 0, 0, 1, 0, 0];
+    let ip_start = ip.write(&mut buf_view);
+    let mut tcp = TcpBuilder::new(49152, 80, 1000, TcpHeader::SYN | TcpHeader::ACK);
+    tcp.ack = 2000;
+    tcp.options = &tcp_options;
+    let tcp_start = tcp.write(&mut buf_view);
+    buf_view.write_bytes(b"GET /");
+    TcpBuilder::finish_v4(&mut buf_view, tcp_start, src, dst).unwrap();
+    Ipv4Builder::finish(&mut buf_view, ip_start).unwrap();
+    let len = buf_view.writer_index();
+    assert_eq!(len, 14 + 4 + 28 + 36 + 5);
+
+    let mut buf_view = BufView::wrap(&buf[..len]);
+    let eth = buf_view.read_header::<EthernetHeader>().unwrap();
+    assert_eq!((eth.dst(), eth.src()), (mac_b, mac_a));
+    assert_eq!(eth.ether_type(), ether_type::VLAN);
+    let vlan = buf_view.read_header::<VlanTag>().unwrap();
+    assert_eq!((vlan.pcp(), vlan.dei(), vlan.vid()), (5, false, 100));
+    assert_eq!(vlan.ether_type(), ether_type::IPV4);
+
+    let ip = buf_view.read_header::<Ipv4Header>().unwrap();
+    assert_eq!((ip.version(), ip.ihl(), ip.header_len()), (4, 7, 28));
+    assert_eq!((ip.total_length(), ip.payload_len()), (69, 41));
+    assert!(ip.dont_fragment() && !ip.more_fragments());
+    assert_eq!((ip.ttl(), ip.protocol()), (64, ip_proto::TCP));
+    assert_eq!((ip.src(), ip.dst()), (src, dst));
+    assert!(ip.verify_checksum());
+    let options: Vec<_> = ip.options().collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        options,
+        [RawOption {
+            kind: 0x94,
+            data: &[0, 0]
+        }]
+    );
+
+    let tcp = buf_view.read_header::<TcpHeader>().unwrap();
+    assert_eq!((tcp.src_port(), tcp.dst_port()), (49152, 80));
+    assert_eq!((tcp.seq(), tcp.ack()), (1000, 2000));
+    assert_eq!((tcp.data_offset(), tcp.header_len()), (9, 36));
+    assert!(tcp.has_flags(TcpHeader::SYN | TcpHeader::ACK) && !tcp.has_flags(TcpHeader::FIN));
+    assert_eq!(tcp.window(), 0xffff);
+    assert_eq!(tcp.mss(), Some(1460));
+    assert!(tcp.sack_permitted());
+    assert_eq!(tcp.timestamps(), Some((1, 0)));
+    assert_eq!(tcp.window_scale(), None);
+    assert_eq!(tcp.options().count(), 3);
+    let payload = &buf[buf_view.reader_index()..len];
+    assert_eq!(payload, b"GET /");
+    assert!(tcp.verify_checksum_v4(src, dst, payload));
+    assert!(!tcp.verify_checksum_v4(src, dst, b"GET /x"));
+
+    buf[14 + 4 + 8] -= 1;
+    assert!(!Ipv4Header::parse(&buf[18..]).unwrap().verify_checksum());
+}
+
+#[test]
+fn test_net_v6_udp_icmp_arp() {
+    let src: Ipv6Addr = "fe80::1".parse().unwrap();
+    let dst: Ipv6Addr = "ff02::fb".parse().unwrap();
+
+    let mut buf = [0u8; 128];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut ip = Ipv6Builder::new(src, dst, ip_proto::UDP);
+    ip.flow_label = 0xabcde;
+    ip.traffic_class = 0x12;
+    let ip_start = ip.write(&mut buf_view);
+    let udp_start = UdpBuilder::new(5353, 5353).write(&mut buf_view);
+    buf_view.write_bytes(&[0; 12]);
+    UdpBuilder::finish_v6(&mut buf_view, udp_start, src, dst).unwrap();
+    Ipv6Builder::finish(&mut buf_view, ip_start).unwrap();
+    let icmp_start = IcmpBuilder::echo(IcmpHeader::V6_ECHO_REQUEST, 7, 1).write(&mut buf_view);
+    buf_view.write_bytes(b"ping");
+    IcmpBuilder::finish_v6(&mut buf_view, icmp_start, src, dst).unwrap();
+    let icmp4_start = IcmpBuilder::echo(IcmpHeader::ECHO_REQUEST, 7, 2).write(&mut buf_view);
+    buf_view.write_bytes(b"pong!");
+    IcmpBuilder::finish(&mut buf_view, icmp4_start);
+    ArpBuilder {
+        oper: ArpPacket::REQUEST,
+        sender_mac: MacAddr::new([2, 0, 0, 0, 0, 1]),
+        sender_ip: Ipv4Addr::new(10, 0, 0, 1),
+        target_mac: MacAddr::default(),
+        target_ip: Ipv4Addr::new(10, 0, 0, 2),
+    }
+    .write(&mut buf_view);
+    let len = buf_view.writer_index();
+
+    let mut buf_view = BufView::wrap(&buf[..len]);
+    let ip = buf_view.read_header::<Ipv6Header>().unwrap();
+    assert_eq!(
+        (ip.version(), ip.traffic_class(), ip.flow_label()),
+        (6, 0x12, 0xabcde)
+    );
+    assert_eq!(
+        (ip.payload_length(), ip.next_header(), ip.hop_limit()),
+        (20, ip_proto::UDP, 64)
+    );
+    assert_eq!((ip.src(), ip.dst()), (src, dst));
+    let udp = buf_view.read_header::<UdpHeader>().unwrap();
+    assert_eq!((udp.length(), udp.payload_len()), (20, 12));
+    assert_ne!(udp.checksum(), 0);
+    assert!(udp.verify_checksum_v6(src, dst, &buf[48..60]));
+    assert!(!udp.verify_checksum_v6(dst, Ipv6Addr::LOCALHOST, &buf[48..60]));
+    buf_view.set_reader_index(60);
+
+    let icmp = buf_view.read_header::<IcmpHeader>().unwrap();
+    assert_eq!(
+        (icmp.icmp_type(), icmp.identifier(), icmp.sequence()),
+        (128, 7, 1)
+    );
+    assert!(icmp.verify_checksum_v6(src, dst, b"ping"));
+    assert!(!icmp.verify_checksum(b"ping"));
+    buf_view.read_array::<4>();
+    let icmp = buf_view.read_header::<IcmpHeader>().unwrap();
+    assert!(icmp.verify_checksum(b"pong!"));
+    buf_view.read_array::<5>();
+
+    let arp = buf_view.read_header::<ArpPacket>().unwrap();
+    assert_eq!(arp.header_len(), 28);
+    assert!(arp.is_ethernet_ipv4());
+    assert_eq!(arp.oper(), ArpPacket::REQUEST);
+    assert_eq!(arp.sender_ipv4(), Some(Ipv4Addr::new(10, 0, 0, 1)));
+    assert_eq!(arp.target_ipv4(), Some(Ipv4Addr::new(10, 0, 0, 2)));
+    assert_eq!(arp.target_mac(), Some(MacAddr::default()));
+    assert_eq!(arp.sender_hw_addr(), &[2, 0, 0, 0, 0, 1]);
+    assert_eq!(buf_view.remaining(), 0);
+}
+
+#[test]
+fn test_net_validation() {
+    // the example header of RFC 1071 style textbooks, checksum 0xb861
+    let header = [
+        0x45, 0x00, 0x00, 0x73, 0x00, 0x00, 0x40, 0x00, 0x40, 0x11, 0xb8, 0x61, 0xc0, 0xa8, 0x00,
+        0x01, 0xc0, 0xa8, 0x00, 0xc7,
+    ];
+    let ip = Ipv4Header::parse(&header).unwrap();
+    assert!(ip.verify_checksum());
+    assert_eq!(ip.protocol(), ip_proto::UDP);
+    assert_eq!(ip.options().next(), None);
+
+    let mut bad = header;
+    bad[0] = 0x44;
+    let err = Ipv4Header::parse(&bad).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("IHL below 5"));
+    bad[0] = 0x65;
+    assert!(Ipv4Header::parse(&bad).is_err());
+    bad[0] = 0x46;
+    let err = Ipv4Header::parse(&bad).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::OutOfBounds {
+            needed: 24,
+            available: 20
+        }
+    );
+    bad[0] = 0x45;
+    bad[3] = 19;
+    assert!(Ipv4Header::parse(&bad).is_err());
+    assert!(Ipv6Header::parse(&[0x40; 40]).is_err());
+
+    // read_header errors have absolute offsets and consume nothing
+    let mut buf = [0u8; 30];
+    buf[10..30].copy_from_slice(&header);
+    buf[22] = 0x40;
+    let mut buf_view = BufView::wrap(&buf);
+    buf_view.set_reader_index(14);
+    let err = buf_view.read_header::<TcpHeader>().unwrap_err();
+    assert_eq!(err.offset(), 14);
+    assert_eq!(buf_view.reader_index(), 14);
+    buf_view.set_reader_index(10);
+    let err = buf_view.read_header::<TcpHeader>().unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("TCP data offset below 5"));
+    assert_eq!(err.offset(), 22);
+    assert!(UdpHeader::parse(&[0, 1, 0, 2, 0, 7, 0, 0]).is_err());
+    assert!(ArpPacket::parse(&[0, 1, 8, 0, 6, 4, 0, 1, 0]).is_err());
+
+    // a TCP header with a truncated option and one with a bad option length
+    let mut tcp = [0u8; 24];
+    tcp[12] = 0x60;
+    tcp[20..].copy_from_slice(&[1, 1, 1, 2]);
+    let header = TcpHeader::parse(&tcp).unwrap();
+    let mut options = header.options();
+    assert_eq!(
+        options.next().unwrap().unwrap_err().kind(),
+        &ErrorKind::Invalid("truncated option")
+    );
+    assert!(options.next().is_none());
+    tcp[20..].copy_from_slice(&[2, 5, 0, 0]);
+    let header = TcpHeader::parse(&tcp).unwrap();
+    let err = header.options().next().unwrap().unwrap_err();
+    assert_eq!(
+        (err.kind(), err.offset()),
+        (&ErrorKind::Invalid("bad option length"), 20)
+    );
+    assert_eq!(header.mss(), None);
+}
@@ -0,0 +1,166 @@
+#![cfg(feature = "pcap")]
+
+use buf_view::pcap::ng::{opt, Block, PcapNgReader, PcapNgWriter};
+use buf_view::pcap::{link_type, PcapHeader, PcapReader, TsResolution};
+use buf_view::{BufView, BufViewMut, Endianness, ErrorKind};
+use std::time::Duration;
+
+#[test]
+fn test_pcap() {
+    // a big endian nanosecond file with one packet, then a truncated record
+    let mut file = vec![0xa1, 0xb2, 0x3c, 0x4d, 0, 2, 0, 4];
+    file.extend_from_slice(&[0; 8]);
+    file.extend_from_slice(&[0, 0, 0xff, 0xff, 0, 0, 0, 101]);
+    file.extend_from_slice(&[0, 0, 0, 3, 0x3b, 0x9a, 0xc9, 0xff, 0, 0, 0, 2, 0, 0, 0, 9]);
+    file.extend_from_slice(&[0x45, 0x00]);
+    file.extend_from_slice(&[0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 8]);
+
+    let mut reader = PcapReader::new(BufView::wrap(&file)).unwrap();
+    let header = reader.header();
+    assert_eq!(header.endianness, Endianness::Big);
+    assert_eq!(header.resolution, TsResolution::Nanos);
+    assert_eq!((header.snaplen, header.link_type), (65535, link_type::RAW));
+    let mut packet = reader.next().unwrap().unwrap();
+    assert_eq!(packet.timestamp(), Duration::new(3, 999_999_999));
+    assert_eq!((packet.orig_len, packet.data.remaining()), (9, 2));
+    assert_eq!(packet.data.reader_index(), 40);
+    assert_eq!(packet.data.read_u8(), 0x45);
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(err.offset(), 42);
+    assert!(reader.next().is_none());
+
+    file[0] = 0;
+    let err = PcapReader::new(BufView::wrap(&file)).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("bad pcap magic"));
+
+    let mut buf = [0u8; 50];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut header = PcapHeader::new(link_type::ETHERNET);
+    header.resolution = TsResolution::Nanos;
+    header.write(&mut buf_view).unwrap();
+    let ts = Duration::new(1_700_000_000, 123);
+    header.write_packet(&mut buf_view, ts, 4, b"abcd").unwrap();
+    let err = header
+        .write_packet(&mut buf_view, Duration::from_secs(1 << 32), 0, b"")
+        .unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Overflow);
+    assert!(header
+        .write_packet(&mut buf_view, ts, 8, b"too long")
+        .is_err());
+    assert_eq!(buf_view.writer_index(), 44);
+    assert_eq!(&buf[..4], &[0x4d, 0x3c, 0xb2, 0xa1]);
+
+    let mut reader = PcapReader::new(BufView::wrap(&buf[..44])).unwrap();
+    assert_eq!(reader.header(), &header);
+    assert_eq!(reader.next().unwrap().unwrap().timestamp(), ts);
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_pcapng() {
+    let mut buf = [0u8; 256];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let writer = PcapNgWriter::new(Endianness::Little);
+    writer.write_section_header(&mut buf_view, &[]).unwrap();
+    writer
+        .write_interface(
+            &mut buf_view,
+            link_type::ETHERNET as u16,
+            3,
+            &[(opt::IF_NAME, b"eth0"), (opt::IF_TSRESOL, &[9])],
+        )
+        .unwrap();
+    let ts = 1_700_000_000_123_456_789u64;
+    writer
+        .write_enhanced_packet(&mut buf_view, 0, ts, 3, b"abc", &[(opt::COMMENT, b"hi")])
+        .unwrap();
+    writer
+        .write_simple_packet(&mut buf_view, 6, b"abc")
+        .unwrap();
+    // a second section, big endian, with an unknown block
+    let second = buf_view.writer_index();
+    let writer = PcapNgWriter::new(Endianness::Big);
+    writer.write_section_header(&mut buf_view, &[]).unwrap();
+    buf_view.write_bytes(&[0, 0, 0, 9, 0, 0, 0, 16, 1, 2, 3, 4, 0, 0, 0, 16]);
+    let len = buf_view.writer_index();
+    assert_eq!(&buf[second + 8..second + 12], &[0x1a, 0x2b, 0x3c, 0x4d]);
+
+    let blocks: Vec<_> = PcapNgReader::new(BufView::wrap(&buf[..len]))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(blocks.len(), 6);
+    match &blocks[1] {
+        Block::Interface(idb) => {
+            assert_eq!((idb.snaplen, idb.tsresol), (3, 9));
+            let name = idb.options.find(opt::IF_NAME).unwrap();
+            assert_eq!(name.as_str(), Some("eth0"));
+            assert_eq!(idb.options.into_iter().count(), 2);
+        }
+        _ => panic!("not an interface"),
+    }
+    match &blocks[2] {
+        Block::EnhancedPacket(epb) => {
+            assert_eq!(epb.timestamp(), Duration::new(1_700_000_000, 123_456_789));
+            assert_eq!(epb.data.remaining(), 3);
+            assert_eq!(epb.options.find(opt::COMMENT).unwrap().value, b"hi");
+        }
+        _ => panic!("not an enhanced packet"),
+    }
+    match &blocks[3] {
+        // truncated to the snapshot length
+        Block::SimplePacket(spb) => assert_eq!((spb.orig_len, spb.data.remaining()), (6, 3)),
+        _ => panic!("not a simple packet"),
+    }
+    match &blocks[4] {
+        Block::Section(shb) => assert_eq!(shb.endianness, Endianness::Big),
+        _ => panic!("not a section header"),
+    }
+    match &blocks[5] {
+        Block::Other { block_type, body } => assert_eq!((*block_type, body.remaining()), (9, 4)),
+        _ => panic!("not an unknown block"),
+    }
+
+    // the trailing length disagrees
+    buf[len - 1] = 20;
+    let mut reader = PcapNgReader::new(BufView::wrap(&buf[..len]));
+    let err = reader.find_map(|b| b.err()).unwrap();
+    assert_eq!(err.kind(), &ErrorKind::Invalid("bad pcapng block length"));
+    assert_eq!(err.offset(), len - 4);
+    assert!(reader.next().is_none());
+
+    // packets need an interface in their section
+    let mut with_packet = buf[..28].to_vec();
+    with_packet.extend_from_slice(&buf[second - 20..second]);
+    let mut reader = PcapNgReader::new(BufView::wrap(&with_packet));
+    assert!(reader.next().unwrap().is_ok());
+    let err = reader.next().unwrap().unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::Invalid("pcapng packet for an unknown interface")
+    );
+}
+
+#[test]
+fn test_pcapng_failed_write() {
+    let mut buf = [0u8; 72];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let writer = PcapNgWriter::new(Endianness::Little);
+    writer.write_section_header(&mut buf_view, &[]).unwrap();
+    writer
+        .write_interface(&mut buf_view, link_type::ETHERNET as u16, 0, &[])
+        .unwrap();
+    let err = writer
+        .write_enhanced_packet(&mut buf_view, 0, 0, 3, b"abc", &[])
+        .unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+    assert_eq!(buf_view.writer_index(), 48);
+    writer.write_simple_packet(&mut buf_view, 0, b"").unwrap();
+    let len = buf_view.writer_index();
+    assert_eq!(len, 64);
+
+    let blocks: Vec<_> = PcapNgReader::new(BufView::wrap(&buf[..len]))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(blocks.len(), 3);
+    assert!(matches!(blocks[2], Block::SimplePacket(_)));
+}
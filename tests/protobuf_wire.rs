@@ -0,0 +1,138 @@
+#![cfg(feature = "protobuf_wire")]
+
+use buf_view::protobuf_wire::{varint_len, ProtobufWriter, Value, WireType};
+use buf_view::{BufView, BufViewMut, ErrorKind};
+
+#[test]
+fn test_protobuf_round_trip() {
+    let mut buf = [0u8; 512];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut writer = ProtobufWriter::new(&mut buf_view);
+    writer.write_i32(1, -2).unwrap();
+    writer.write_sint(2, -2).unwrap();
+    writer.write_bool(3, true).unwrap();
+    writer.write_fixed64(4, u64::MAX - 1).unwrap();
+    writer.write_f32(5, 1.5).unwrap();
+    let outer = writer.begin(6).unwrap();
+    // a 200 bytes value, the outer length grows to 2 bytes and the content moves
+    writer.write_bytes(1, &[0xab; 200]).unwrap();
+    writer.write_packed_fixed(2, &[1.0f64, -2.0]).unwrap();
+    writer.end(outer).unwrap();
+    writer.begin_group(7).unwrap();
+    writer.write_f64(1, 0.25).unwrap();
+    writer.end_group(7).unwrap();
+    assert_eq!(
+        writer.write_varint(1 << 29, 0).unwrap_err().kind(),
+        &ErrorKind::Invalid("bad protobuf field number")
+    );
+    let len = buf_view.writer_index();
+    assert_eq!(
+        &buf[..12],
+        &[0x08, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, 0x10]
+    );
+    assert_eq!(varint_len(u64::MAX), 10);
+    assert_eq!((varint_len(0), varint_len(127), varint_len(128)), (1, 1, 2));
+
+    let fields: Vec<_> = BufView::wrap(&buf[..len])
+        .protobuf_fields()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(fields.len(), 7);
+    let numbers: Vec<_> = fields.iter().map(|f| (f.number, f.wire_type())).collect();
+    assert_eq!(numbers[4], (5, WireType::I32));
+    assert_eq!(numbers[6], (7, WireType::StartGroup));
+    assert_eq!(fields[0].as_i32(), Ok(-2));
+    assert_eq!(fields[0].as_u64(), Ok(u64::MAX - 1));
+    assert_eq!(fields[1].as_sint(), Ok(-2));
+    assert_eq!(fields[2].as_bool(), Ok(true));
+    assert_eq!(fields[3].as_fixed64(), Ok(u64::MAX - 1));
+    assert_eq!(fields[4].as_f32(), Ok(1.5));
+    assert_eq!(
+        fields[4].as_u64().unwrap_err().kind(),
+        &ErrorKind::Invalid("unexpected protobuf wire type")
+    );
+
+    let outer = fields[5].as_view().unwrap();
+    assert_eq!(
+        (outer.reader_index(), outer.remaining()),
+        (fields[5].offset() + 3, 221)
+    );
+    let mut inner = fields[5].as_message().unwrap();
+    let bytes = inner.next().unwrap().unwrap();
+    assert_eq!(bytes.as_bytes().unwrap(), &[0xab; 200][..]);
+    assert!(bytes.as_str().is_err());
+    let packed = inner.next().unwrap().unwrap();
+    let doubles: Vec<f64> = packed.packed_fixed().unwrap().collect();
+    assert_eq!(doubles, [1.0, -2.0]);
+    assert!(packed.packed_fixed::<u32>().is_ok());
+    assert!(inner.next().is_none());
+
+    let mut group = fields[6].as_group().unwrap();
+    match group.next().unwrap().unwrap().value {
+        Value::I64(bits) => assert_eq!(f64::from_bits(bits), 0.25),
+        _ => panic!("not an I64 value"),
+    }
+    assert!(group.next().is_none());
+}
+
+#[test]
+fn test_protobuf_errors() {
+    let err = |bytes: &[u8]| {
+        let mut fields = BufView::wrap(bytes).protobuf_fields();
+        let err = fields.find_map(|f| f.err()).unwrap();
+        assert!(fields.next().is_none());
+        (err.kind().clone(), err.offset())
+    };
+    let invalid = |msg| ErrorKind::Invalid(msg);
+    assert_eq!(
+        err(&[0x08, 1, 0x00, 1]),
+        (invalid("bad protobuf field number"), 2)
+    );
+    assert_eq!(err(&[0x0e, 1]), (invalid("bad protobuf wire type"), 0));
+    assert_eq!(err(&[0x0c]), (invalid("mismatched protobuf end group"), 0));
+    assert_eq!(
+        err(&[0x0b, 0x08, 1, 0x14]),
+        (invalid("mismatched protobuf end group"), 3)
+    );
+    assert_eq!(
+        err(&[0x0b, 0x08, 1]),
+        (invalid("unterminated protobuf group"), 0)
+    );
+    let mut overflow = vec![0x08];
+    overflow.extend_from_slice(&[0xff; 9]);
+    overflow.push(0x82);
+    assert_eq!(err(&overflow), (ErrorKind::Overflow, 1));
+    assert_eq!(
+        err(&[0x08, 1, 0x12, 5, 0, 0]),
+        (
+            ErrorKind::OutOfBounds {
+                needed: 5,
+                available: 2
+            },
+            4
+        )
+    );
+    let nested = [[0x0b; 65], [0x0c; 65]].concat();
+    assert_eq!(err(&nested), (invalid("protobuf nesting too deep"), 64));
+    assert!(BufView::wrap(&nested[1..129])
+        .protobuf_fields()
+        .all(|f| f.is_ok()));
+
+    let mut buf_view = BufView::wrap(&[0x80, 0x80]);
+    assert!(buf_view.try_read_varint().is_err());
+    assert_eq!(buf_view.reader_index(), 0);
+    let packed = [0x0a, 3, 1, 0x80, 0x80];
+    let field = BufView::wrap(&packed)
+        .protobuf_fields()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        field.packed_fixed::<u32>().unwrap_err().kind(),
+        &invalid("bad protobuf packed length")
+    );
+    let mut varints = field.packed_varints().unwrap();
+    assert_eq!(varints.next(), Some(Ok(1)));
+    assert!(varints.next().unwrap().is_err());
+    assert!(varints.next().is_none());
+}
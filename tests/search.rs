@@ -0,0 +1,71 @@
+use buf_view::BufView;
+
+fn naive_find(hay: &[u8], needle: &[u8]) -> Option<usize> {
+    (0..=hay.len().checked_sub(needle.len())?).find(|i| &hay[*i..*i + needle.len()] == needle)
+}
+
+fn naive_rfind(hay: &[u8], needle: &[u8]) -> Option<usize> {
+    (0..=hay.len().checked_sub(needle.len())?)
+        .rev()
+        .find(|i| &hay[*i..*i + needle.len()] == needle)
+}
+
+#[test]
+fn test_find() {
+    let hay: Vec<u8> = (0..100u32).map(|i| (i * 7 % 13) as u8 + 0x7c).collect();
+    for start in [0, 1, 5, 9, 50] {
+        let buf_view = BufView::wrap_with(&hay, start, hay.len() - 3);
+        let window = &hay[start..hay.len() - 3];
+        for b in 0x78..0x90u8 {
+            let pos = window.iter().position(|x| *x == b).map(|i| i + start);
+            let rpos = window.iter().rposition(|x| *x == b).map(|i| i + start);
+            assert_eq!(buf_view.find_u8(b), pos);
+            assert_eq!(buf_view.rfind_u8(b), rpos);
+        }
+        for needle in [
+            &hay[10..13],
+            &hay[40..48],
+            &hay[95..97],
+            &[0x7c, 0x7c][..],
+            &[][..],
+        ] {
+            let pos = naive_find(window, needle).map(|i| i + start);
+            let rpos = naive_rfind(window, needle).map(|i| i + start);
+            assert_eq!(buf_view.find(needle), pos);
+            assert_eq!(buf_view.rfind(needle), rpos);
+        }
+    }
+
+    // a 0x01 byte above a match must not be reported by the word search
+    let hay = [0x80u8, 0x01, 0x00, 0x01, 0x81, 0x00, 0x00, 0x01, 0x01];
+    let buf_view = BufView::wrap(&hay);
+    assert_eq!(buf_view.find_u8(0x00), Some(2));
+    assert_eq!(buf_view.rfind_u8(0x00), Some(6));
+    assert_eq!(buf_view.rfind_u8(0x80), Some(0));
+    assert_eq!(buf_view.find(&[0x00, 0x01]), Some(2));
+    assert_eq!(buf_view.rfind(&[0x00, 0x01]), Some(6));
+}
+
+#[test]
+fn test_read_until() {
+    let buf = b"*2\r\n$3\r\nGET\r\n$3\r\nkey\nrest";
+    let mut buf_view = BufView::wrap(buf);
+
+    assert_eq!(buf_view.read_line(), Some(&b"*2"[..]));
+    assert_eq!(buf_view.read_until(b'\r'), Some(&b"$3"[..]));
+    assert_eq!(buf_view.read_u8(), b'\n');
+    assert_eq!(buf_view.read_line(), Some(&b"GET"[..]));
+    buf_view.read_line();
+    assert_eq!(buf_view.read_line(), Some(&b"key"[..]));
+    let index = buf_view.reader_index();
+    assert_eq!(buf_view.read_line(), None);
+    assert_eq!(buf_view.read_until(b'x'), None);
+    assert_eq!(buf_view.reader_index(), index);
+
+    let mut buf_view = BufView::wrap(b"   \t 42 ");
+    assert_eq!(buf_view.skip_while(|b| b.is_ascii_whitespace()), 5);
+    assert_eq!(buf_view.read_u8(), b'4');
+    assert_eq!(buf_view.skip_while(|b| b.is_ascii_digit()), 1);
+    assert_eq!(buf_view.skip_while(|_| true), 1);
+    assert_eq!(buf_view.remaining(), 0);
+}
@@ -0,0 +1,44 @@
+use buf_view::{BufViewMut, SizeCounter, ViewWrite};
+
+fn encode<W: ViewWrite>(w: &mut W, items: &[u32]) {
+    let len_index = w.writer_index();
+    w.write_u16(0);
+    for item in items {
+        w.write_u32_le(*item);
+    }
+    w.write_f64(1.5);
+    let len = (w.writer_index() - len_index) as u16;
+    w.set_u16(len_index, len);
+}
+
+#[test]
+fn test_size_counter() {
+    let items = [1, 2, 3];
+
+    let mut counter = SizeCounter::new();
+    encode(&mut counter, &items);
+    assert_eq!(counter.size(), 22);
+    assert_eq!(counter.writer_index(), 22);
+
+    let mut buf = vec![0u8; counter.size()];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    encode(&mut buf_view, &items);
+    assert_eq!(buf_view.writer_index(), 22);
+    assert_eq!(buf_view.read_u16(), 22);
+    assert_eq!(buf_view.read_u32_le(), 1);
+    assert_eq!(buf_view.read_u32_le(), 2);
+    assert_eq!(buf_view.read_u32_le(), 3);
+    assert_eq!(buf_view.read_f64(), 1.5);
+
+    let mut counter = SizeCounter::new();
+    counter.write_u8(1);
+    counter.set_u32(10, 0);
+    assert_eq!(counter.writer_index(), 1);
+    assert_eq!(counter.size(), 14);
+
+    counter.set_writer_index(4);
+    counter.write_u16(0);
+    assert_eq!(counter.size(), 14);
+    counter.clear();
+    assert_eq!(counter.size(), 0);
+}
@@ -0,0 +1,145 @@
+use buf_view::time::{DosDateTime, FileTime, NtpTimestamp, PtpTimestamp};
+use buf_view::{BufView, BufViewMut, ErrorKind};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn unix(secs: i64, nanos: u32) -> SystemTime {
+    if secs >= 0 {
+        UNIX_EPOCH + Duration::new(secs as u64, nanos)
+    } else {
+        UNIX_EPOCH - Duration::new(secs.unsigned_abs(), 0) + Duration::new(0, nanos)
+    }
+}
+
+#[test]
+fn test_unix_time() {
+    let t = unix(1_234_567_890, 999_000_000);
+    let mut buf = [0u8; 40];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_unix_time32(t).unwrap();
+    buf_view.write_unix_time32_le(t).unwrap();
+    buf_view.write_unix_time64(unix(-1, 500_000_000)).unwrap();
+    buf_view.write_unix_millis64(t).unwrap();
+    buf_view
+        .write_unix_millis64_le(unix(-1, 500_000_000))
+        .unwrap();
+    buf_view
+        .write_unix_time64_le(unix(i64::from(u32::MAX) + 1, 0))
+        .unwrap();
+    assert_eq!(buf_view.writer_index(), 40);
+
+    let err = buf_view.set_unix_time32(0, unix(-1, 0)).unwrap_err();
+    assert_eq!(err.kind(), &ErrorKind::Overflow);
+    assert!(buf_view.set_unix_time32(0, unix(1 << 32, 0)).is_err());
+    assert!(buf_view.set_unix_time64(36, t).is_err());
+
+    assert_eq!(&buf[..4], &[0x49, 0x96, 0x02, 0xd2]);
+    assert_eq!(&buf[8..16], &(-1i64).to_be_bytes());
+    assert_eq!(&buf[16..24], &1_234_567_890_999i64.to_be_bytes());
+    assert_eq!(&buf[24..32], &(-500i64).to_le_bytes());
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.read_unix_time32(), unix(1_234_567_890, 0));
+    assert_eq!(buf_view.read_unix_time32_le(), unix(1_234_567_890, 0));
+    assert_eq!(buf_view.read_unix_time64(), Ok(unix(-1, 0)));
+    assert_eq!(buf_view.read_unix_millis64(), Ok(t));
+    assert_eq!(buf_view.read_unix_millis64_le(), Ok(unix(-1, 500_000_000)));
+    assert_eq!(buf_view.read_unix_time64_le(), Ok(unix(1 << 32, 0)));
+
+    let buf = [0u8; 7];
+    let mut buf_view = BufView::wrap(&buf);
+    let err = buf_view.read_unix_millis64().unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::OutOfBounds { .. }));
+    assert_eq!(buf_view.reader_index(), 0);
+}
+
+#[test]
+fn test_raw_timestamps() {
+    // 2000-01-01 00:00:00.5
+    let y2k = unix(946_684_800, 500_000_000);
+    let ntp = NtpTimestamp::from_system_time(y2k).unwrap();
+    assert_eq!(
+        ntp,
+        NtpTimestamp {
+            seconds: 0xbc17_c200,
+            fraction: 0x8000_0000
+        }
+    );
+    assert_eq!(ntp.to_system_time(), Some(y2k));
+    // era 1, the seconds wrapped in 2036
+    let t = unix((1 << 32) - 2_208_988_800 + 5, 0);
+    let ntp = NtpTimestamp::from_system_time(t).unwrap();
+    assert_eq!(ntp.seconds, 5);
+    assert_eq!(ntp.to_system_time(), Some(t));
+    assert_eq!(NtpTimestamp::from_system_time(unix(-100_000_000, 0)), None);
+    let d = Duration::new(7, 123_456_789);
+    assert_eq!(NtpTimestamp::from_duration(d).unwrap().to_duration(), d);
+
+    let ptp = PtpTimestamp::from_system_time(y2k).unwrap();
+    assert_eq!((ptp.seconds, ptp.nanoseconds), (946_684_800, 500_000_000));
+    assert_eq!(ptp.to_system_time(), Some(y2k));
+    assert_eq!(
+        PtpTimestamp {
+            seconds: 0,
+            nanoseconds: 1_000_000_000
+        }
+        .to_system_time(),
+        None
+    );
+    assert_eq!(PtpTimestamp::from_system_time(unix(-1, 0)), None);
+
+    let ft = FileTime::from_system_time(y2k).unwrap();
+    assert_eq!(ft, FileTime(125_911_584_005_000_000));
+    assert_eq!(ft.to_system_time(), Some(y2k));
+    assert_eq!(FileTime(0).to_system_time(), Some(unix(-11_644_473_600, 0)));
+    assert_eq!(FileTime::from_system_time(unix(-11_644_473_601, 0)), None);
+
+    let dos = DosDateTime::from_system_time(unix(951_827_999, 0)).unwrap();
+    // 2000-02-29 12:39:59, the odd second rounds down
+    assert_eq!((dos.year(), dos.month(), dos.day()), (2000, 2, 29));
+    assert_eq!((dos.hour(), dos.minute(), dos.second()), (12, 39, 58));
+    assert_eq!(dos.to_system_time(), Some(unix(951_827_998, 0)));
+    assert_eq!(
+        DosDateTime {
+            date: 0x0021,
+            time: 0
+        }
+        .to_system_time(),
+        Some(unix(315_532_800, 0))
+    );
+    // 1981-02-29 does not exist, nor does a zero month
+    assert_eq!(
+        DosDateTime {
+            date: 0x025d,
+            time: 0
+        }
+        .to_system_time(),
+        None
+    );
+    assert_eq!(
+        DosDateTime {
+            date: 0x0001,
+            time: 0
+        }
+        .to_system_time(),
+        None
+    );
+    assert_eq!(DosDateTime::from_system_time(unix(0, 0)), None);
+
+    let mut buf = [0u8; 30];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_ntp_timestamp(ntp);
+    buf_view.write_ptp_timestamp(ptp);
+    buf_view.write_filetime(ft);
+    buf_view.write_dos_date_time(dos);
+    assert_eq!(buf_view.writer_index(), 30);
+    assert_eq!(&buf[8..14], &[0, 0, 0x38, 0x6d, 0x43, 0x80]);
+    assert_eq!(&buf[26..], &[0xfd, 0x64, 0x5d, 0x28]);
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.read_ntp_timestamp(), ntp);
+    assert_eq!(buf_view.read_ptp_timestamp(), ptp);
+    assert_eq!(buf_view.read_filetime(), ft);
+    assert_eq!(buf_view.read_dos_date_time(), dos);
+    assert_eq!(buf_view.get_ptp_timestamp(8), ptp);
+    assert_eq!(buf_view.get_dos_date_time(26), dos);
+}
@@ -0,0 +1,99 @@
+use buf_view::{
+    BufView, BufViewMut, Endianness, ErrorKind, LengthFormat, TagFormat, TlvFormat, TlvWriter,
+};
+
+#[test]
+fn test_ber_tlv() {
+    let format = TlvFormat::ber();
+    let mut buf = [0u8; 300];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut writer = TlvWriter::new(&mut buf_view, format);
+    let template = writer.begin(0x70).unwrap();
+    writer.write(0x9f02, &[0, 0, 0, 0, 1, 0]).unwrap();
+    // a 200 bytes value, the template length grows to 2 bytes and the content moves
+    writer.write(0x5a, &[0xab; 200]).unwrap();
+    writer.end(template).unwrap();
+    let len = buf_view.writer_index();
+    assert_eq!(len, 1 + 2 + 9 + 3 + 200);
+    assert_eq!(&buf[..6], &[0x70, 0x81, 212, 0x9f, 0x02, 6]);
+    assert_eq!(&buf[12..15], &[0x5a, 0x81, 200]);
+
+    let buf_view = BufView::wrap(&buf[..len]);
+    let mut iter = buf_view.tlv_iter(format);
+    let (tag, template) = iter.next().unwrap().unwrap();
+    assert_eq!(
+        (tag, template.reader_index(), template.remaining()),
+        (0x70, 3, 212)
+    );
+    assert!(iter.next().is_none());
+    let items: Vec<_> = template
+        .tlv_iter(format)
+        .map(|item| item.map(|(tag, value)| (tag, value.remaining())))
+        .collect();
+    assert_eq!(items, [Ok((0x9f02, 6)), Ok((0x5a, 200))]);
+
+    let err = BufView::wrap(&[0x5a, 0x80])
+        .tlv_iter(format)
+        .next()
+        .unwrap();
+    assert_eq!(
+        err.unwrap_err().kind(),
+        &ErrorKind::Invalid("indefinite BER length")
+    );
+    let err = BufView::wrap(&[0x9f, 0x81, 0x82, 0x83, 0x04])
+        .tlv_iter(format)
+        .next()
+        .unwrap();
+    assert_eq!(
+        err.unwrap_err().kind(),
+        &ErrorKind::Invalid("BER tag too long")
+    );
+}
+
+#[test]
+fn test_fixed_width_tlv() {
+    let mut format = TlvFormat::new(TagFormat::U16, LengthFormat::U16);
+    format.endianness = Endianness::Little;
+    format.length_includes_header = true;
+
+    let mut buf = [0u8; 16];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    let mut writer = TlvWriter::new(&mut buf_view, format);
+    writer.write(0x0102, b"ab").unwrap();
+    let mark = writer.begin(3).unwrap();
+    writer.view().write_u16(0xbeef);
+    writer.end(mark).unwrap();
+    assert_eq!(
+        writer.write(0x1_0000, b"").unwrap_err().kind(),
+        &ErrorKind::Overflow
+    );
+    assert_eq!(writer.view().writer_index(), 12);
+    assert!(writer.write(4, b"too long").is_err());
+    assert_eq!(&buf[..6], &[0x02, 0x01, 6, 0, b'a', b'b']);
+
+    // a truncated third item after the two valid ones
+    buf[12..16].copy_from_slice(&[5, 0, 9, 0]);
+    let buf_view = BufView::wrap(&buf);
+    let mut iter = buf_view.tlv_iter(format);
+    let (tag, mut value) = iter.next().unwrap().unwrap();
+    assert_eq!((tag, value.read_u16()), (0x0102, 0x6162));
+    let (tag, mut value) = iter.next().unwrap().unwrap();
+    assert_eq!((tag, value.read_u16()), (3, 0xbeef));
+    let err = iter.next().unwrap().unwrap_err();
+    assert_eq!(
+        err.kind(),
+        &ErrorKind::OutOfBounds {
+            needed: 5,
+            available: 0
+        }
+    );
+    assert_eq!(iter.offset(), 12);
+    assert!(iter.next().is_none());
+
+    buf[14] = 3;
+    let err = BufView::wrap(&buf[12..]).tlv_iter(format).next().unwrap();
+    assert_eq!(
+        err.unwrap_err().kind(),
+        &ErrorKind::Invalid("TLV length shorter than its header")
+    );
+}
@@ -0,0 +1,93 @@
+use buf_view::trace::{TraceRecord, TraceValue};
+use buf_view::BufView;
+
+#[test]
+fn test_trace() {
+    // ethertype, then an IPv4 header start with the source address at 14..18
+    let mut buf = [0u8; 18];
+    buf[12..14].copy_from_slice(&[0x08, 0x00]);
+    buf[14..18].copy_from_slice(&[192, 168, 0, 1]);
+
+    let mut view = BufView::wrap(&buf).traced();
+    view.scope("eth", |v| {
+        v.field("dst", |v| {
+            let mut mac = [0u8; 6];
+            v.read_bytes(&mut mac);
+            mac
+        });
+        v.field("src", |v| v.set_reader_index(12));
+        v.field("type", |v| v.read_u16());
+    });
+    let src = view.scope("ip", |v| v.field("src", |v| v.read_u32()));
+    assert_eq!(src, 0xc0a80001);
+    view.view_mut().set_reader_index(0);
+    assert_eq!(view.trace().records().len(), 4);
+
+    let (buf_view, trace) = view.into_parts();
+    assert_eq!(buf_view.reader_index(), 0);
+    assert_eq!(
+        trace.records()[3],
+        TraceRecord {
+            path: "ip.src".to_string(),
+            range: 14..18,
+            value: TraceValue::Unsigned(0xc0a80001),
+        }
+    );
+    assert_eq!(trace.find(13).unwrap().path, "eth.type");
+    assert_eq!(trace.find(8).unwrap().path, "eth.src");
+    assert!(trace.find(18).is_none());
+
+    assert_eq!(
+        trace.to_json(),
+        "[{\"path\":\"eth.dst\",\"start\":0,\"end\":6,\"value\":\"000000000000\"},\
+         {\"path\":\"eth.src\",\"start\":6,\"end\":12,\"value\":null},\
+         {\"path\":\"eth.type\",\"start\":12,\"end\":14,\"value\":2048},\
+         {\"path\":\"ip.src\",\"start\":14,\"end\":18,\"value\":3232235521}]"
+    );
+
+    let dump = trace.annotate(&buf).to_string();
+    assert!(dump.ends_with(
+        "ip.src [0xe..0x12] = 3232235521 (0xc0a80001)\n\
+         00000000                                             c0 a8  |              ..|\n\
+         00000010  00 01                                             |..|\n"
+    ));
+}
+
+#[test]
+fn test_trace_nested_index() {
+    let buf = [2u8, 1, 10, 2, 20, 30];
+    let mut view = BufView::wrap(&buf).traced();
+    let count = view.field("count", |v| v.read_u8());
+    for i in 0..count as usize {
+        view.scope("items", |v| {
+            v.index(i, |v| {
+                let len = v.field("len", |v| v.read_u8());
+                v.scope("data", |v| {
+                    for j in 0..len as usize {
+                        v.index(j, |v| v.field("value", |v| v.read_i8()));
+                    }
+                });
+            })
+        });
+    }
+
+    let paths: Vec<&str> = view
+        .trace()
+        .records()
+        .iter()
+        .map(|r| r.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "count",
+            "items[0].len",
+            "items[0].data[0].value",
+            "items[1].len",
+            "items[1].data[0].value",
+            "items[1].data[1].value",
+        ]
+    );
+    assert_eq!(view.trace().records()[5].value, TraceValue::Signed(30));
+    assert_eq!(view.trace().records()[5].range, 5..6);
+}
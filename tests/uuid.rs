@@ -0,0 +1,55 @@
+use buf_view::{BufView, BufViewMut, Uuid};
+
+#[test]
+fn test_uuid() {
+    let uuid: Uuid = "{6BA7B810-9DAD-11D1-80B4-00C04FD430C8}".parse().unwrap();
+    assert_eq!(uuid.as_u128(), 0x6ba7b810_9dad_11d1_80b4_00c04fd430c8);
+    assert_eq!(uuid, Uuid::from_u128(uuid.as_u128()));
+    assert_eq!(uuid.to_string(), "6ba7b810-9dad-11d1-80b4-00c04fd430c8");
+    assert_eq!(uuid.version(), 1);
+    assert!(Uuid::NIL.is_nil() && !uuid.is_nil());
+    assert_eq!(
+        uuid.to_bytes_mixed_endian()[..8],
+        [0x10, 0xb8, 0xa7, 0x6b, 0xad, 0x9d, 0xd1, 0x11]
+    );
+    assert_eq!(
+        Uuid::from_bytes_mixed_endian(uuid.to_bytes_mixed_endian()),
+        uuid
+    );
+
+    for bad in [
+        "",
+        "6ba7b810-9dad-11d1-80b4-00c04fd430c",
+        "6ba7b810-9dad-11d1-80b4-00c04fd430c8a",
+        "6ba7b8109dad11d180b400c04fd430c8",
+        "6ba7b810-9dad-11d1-80b4+00c04fd430c8",
+        "6ba7b810-9dad-11d1-80b4-00c04fd430cg",
+        "{6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+        "6ba7b810-9dad-11d1-80b4-00c04fd430é",
+    ] {
+        assert!(bad.parse::<Uuid>().is_err(), "{}", bad);
+    }
+
+    let mut buf = [0u8; 56];
+    let mut buf_view = BufViewMut::wrap(&mut buf);
+    buf_view.write_uuid(uuid);
+    buf_view.write_guid_mixed_endian(uuid);
+    buf_view.set_guid_mixed_endian(32, Uuid::from_bytes([0xaa; 16]));
+    buf_view.set_uuid(40, Uuid::NIL);
+    assert_eq!(&buf[..4], &[0x6b, 0xa7, 0xb8, 0x10]);
+    assert_eq!(
+        &buf[16..24],
+        &[0x10, 0xb8, 0xa7, 0x6b, 0xad, 0x9d, 0xd1, 0x11]
+    );
+    assert_eq!(buf[24..32], buf[8..16]);
+
+    let mut buf_view = BufView::wrap(&buf);
+    assert_eq!(buf_view.read_uuid(), uuid);
+    assert_eq!(buf_view.read_guid_mixed_endian(), uuid);
+    assert_eq!(
+        buf_view.get_uuid(16),
+        Uuid::from_bytes(uuid.to_bytes_mixed_endian())
+    );
+    assert_eq!(buf_view.get_guid_mixed_endian(16), uuid);
+    assert_eq!(buf_view.get_uuid(32).as_bytes()[..8], [0xaa; 8]);
+}